@@ -0,0 +1,46 @@
+use shaco::model::ingame::{AllGameData, GameEvent};
+
+#[test]
+fn known_event_with_unknown_field() {
+    let raw = r#"{"EventID": 0, "EventName": "GameStart", "EventTime": 0.0, "SomeNewField": 1}"#;
+
+    let event: GameEvent = serde_json::from_str(raw).unwrap();
+    assert!(matches!(event, GameEvent::GameStart(_)));
+}
+
+#[test]
+fn known_event_with_changed_field() {
+    let raw = r#"{"EventID": 7, "EventName": "DragonKill", "EventTime": 300.5, "DragonType": {"Name": "Fire"}, "KillerName": "summonerName1", "Stolen": "False", "Assisters": []}"#;
+
+    let event: GameEvent = serde_json::from_str(raw).unwrap();
+    let GameEvent::Unknown {
+        name,
+        event_id,
+        error,
+        ..
+    } = &event
+    else {
+        panic!("expected an unknown event");
+    };
+    assert_eq!(name, "DragonKill");
+    assert_eq!(*event_id, 7);
+    assert!(error.is_some());
+}
+
+#[test]
+fn unknown_event_has_no_error() {
+    let raw = r#"{"EventID": 42, "EventName": "SomeFutureEvent", "EventTime": 1234.5}"#;
+
+    let event: GameEvent = serde_json::from_str(raw).unwrap();
+    assert!(matches!(event, GameEvent::Unknown { error: None, .. }));
+}
+
+#[test]
+fn all_game_data_with_unknown_fields() {
+    let mut data: serde_json::Value =
+        serde_json::from_str(include_str!("../../tests/aram_allgamedata1.json")).unwrap();
+    data["gameData"]["someNewField"] = 1.into();
+    data["allPlayers"][0]["someNewField"] = "value".into();
+
+    serde_json::from_value::<AllGameData>(data).unwrap();
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use futures_util::StreamExt;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tokio::{sync::watch, task::JoinHandle};
+
+use crate::{
+    error::LcuWebsocketError,
+    model::ws::{JsonApiEvent, LcuEventPayload, LcuSubscriptionType},
+    rest::RESTClient,
+    ws::LcuWebsocketClient,
+};
+
+/// The current gameflow phase (e.g. `"Lobby"`, `"ChampSelect"`, `"InProgress"`)
+pub const GAMEFLOW_PHASE: &str = "/lol-gameflow/v1/gameflow-phase";
+/// The current lobby
+pub const LOBBY: &str = "/lol-lobby/v2/lobby";
+/// The current champ select session
+pub const CHAMP_SELECT_SESSION: &str = "/lol-champ-select/v1/session";
+/// The current matchmaking search, including the time in queue
+pub const MATCHMAKING_SEARCH: &str = "/lol-matchmaking/v1/search";
+/// The ranked stats of the current summoner per queue
+pub const RANKED_STATS: &str = "/lol-ranked/v1/current-ranked-stats";
+
+/// An in-memory mirror of selected LCU resources \
+/// Every tracked URI is seeded with a GET request and then kept up to date by the Create/Update/Delete events of the websocket API
+pub struct LcuStateCache {
+    resources: Arc<HashMap<String, Resource>>,
+    connected_rx: watch::Receiver<bool>,
+    update_task_handle: JoinHandle<()>,
+}
+
+struct Resource {
+    tx: watch::Sender<Option<Value>>,
+    /// The events that arrived before the seed was in place, None once it is seeded
+    pending: Mutex<Option<Vec<JsonApiEvent>>>,
+}
+
+impl Resource {
+    /// Every event carries the full state of the resource
+    fn apply(&self, event: JsonApiEvent) {
+        match event.event_type.as_str() {
+            "Delete" => self.tx.send_replace(None),
+            _ => self.tx.send_replace(Some(event.data)),
+        };
+    }
+}
+
+impl LcuStateCache {
+    /// Create a new [LcuStateCache] that tracks the given URIs (e.g. [GAMEFLOW_PHASE]) \
+    /// The [LcuWebsocketClient] is owned by the cache from now on, subscribing to other events on it is not possible
+    pub async fn new<S: AsRef<str>>(
+        rest_client: &RESTClient,
+        ws_client: LcuWebsocketClient,
+        uris: impl IntoIterator<Item = S>,
+    ) -> Result<Self, LcuWebsocketError> {
+        Self::with_seed(ws_client, uris, |uri| async move {
+            rest_client
+                .get(uri)
+                .await
+                .map_err(|e| LcuWebsocketError::LcuNotAvailable(e.to_string()))
+        })
+        .await
+    }
+
+    /// Create a new [LcuStateCache] that gets the initial state of every URI from `seed` instead of the REST API \
+    /// Events that arrive while a URI is being seeded are applied in order after the seed,
+    /// the seed might have been taken before the latest of them
+    pub async fn with_seed<S, F, Fut>(
+        mut ws_client: LcuWebsocketClient,
+        uris: impl IntoIterator<Item = S>,
+        mut seed: F,
+    ) -> Result<Self, LcuWebsocketError>
+    where
+        S: AsRef<str>,
+        F: FnMut(String) -> Fut,
+        Fut: Future<Output = Result<Value, LcuWebsocketError>>,
+    {
+        let mut resources = HashMap::new();
+
+        for uri in uris {
+            let uri = uri.as_ref().to_string();
+            // subscribe before seeding so no update between the GET and the subscription gets lost
+            ws_client
+                .subscribe(LcuSubscriptionType::JsonApiEvent(uri.clone()))
+                .await?;
+
+            let (tx, _) = watch::channel(None);
+            let pending = Mutex::new(Some(Vec::new()));
+            resources.insert(uri, Resource { tx, pending });
+        }
+
+        let resources = Arc::new(resources);
+        let (connected_tx, connected_rx) = watch::channel(true);
+
+        let update_task_handle = tokio::spawn({
+            let resources = resources.clone();
+            async move {
+                while let Some(event) = ws_client.next().await {
+                    let LcuEventPayload::JsonApi(event) = event.payload else {
+                        continue;
+                    };
+                    let Some(resource) = resources.get(&event.uri) else {
+                        continue;
+                    };
+                    let mut pending = resource.pending.lock().unwrap();
+                    match pending.as_mut() {
+                        Some(pending) => pending.push(event),
+                        None => resource.apply(event),
+                    }
+                }
+                connected_tx.send_replace(false);
+            }
+        });
+
+        // the update task gets aborted on drop if seeding fails
+        let cache = Self {
+            resources,
+            connected_rx,
+            update_task_handle,
+        };
+
+        for (uri, resource) in cache.resources.iter() {
+            let value = seed(uri.clone()).await?;
+            // locked until the buffered events are applied, so no new event gets in between
+            let mut pending = resource.pending.lock().unwrap();
+            resource.tx.send_replace(resource_from_response(value));
+            for event in pending.take().unwrap_or_default() {
+                resource.apply(event);
+            }
+        }
+
+        Ok(cache)
+    }
+
+    /// Returns the latest known state of a resource \
+    /// Returns None if the resource doesn't exist (e.g. no lobby) or the URI is not tracked
+    pub fn get(&self, uri: &str) -> Option<Value> {
+        self.resources
+            .get(uri)
+            .and_then(|resource| resource.tx.borrow().clone())
+    }
+
+    /// Returns the latest known state of a resource deserialized into `T` \
+    /// Returns None if the resource doesn't exist, the URI is not tracked or the data doesn't match `T`
+    pub fn get_as<T: DeserializeOwned>(&self, uri: &str) -> Option<T> {
+        self.get(uri)
+            .and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    /// Returns all tracked URIs
+    pub fn uris(&self) -> impl Iterator<Item = &String> {
+        self.resources.keys()
+    }
+
+    /// Checks if the cache still receives updates from the websocket
+    pub fn is_connected(&self) -> bool {
+        *self.connected_rx.borrow()
+    }
+
+    /// Waits for the next change of a resource and returns its new state \
+    /// Returns an [LcuWebsocketError] if the URI is not tracked or the websocket got disconnected
+    pub async fn changed(&self, uri: &str) -> Result<Option<Value>, LcuWebsocketError> {
+        let mut resource_rx = self
+            .resources
+            .get(uri)
+            .ok_or_else(|| LcuWebsocketError::NotSubscribed(uri.to_string()))?
+            .tx
+            .subscribe();
+        let mut connected_rx = self.connected_rx.clone();
+
+        if !*connected_rx.borrow_and_update() {
+            return Err(LcuWebsocketError::Disconnected(
+                "state cache is no longer updated".to_string(),
+            ));
+        }
+
+        tokio::select! {
+            _ = resource_rx.changed() => Ok(resource_rx.borrow().clone()),
+            _ = connected_rx.changed() => Err(LcuWebsocketError::Disconnected(
+                "state cache is no longer updated".to_string(),
+            )),
+        }
+    }
+}
+
+impl Drop for LcuStateCache {
+    fn drop(&mut self) {
+        self.update_task_handle.abort()
+    }
+}
+
+/// The LCU answers requests to resources that don't exist (e.g. the lobby while not in a lobby)
+/// with an error object instead of the resource
+fn resource_from_response(value: Value) -> Option<Value> {
+    match &value {
+        Value::Object(map) if map.contains_key("errorCode") && map.contains_key("httpStatus") => {
+            None
+        }
+        _ => Some(value),
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::HashMap;
+
+use crate::model::{
+    cooldowns::*,
+    ingame::{AbilityHaste, AllGameData, ItemId, RuneId, SummonerName, SummonerSpell, Time},
+};
+
+/// Base cooldowns and haste sources used by the [CooldownTracker] \
+/// The defaults match the current patch, all times are in seconds
+#[derive(Debug, Clone)]
+pub struct CooldownOptions {
+    /// The base cooldowns by spell key, see [SummonerSpell::spell_key]
+    pub summoner_spell_cooldowns: HashMap<String, Time>,
+    /// Items that grant summoner spell haste, e.g. Ionian Boots of Lucidity
+    pub summoner_spell_haste_items: HashMap<ItemId, AbilityHaste>,
+    /// Runes that grant summoner spell haste, e.g. Cosmic Insight \
+    /// Only the runes of the active player are known, the ingame API only exposes the keystone of other players
+    pub summoner_spell_haste_runes: HashMap<RuneId, AbilityHaste>,
+}
+
+impl Default for CooldownOptions {
+    fn default() -> Self {
+        Self {
+            summoner_spell_cooldowns: HashMap::from(
+                [
+                    ("SummonerBarrier", 180.0),
+                    ("SummonerBoost", 240.0),
+                    ("SummonerDot", 180.0),
+                    ("SummonerExhaust", 240.0),
+                    ("SummonerFlash", 300.0),
+                    ("SummonerHaste", 240.0),
+                    ("SummonerHeal", 240.0),
+                    ("SummonerMana", 240.0),
+                    ("SummonerSmite", 90.0),
+                    ("SummonerSnowball", 80.0),
+                    ("SummonerTeleport", 360.0),
+                    ("S12_SummonerTeleportUpgrade", 330.0),
+                ]
+                .map(|(key, cooldown)| (key.to_string(), cooldown)),
+            ),
+            // Ionian Boots of Lucidity and Crimson Lucidity
+            summoner_spell_haste_items: HashMap::from([(3158, 10.0), (3171, 20.0)]),
+            // Cosmic Insight
+            summoner_spell_haste_runes: HashMap::from([(8347, 18.0)]),
+        }
+    }
+}
+
+/// The state of a player as of the last snapshot
+#[derive(Debug, Clone, Default)]
+struct PlayerState {
+    summoner_spells: [Option<String>; 2],
+    summoner_spell_haste: AbilityHaste,
+    /// only known for the active player
+    ability_haste: Option<AbilityHaste>,
+}
+
+/// Estimates the cooldowns of summoner spells and ultimates \
+/// The ingame API doesn't expose cooldowns, so uses have to be reported with
+/// [CooldownTracker::report_summoner_spell] and [CooldownTracker::report_ultimate] \
+/// Keep the haste up to date by feeding it [AllGameData] snapshots with [CooldownTracker::update]
+#[derive(Debug, Clone, Default)]
+pub struct CooldownTracker {
+    options: CooldownOptions,
+    game_time: Time,
+    players: HashMap<SummonerName, PlayerState>,
+    cooldowns: HashMap<(SummonerName, CooldownSlot), Cooldown>,
+}
+
+impl CooldownTracker {
+    pub fn new(options: CooldownOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// Forget all players and cooldowns, e.g. when a new game starts
+    pub fn reset(&mut self) {
+        self.game_time = 0.0;
+        self.players.clear();
+        self.cooldowns.clear();
+    }
+
+    /// The game time of the last snapshot
+    pub fn game_time(&self) -> Time {
+        self.game_time
+    }
+
+    /// Updates the game time, the summoner spells and the haste of every player
+    pub fn update(&mut self, data: &AllGameData) {
+        // the game time going backwards means a new game has started
+        if data.game_data.game_time < self.game_time {
+            self.reset();
+        }
+        self.game_time = data.game_data.game_time;
+
+        let rune_haste = data
+            .active_player
+            .as_ref()
+            .map(|active_player| {
+                active_player
+                    .runes
+                    .general_runes
+                    .iter()
+                    .filter_map(|rune| self.options.summoner_spell_haste_runes.get(&rune.id))
+                    .sum::<AbilityHaste>()
+            })
+            .unwrap_or_default();
+
+        for player in &data.all_players {
+            let is_active_player = data
+                .active_player
+                .as_ref()
+                .is_some_and(|active_player| active_player.summoner_name == player.summoner_name);
+
+            let item_haste: AbilityHaste = player
+                .items
+                .iter()
+                .filter_map(|item| self.options.summoner_spell_haste_items.get(&item.item_id))
+                .sum();
+
+            let spell_key = |spell: &SummonerSpell| spell.spell_key().map(str::to_string);
+            self.players.insert(
+                player.summoner_name.clone(),
+                PlayerState {
+                    summoner_spells: [
+                        spell_key(&player.summoner_spells.summoner_spell_one),
+                        spell_key(&player.summoner_spells.summoner_spell_two),
+                    ],
+                    summoner_spell_haste: if is_active_player {
+                        item_haste + rune_haste
+                    } else {
+                        item_haste
+                    },
+                    ability_haste: data
+                        .active_player
+                        .as_ref()
+                        .filter(|_| is_active_player)
+                        .map(|active_player| active_player.champion_stats.ability_haste),
+                },
+            );
+        }
+    }
+
+    /// Report that a player used a summoner spell \
+    /// Takes an [Option] with the game time of the use, the default is the game time of the last snapshot \
+    /// Returns None if the player or the cooldown of the spell is not known
+    pub fn report_summoner_spell(
+        &mut self,
+        summoner_name: &str,
+        slot: CooldownSlot,
+        used_at: Option<Time>,
+    ) -> Option<&Cooldown> {
+        let index = match slot {
+            CooldownSlot::SummonerSpellOne => 0,
+            CooldownSlot::SummonerSpellTwo => 1,
+            CooldownSlot::Ultimate => return None,
+        };
+        let player = self.players.get(summoner_name)?;
+        let spell = player.summoner_spells[index].clone()?;
+        let base_cooldown = *self.options.summoner_spell_cooldowns.get(&spell)?;
+        let haste = player.summoner_spell_haste;
+
+        self.insert_cooldown(
+            summoner_name,
+            slot,
+            Some(spell),
+            base_cooldown,
+            haste,
+            used_at,
+        )
+    }
+
+    /// Report that a player used their ultimate \
+    /// The ingame API doesn't expose the cooldowns of champion abilities, so the base cooldown of the current rank has to be provided \
+    /// The ability haste is only known for the active player, for other players it can be provided if it is known \
+    /// Takes an [Option] with the game time of the use, the default is the game time of the last snapshot
+    pub fn report_ultimate(
+        &mut self,
+        summoner_name: &str,
+        base_cooldown: Time,
+        ability_haste: Option<AbilityHaste>,
+        used_at: Option<Time>,
+    ) -> Option<&Cooldown> {
+        let player = self.players.get(summoner_name)?;
+        let haste = ability_haste.or(player.ability_haste).unwrap_or_default();
+
+        self.insert_cooldown(
+            summoner_name,
+            CooldownSlot::Ultimate,
+            None,
+            base_cooldown,
+            haste,
+            used_at,
+        )
+    }
+
+    /// The seconds until the ability of the player is available again \
+    /// Returns None if no use has been reported
+    pub fn remaining(&self, summoner_name: &str, slot: CooldownSlot) -> Option<Time> {
+        self.cooldowns
+            .get(&(summoner_name.to_string(), slot))
+            .map(|cooldown| cooldown.remaining(self.game_time))
+    }
+
+    /// All reported cooldowns that have not run out yet
+    pub fn active_cooldowns(&self) -> impl Iterator<Item = &Cooldown> {
+        self.cooldowns
+            .values()
+            .filter(|cooldown| cooldown.ready_at > self.game_time)
+    }
+
+    /// The summoner spell haste of the player as of the last snapshot
+    pub fn summoner_spell_haste(&self, summoner_name: &str) -> Option<AbilityHaste> {
+        self.players
+            .get(summoner_name)
+            .map(|player| player.summoner_spell_haste)
+    }
+
+    fn insert_cooldown(
+        &mut self,
+        summoner_name: &str,
+        slot: CooldownSlot,
+        spell: Option<String>,
+        base_cooldown: Time,
+        haste: AbilityHaste,
+        used_at: Option<Time>,
+    ) -> Option<&Cooldown> {
+        let used_at = used_at.unwrap_or(self.game_time);
+        let cooldown = Cooldown {
+            summoner_name: summoner_name.to_string(),
+            slot,
+            spell,
+            used_at,
+            haste,
+            ready_at: used_at + apply_haste(base_cooldown, haste),
+        };
+
+        let key = (summoner_name.to_string(), slot);
+        self.cooldowns.insert(key.clone(), cooldown);
+        self.cooldowns.get(&key)
+    }
+}
+
+/// Haste reduces cooldowns by `haste / (100 + haste)`
+pub fn apply_haste(base_cooldown: Time, haste: AbilityHaste) -> Time {
+    base_cooldown * 100.0 / (100.0 + haste as Time)
+}
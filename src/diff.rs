@@ -0,0 +1,403 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    task::Poll,
+    time::Duration,
+};
+
+use futures_util::Stream;
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver},
+    sync::oneshot,
+    sync::oneshot::Sender,
+    task::JoinHandle,
+};
+
+use crate::{
+    ingame::{IngameClient, DEFAULT_MAX_RETRIES, DEFAULT_POLLING_RATE_MILLIS},
+    model::{
+        diff::*,
+        ingame::{
+            ActivePlayer, AllGameData, CreepScore, Gold, ItemCount, ItemId, Player, PlayerItem,
+            SummonerName, Time,
+        },
+    },
+};
+
+/// Thresholds used by the [GameDataDiffer] to decide which changes are worth an event
+#[derive(Debug, Clone)]
+pub struct DiffOptions {
+    /// An item that disappears within this many seconds after it appeared counts as undone \
+    /// The default is 10s
+    pub undo_window: Time,
+    /// The minimum increase of the active players gold between two snapshots for a [ActivePlayerChange::GoldSpike] \
+    /// The default is 150
+    pub gold_spike_threshold: Gold,
+    /// A [PlayerChange::CreepScoreMilestone] is emitted every time the creep score passes a multiple of this \
+    /// The default is 50
+    pub creep_score_milestone: CreepScore,
+}
+
+impl Default for DiffOptions {
+    fn default() -> Self {
+        Self {
+            undo_window: 10.0,
+            gold_spike_threshold: 150.0,
+            creep_score_milestone: 50,
+        }
+    }
+}
+
+/// Compares consecutive [AllGameData] snapshots and derives [DerivedEvent]s from the changes
+#[derive(Debug, Clone, Default)]
+pub struct GameDataDiffer {
+    options: DiffOptions,
+    previous: Option<AllGameData>,
+    recent_purchases: HashMap<SummonerName, Vec<(ItemId, Time)>>,
+    recent_combinations: HashMap<SummonerName, Vec<(ItemId, Time)>>,
+}
+
+impl GameDataDiffer {
+    pub fn new(options: DiffOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// Forget the previous snapshot, e.g. when a new game starts
+    pub fn reset(&mut self) {
+        self.previous = None;
+        self.recent_purchases.clear();
+        self.recent_combinations.clear();
+    }
+
+    /// Compares the snapshot with the previous one and stores it for the next call \
+    /// Returns no events for the first snapshot of a game
+    pub fn diff(&mut self, next: &AllGameData) -> Vec<DerivedEvent> {
+        // the game time going backwards means a new game has started
+        if let Some(previous) = &self.previous {
+            if next.game_data.game_time < previous.game_data.game_time {
+                self.reset();
+            }
+        }
+
+        let Some(previous) = self.previous.replace(next.clone()) else {
+            return Vec::new();
+        };
+        let game_time = next.game_data.game_time;
+        let mut events = Vec::new();
+
+        for player in &next.all_players {
+            let Some(previous_player) = previous
+                .all_players
+                .iter()
+                .find(|p| p.summoner_name == player.summoner_name)
+            else {
+                continue;
+            };
+            self.diff_player(previous_player, player, game_time)
+                .into_iter()
+                .for_each(|change| {
+                    events.push(DerivedEvent::Player {
+                        summoner_name: player.summoner_name.clone(),
+                        game_time,
+                        change,
+                    })
+                });
+        }
+
+        if let (Some(previous_active_player), Some(active_player)) =
+            (&previous.active_player, &next.active_player)
+        {
+            if previous_active_player.summoner_name == active_player.summoner_name {
+                self.diff_active_player(previous_active_player, active_player)
+                    .into_iter()
+                    .for_each(|change| {
+                        events.push(DerivedEvent::ActivePlayer { game_time, change })
+                    });
+            }
+        }
+
+        events
+    }
+
+    fn diff_player(
+        &mut self,
+        previous: &Player,
+        next: &Player,
+        game_time: Time,
+    ) -> Vec<PlayerChange> {
+        let mut changes = Vec::new();
+
+        if next.level > previous.level {
+            changes.push(PlayerChange::LevelUp { level: next.level });
+        }
+
+        match (previous.is_dead, next.is_dead) {
+            (false, true) => changes.push(PlayerChange::Died {
+                respawn_timer: next.respawn_timer,
+            }),
+            (true, false) => changes.push(PlayerChange::Respawned),
+            _ => {}
+        }
+
+        if next.scores.ward_score != previous.scores.ward_score {
+            changes.push(PlayerChange::WardScoreChanged {
+                previous: previous.scores.ward_score,
+                current: next.scores.ward_score,
+            });
+        }
+
+        let milestone = self.options.creep_score_milestone;
+        if milestone > 0
+            && next.scores.creep_score / milestone > previous.scores.creep_score / milestone
+        {
+            changes.push(PlayerChange::CreepScoreMilestone {
+                creep_score: next.scores.creep_score / milestone * milestone,
+            });
+        }
+
+        changes.extend(self.diff_items(
+            &next.summoner_name,
+            &previous.items,
+            &next.items,
+            game_time,
+        ));
+        changes
+    }
+
+    fn diff_items(
+        &mut self,
+        summoner_name: &SummonerName,
+        previous: &[PlayerItem],
+        next: &[PlayerItem],
+        game_time: Time,
+    ) -> Vec<PlayerChange> {
+        let undo_window = self.options.undo_window;
+        let recent_purchases = self
+            .recent_purchases
+            .entry(summoner_name.clone())
+            .or_default();
+        recent_purchases.retain(|(_, time)| game_time - time <= undo_window);
+        let recent_combinations = self
+            .recent_combinations
+            .entry(summoner_name.clone())
+            .or_default();
+        recent_combinations.retain(|(_, time)| game_time - time <= undo_window);
+
+        // items can move between slots, so only the total count per item matters
+        let previous_items = count_items(previous);
+        let next_items = count_items(next);
+
+        let added: Vec<&PlayerItem> = next_items
+            .iter()
+            .filter(|(id, (count, _))| {
+                *count
+                    > previous_items
+                        .get(*id)
+                        .map(|(count, _)| *count)
+                        .unwrap_or(0)
+            })
+            .map(|(_, (_, item))| *item)
+            .collect();
+
+        let mut changes = Vec::new();
+        let mut undone = false;
+
+        for (id, (previous_count, item)) in &previous_items {
+            let count = next_items.get(id).map(|(count, _)| *count).unwrap_or(0);
+            if count >= *previous_count {
+                continue;
+            }
+
+            let item = (*item).clone();
+            if item.consumable {
+                changes.push(PlayerChange::ItemConsumed {
+                    item,
+                    previous_count: *previous_count,
+                });
+            } else if let Some(index) = recent_purchases.iter().position(|(i, _)| i == id) {
+                recent_purchases.remove(index);
+                undone = true;
+                changes.push(PlayerChange::ItemUndone { item });
+            } else if !added.is_empty() {
+                recent_combinations.push((*id, game_time));
+                changes.push(PlayerChange::ItemCombined { item });
+            } else {
+                changes.push(PlayerChange::ItemSold { item });
+            }
+        }
+
+        for item in added {
+            // undoing an item restores its components, they were never bought
+            if undone {
+                if let Some(index) = recent_combinations
+                    .iter()
+                    .position(|(i, _)| *i == item.item_id)
+                {
+                    recent_combinations.remove(index);
+                    continue;
+                }
+            }
+            recent_purchases.push((item.item_id, game_time));
+            changes.push(PlayerChange::ItemPurchased { item: item.clone() });
+        }
+
+        changes
+    }
+
+    fn diff_active_player(
+        &self,
+        previous: &ActivePlayer,
+        next: &ActivePlayer,
+    ) -> Vec<ActivePlayerChange> {
+        let mut changes = Vec::new();
+
+        [
+            (AbilitySlot::Q, &previous.abilities.q, &next.abilities.q),
+            (AbilitySlot::W, &previous.abilities.w, &next.abilities.w),
+            (AbilitySlot::E, &previous.abilities.e, &next.abilities.e),
+            (AbilitySlot::R, &previous.abilities.r, &next.abilities.r),
+        ]
+        .into_iter()
+        .filter(|(_, previous, next)| next.ability_level > previous.ability_level)
+        .for_each(|(ability, _, next)| {
+            changes.push(ActivePlayerChange::AbilityLeveled {
+                ability,
+                level: next.ability_level,
+            })
+        });
+
+        let amount = next.current_gold - previous.current_gold;
+        if amount >= self.options.gold_spike_threshold {
+            changes.push(ActivePlayerChange::GoldSpike {
+                amount,
+                current_gold: next.current_gold,
+            });
+        }
+
+        changes
+    }
+}
+
+/// Sums up the counts of items that occupy multiple slots
+fn count_items(items: &[PlayerItem]) -> BTreeMap<ItemId, (ItemCount, &PlayerItem)> {
+    let mut counts: BTreeMap<ItemId, (ItemCount, &PlayerItem)> = BTreeMap::new();
+    for item in items {
+        counts
+            .entry(item.item_id)
+            .and_modify(|(count, _)| *count += item.count)
+            .or_insert((item.count, item));
+    }
+    counts
+}
+
+/// A wrapper around a [IngameClient] that regularly polls all game data \
+/// Emits the native ingame events and the events derived by a [GameDataDiffer] in one stream
+pub struct DiffEventStream {
+    start_tx: Option<Sender<()>>,
+    poll_task_handle: JoinHandle<()>,
+    events_rx: UnboundedReceiver<IngameDiffEvent>,
+}
+
+impl DiffEventStream {
+    /// Create a [DiffEventStream] from an [IngameClient] \
+    /// Takes an [Option] that specifies the polling rate of the [IngameClient] that's being wrapped \
+    /// The default [Duration] is 500ms \
+    /// Takes an [Option] with the [DiffOptions] of the [GameDataDiffer] \
+    /// Takes an [Option] that specifies how many API calls in a row can fail before the stream ends \
+    /// The default is 5
+    pub fn from_ingame_client(
+        ingame_client: IngameClient,
+        polling_rate: Option<Duration>,
+        options: Option<DiffOptions>,
+        max_retries: Option<u32>,
+    ) -> Self {
+        let (start_tx, start_rx) = oneshot::channel::<()>();
+        let (events_tx, events_rx) = unbounded_channel();
+
+        let poll_task_handle = tokio::spawn(async move {
+            let polling_rate =
+                polling_rate.unwrap_or(Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS));
+            let max_retries = max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
+            let mut timer = tokio::time::interval(polling_rate);
+            let mut differ = GameDataDiffer::new(options.unwrap_or_default());
+            let mut current_event_id = 0;
+            let mut failed_requests = 0;
+
+            // await start, but return on error (start_tx got dropped)
+            if start_rx.await.is_err() {
+                return;
+            }
+
+            // wait for a game to start
+            loop {
+                timer.tick().await;
+                if ingame_client.active_game().await {
+                    break;
+                };
+            }
+
+            // loop until the api calls fail more than max_retries times in a row
+            loop {
+                timer.tick().await;
+                match ingame_client.all_game_data(Some(current_event_id)).await {
+                    Ok(data) => {
+                        failed_requests = 0;
+                        let mut events: Vec<IngameDiffEvent> = data
+                            .events
+                            .iter()
+                            .filter(|e| e.get_event_id() >= current_event_id)
+                            .cloned()
+                            .map(IngameDiffEvent::Native)
+                            .collect();
+                        if let Some(last_event) = data.events.last() {
+                            current_event_id = current_event_id.max(last_event.get_event_id() + 1);
+                        }
+                        events.extend(differ.diff(&data).into_iter().map(IngameDiffEvent::Derived));
+
+                        // the sort is stable, so events of the same time keep their order
+                        events.sort_by(|a, b| a.get_event_time().total_cmp(&b.get_event_time()));
+                        events.drain(..).for_each(|e| {
+                            let _ = events_tx.send(e);
+                        })
+                    }
+                    Err(_) => {
+                        failed_requests += 1;
+                        if failed_requests > max_retries {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Self {
+            start_tx: Some(start_tx),
+            poll_task_handle,
+            events_rx,
+        }
+    }
+}
+
+impl Stream for DiffEventStream {
+    type Item = IngameDiffEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if let Some(start_tx) = self.start_tx.take() {
+            if start_tx.send(()).is_err() {
+                return Poll::Ready(None);
+            }
+        }
+        self.events_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for DiffEventStream {
+    fn drop(&mut self) {
+        self.poll_task_handle.abort()
+    }
+}
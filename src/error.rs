@@ -9,6 +9,8 @@ pub enum ProcessInfoError {
     PortNotFound,
     /// There has been an error getting the API auth token
     AuthTokenNotFound,
+    /// None of the lockfiles could be used, see [discover_client_info](crate::utils::lockfile::discover_client_info)
+    Lockfile(LockfileError),
 }
 
 impl Error for ProcessInfoError {}
@@ -31,6 +33,30 @@ impl Display for ProcessInfoError {
                 "{:?}: API auth token could not be parsed from process arguments",
                 self
             ),
+            Self::Lockfile(e) => write!(f, "Lockfile: {}", e),
+        }
+    }
+}
+
+/// Errors that can occur when reading a lockfile of the League or Riot client
+#[derive(Debug, Clone)]
+pub enum LockfileError {
+    /// None of the lockfiles exists, the client is probably not running
+    NotFound,
+    /// A lockfile exists but could not be read
+    IoError(String),
+    /// The lockfile doesn't have the format `name:pid:port:password:protocol`
+    InvalidFormat(String),
+}
+
+impl Error for LockfileError {}
+
+impl Display for LockfileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "No lockfile found"),
+            Self::IoError(s) => write!(f, "Could not read the lockfile: {}", s),
+            Self::InvalidFormat(s) => write!(f, "Invalid lockfile: {}", s),
         }
     }
 }
@@ -81,6 +107,50 @@ impl Display for IngameClientError {
     }
 }
 
+/// Errors for the Replay API
+#[derive(Debug, Clone)]
+pub enum ReplayClientError {
+    /// The Replay API is disabled, it has to be enabled with `EnableReplayApi=1` in the `game.cfg` \
+    /// Corresponds to HTTP status response 404
+    ApiNotEnabled,
+    /// The request was rejected, e.g. because of an invalid value \
+    /// Corresponds to HTTP status responses 400 – 499, excluding 404 which is [ReplayClientError::ApiNotEnabled]
+    ClientError(String),
+    /// An error ocurred on the server side \
+    /// Corresponds to HTTP status responses 500 – 599
+    ServerError(String),
+    /// There was an error deserializing the received data
+    DeserializationError(String),
+    /// All errors not caught by the other [ReplayClientError] variants are categorised as a [ReplayClientError::ConnectionError]
+    ConnectionError(String),
+}
+
+impl From<reqwest::Error> for ReplayClientError {
+    fn from(error: reqwest::Error) -> Self {
+        if let Some(status) = error.status() {
+            if status == 404 {
+                return ReplayClientError::ApiNotEnabled;
+            } else if status.is_client_error() {
+                return ReplayClientError::ClientError(status.to_string());
+            } else if status.is_server_error() {
+                return ReplayClientError::ServerError(status.to_string());
+            }
+        }
+        if error.is_decode() {
+            return ReplayClientError::DeserializationError(error.to_string());
+        }
+        ReplayClientError::ConnectionError(error.to_string())
+    }
+}
+
+impl Error for ReplayClientError {}
+
+impl Display for ReplayClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
 /// Errors for the Websocket connection to the LCU API
 #[derive(Debug, Clone)]
 pub enum LcuWebsocketError {
@@ -92,6 +162,10 @@ pub enum LcuWebsocketError {
     SendError,
     /// The connection was terminated
     Disconnected(String),
+    /// The requested resource is not tracked by the subscriptions of the connection
+    NotSubscribed(String),
+    /// A remote procedure call was answered with an error
+    CallError(String),
 }
 
 impl Error for LcuWebsocketError {}
@@ -103,6 +177,8 @@ impl Display for LcuWebsocketError {
             Self::AuthError => write!(f, "Authentication error"),
             Self::SendError => write!(f, "Error sending message"),
             Self::Disconnected(s) => write!(f, "Websocket disconnected: {}", s),
+            Self::NotSubscribed(s) => write!(f, "Not subscribed to: {}", s),
+            Self::CallError(s) => write!(f, "Call failed: {}", s),
         }
     }
 }
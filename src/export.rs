@@ -0,0 +1,293 @@
+use serde_json::Value;
+
+use crate::{
+    model::{
+        export::*,
+        ingame::{AllGameData, GameEvent},
+    },
+    stats::recipe_gold,
+};
+
+/// A row of the stats of every player per recorded [AllGameData], e.g. the frames of a [Timeline](crate::timeline::Timeline) \
+/// The items are the item ids joined with `;`
+pub fn player_ticks_table<'a, I: IntoIterator<Item = &'a AllGameData>>(frames: I) -> Table {
+    let mut builder = TableBuilder::default();
+    for data in frames {
+        for player in &data.all_players {
+            let items = player
+                .items
+                .iter()
+                .map(|item| item.item_id.to_string())
+                .collect::<Vec<_>>()
+                .join(";");
+            builder.push_row(vec![
+                ("game_time", Cell::Float(Some(data.game_data.game_time))),
+                (
+                    "summoner_name",
+                    Cell::Text(Some(player.summoner_name.clone())),
+                ),
+                (
+                    "champion_name",
+                    Cell::Text(Some(player.champion_name.to_string())),
+                ),
+                ("team", Cell::Text(Some(player.team.to_string()))),
+                ("level", Cell::Int(Some(player.level as i64))),
+                ("kills", Cell::Int(Some(player.scores.kills as i64))),
+                ("deaths", Cell::Int(Some(player.scores.deaths as i64))),
+                ("assists", Cell::Int(Some(player.scores.assists as i64))),
+                (
+                    "creep_score",
+                    Cell::Int(Some(player.scores.creep_score as i64)),
+                ),
+                (
+                    "ward_score",
+                    Cell::Float(Some(player.scores.ward_score as f64)),
+                ),
+                ("items", Cell::Text(Some(items))),
+                (
+                    "recipe_gold",
+                    Cell::Int(Some(recipe_gold(&player.items) as i64)),
+                ),
+                ("is_dead", Cell::Bool(Some(player.is_dead))),
+                ("respawn_timer", Cell::Float(Some(player.respawn_timer))),
+            ]);
+        }
+    }
+    builder.table
+}
+
+/// A row per event with the fields most events have in common \
+/// The assisters are joined with `;`, the remaining fields of the event are in the `details` column as JSON
+pub fn events_table<'a, I: IntoIterator<Item = &'a GameEvent>>(events: I) -> Table {
+    let mut builder = TableBuilder::default();
+    for event in events {
+        let Ok(Value::Object(mut fields)) = serde_json::to_value(event) else {
+            continue;
+        };
+        // written from the typed event below
+        fields.remove("KillerName");
+        let mut text = |key: &str| match fields.remove(key) {
+            Some(Value::String(s)) => Some(s),
+            Some(Value::Array(values)) => Some(
+                values
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_string).unwrap_or(v.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(";"),
+            ),
+            Some(Value::Null) | None => None,
+            Some(value) => Some(value.to_string()),
+        };
+        let event_name = text("EventName");
+        let victim_name = text("VictimName");
+        let assisters = text("Assisters");
+        let stolen = match fields.remove("Stolen") {
+            Some(Value::Bool(stolen)) => Some(stolen),
+            Some(Value::String(stolen)) => Some(stolen.eq_ignore_ascii_case("true")),
+            _ => None,
+        };
+        fields.remove("EventID");
+        fields.remove("EventTime");
+        let details = (!fields.is_empty()).then(|| Value::Object(fields).to_string());
+
+        builder.push_row(vec![
+            ("event_id", Cell::Int(Some(event.get_event_id() as i64))),
+            ("event_time", Cell::Float(Some(event.get_event_time()))),
+            ("event_name", Cell::Text(event_name)),
+            ("killer_name", Cell::Text(killer_name(event))),
+            ("victim_name", Cell::Text(victim_name)),
+            ("assisters", Cell::Text(assisters)),
+            ("stolen", Cell::Bool(stolen)),
+            ("details", Cell::Text(details)),
+        ]);
+    }
+    builder.table
+}
+
+/// The display name of the killer, e.g. `SRU_Baron` for a Baron Nashor that killed a champion
+fn killer_name(event: &GameEvent) -> Option<String> {
+    match event {
+        GameEvent::AtakhanKill(e) => Some(e.killer_name.to_string()),
+        GameEvent::BaronKill(e) => Some(e.killer_name.to_string()),
+        GameEvent::ChampionKill(e) => Some(e.killer_name.to_string()),
+        GameEvent::DragonKill(e) => Some(e.killer_name.to_string()),
+        GameEvent::FirstBrick(e) => Some(e.killer_name.to_string()),
+        GameEvent::HeraldKill(e) => Some(e.killer_name.to_string()),
+        GameEvent::HordeKill(e) => Some(e.killer_name.to_string()),
+        GameEvent::InhibKilled(e) => Some(e.killer_name.to_string()),
+        GameEvent::Multikill(e) => Some(e.killer_name.clone()),
+        GameEvent::TurretKilled(e) => Some(e.killer_name.to_string()),
+        _ => None,
+    }
+}
+
+/// A row of the champion stats of the active player per recorded [AllGameData] \
+/// Frames without an active player, e.g. of a spectated game, are skipped
+pub fn active_player_stats_table<'a, I: IntoIterator<Item = &'a AllGameData>>(frames: I) -> Table {
+    let mut builder = TableBuilder::default();
+    for data in frames {
+        let Some(active_player) = &data.active_player else {
+            continue;
+        };
+        let mut row = vec![
+            (
+                "game_time".to_string(),
+                Cell::Float(Some(data.game_data.game_time)),
+            ),
+            (
+                "summoner_name".to_string(),
+                Cell::Text(Some(active_player.summoner_name.clone())),
+            ),
+            (
+                "level".to_string(),
+                Cell::Int(Some(active_player.level as i64)),
+            ),
+            (
+                "current_gold".to_string(),
+                Cell::Float(Some(active_player.current_gold as f64)),
+            ),
+        ];
+        // one column per stat, so stats added to the API show up without changes here
+        if let Ok(Value::Object(stats)) = serde_json::to_value(&active_player.champion_stats) {
+            row.extend(stats.into_iter().map(|(key, value)| {
+                let cell = match value {
+                    Value::Number(n) => Cell::Float(n.as_f64()),
+                    Value::Bool(b) => Cell::Bool(Some(b)),
+                    Value::String(s) => Cell::Text(Some(s)),
+                    value => Cell::Text(Some(value.to_string())),
+                };
+                (snake_case(&key), cell)
+            }));
+        }
+        builder.push_row(row);
+    }
+    builder.table
+}
+
+/// Writes the table as CSV with a header row, values that are None are written as empty fields
+#[cfg(feature = "csv")]
+pub fn write_csv<W: std::io::Write>(table: &Table, writer: W) -> csv::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(table.columns.iter().map(|column| column.name.as_str()))?;
+    for row in 0..table.num_rows() {
+        writer.write_record(table.columns.iter().map(|column| {
+            match &column.values {
+                ColumnValues::Int(values) => values[row].map(|v| v.to_string()),
+                ColumnValues::Float(values) => values[row].map(|v| v.to_string()),
+                ColumnValues::Bool(values) => values[row].map(|v| v.to_string()),
+                ColumnValues::Text(values) => values[row].clone(),
+            }
+            .unwrap_or_default()
+        }))?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Converts the table to an Arrow [RecordBatch](arrow_array::RecordBatch) with nullable columns,
+/// e.g. to write it as Parquet
+#[cfg(feature = "arrow")]
+pub fn to_record_batch(
+    table: &Table,
+) -> Result<arrow_array::RecordBatch, arrow_schema::ArrowError> {
+    use std::sync::Arc;
+
+    use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema};
+
+    let (fields, arrays): (Vec<Field>, Vec<ArrayRef>) = table
+        .columns
+        .iter()
+        .map(|column| {
+            let (data_type, array): (DataType, ArrayRef) = match &column.values {
+                ColumnValues::Int(values) => {
+                    (DataType::Int64, Arc::new(Int64Array::from(values.clone())))
+                }
+                ColumnValues::Float(values) => (
+                    DataType::Float64,
+                    Arc::new(Float64Array::from(values.clone())),
+                ),
+                ColumnValues::Bool(values) => (
+                    DataType::Boolean,
+                    Arc::new(BooleanArray::from(values.clone())),
+                ),
+                ColumnValues::Text(values) => {
+                    (DataType::Utf8, Arc::new(StringArray::from(values.clone())))
+                }
+            };
+            (Field::new(column.name.clone(), data_type, true), array)
+        })
+        .unzip();
+
+    arrow_array::RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)
+}
+
+enum Cell {
+    Int(Option<i64>),
+    Float(Option<f64>),
+    Bool(Option<bool>),
+    Text(Option<String>),
+}
+
+/// Builds a [Table] row by row \
+/// Columns are created in the order they first appear, rows without a value for a column get None
+#[derive(Default)]
+struct TableBuilder {
+    table: Table,
+    rows: usize,
+}
+
+impl TableBuilder {
+    fn push_row<S: Into<String>>(&mut self, cells: Vec<(S, Cell)>) {
+        for (name, cell) in cells {
+            let name = name.into();
+            let column = match self.table.columns.iter().position(|c| c.name == name) {
+                Some(index) => &mut self.table.columns[index],
+                None => {
+                    let values = match cell {
+                        Cell::Int(_) => ColumnValues::Int(vec![None; self.rows]),
+                        Cell::Float(_) => ColumnValues::Float(vec![None; self.rows]),
+                        Cell::Bool(_) => ColumnValues::Bool(vec![None; self.rows]),
+                        Cell::Text(_) => ColumnValues::Text(vec![None; self.rows]),
+                    };
+                    self.table.columns.push(Column { name, values });
+                    self.table.columns.last_mut().unwrap()
+                }
+            };
+            // a value of another type than the column is dropped
+            match (&mut column.values, cell) {
+                (ColumnValues::Int(values), Cell::Int(value)) => values.push(value),
+                (ColumnValues::Float(values), Cell::Float(value)) => values.push(value),
+                (ColumnValues::Bool(values), Cell::Bool(value)) => values.push(value),
+                (ColumnValues::Text(values), Cell::Text(value)) => values.push(value),
+                _ => {}
+            }
+        }
+
+        self.rows += 1;
+        for column in &mut self.table.columns {
+            match &mut column.values {
+                ColumnValues::Int(values) => values.resize(self.rows, None),
+                ColumnValues::Float(values) => values.resize(self.rows, None),
+                ColumnValues::Bool(values) => values.resize(self.rows, None),
+                ColumnValues::Text(values) => values.resize(self.rows, None),
+            }
+        }
+    }
+}
+
+/// `abilityHaste` -> `ability_haste`
+fn snake_case(name: &str) -> String {
+    let mut snake_case = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                snake_case.push('_');
+            }
+            snake_case.extend(c.to_lowercase());
+        } else {
+            snake_case.push(c);
+        }
+    }
+    snake_case
+}
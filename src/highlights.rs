@@ -0,0 +1,300 @@
+use std::collections::HashMap;
+
+use crate::{
+    error::ReplayClientError,
+    model::{
+        highlights::*,
+        ingame::{DragonType, GameEvent, Killer, Player, SummonerName, Time},
+        replay::{CameraMode, EasingType, KeyFrame, Playback, Render, Sequence},
+    },
+    replay::ReplayClient,
+};
+
+/// The scores of the moments and the shape of the clips planned by [plan_highlights] \
+/// Multikill scores are a bonus on top of the kills they consist of, the steal score is a bonus on top of the objective
+#[derive(Debug, Clone)]
+pub struct HighlightOptions {
+    pub kill: f32,
+    pub first_blood: f32,
+    pub double_kill: f32,
+    pub triple_kill: f32,
+    pub quadra_kill: f32,
+    pub penta_kill: f32,
+    pub ace: f32,
+    pub baron: f32,
+    pub dragon: f32,
+    pub elder_dragon: f32,
+    pub steal: f32,
+    /// The seconds of game time before the first moment of a clip \
+    /// The default is 10s
+    pub lead_in: Time,
+    /// The seconds of game time after the last moment of a clip \
+    /// The default is 5s
+    pub lead_out: Time,
+    /// Moments less than this apart end up in the same clip \
+    /// The default is 15s
+    pub merge_gap: Time,
+    /// Clips with a lower score are dropped \
+    /// The default is 2.0, so a single kill without anything else is not a highlight
+    pub min_score: f32,
+    /// The playback speed around the top moment of a clip, 1.0 disables the slow motion \
+    /// The default is 0.5
+    pub slow_motion_speed: f32,
+    /// The seconds of game time played in slow motion, centered on the top moment \
+    /// The default is 4s
+    pub slow_motion_duration: Time,
+}
+
+impl Default for HighlightOptions {
+    fn default() -> Self {
+        Self {
+            kill: 1.0,
+            first_blood: 2.0,
+            double_kill: 2.0,
+            triple_kill: 4.0,
+            quadra_kill: 7.0,
+            penta_kill: 12.0,
+            ace: 3.0,
+            baron: 4.0,
+            dragon: 2.0,
+            elder_dragon: 5.0,
+            steal: 5.0,
+            lead_in: 10.0,
+            lead_out: 5.0,
+            merge_gap: 15.0,
+            min_score: 2.0,
+            slow_motion_speed: 0.5,
+            slow_motion_duration: 4.0,
+        }
+    }
+}
+
+/// Scores the events that make a highlight, other events are skipped \
+/// The moments are in the order of the events
+pub fn score_moments(events: &[GameEvent], options: &HighlightOptions) -> Vec<HighlightMoment> {
+    let summoner = |killer: &Killer| match killer {
+        Killer::Summoner(name) => Some(name.clone()),
+        _ => None,
+    };
+    let steal_bonus = |stolen: bool| if stolen { options.steal } else { 0.0 };
+
+    events
+        .iter()
+        .filter_map(|event| {
+            let (event_id, time, reason, player, score) = match event {
+                GameEvent::ChampionKill(e) => (
+                    e.event_id,
+                    e.event_time,
+                    HighlightReason::Kill,
+                    summoner(&e.killer_name),
+                    options.kill,
+                ),
+                GameEvent::FirstBlood(e) => (
+                    e.event_id,
+                    e.event_time,
+                    HighlightReason::FirstBlood,
+                    Some(e.recipient.clone()),
+                    options.first_blood,
+                ),
+                GameEvent::Multikill(e) => {
+                    let score = match e.kill_streak {
+                        ..=1 => return None,
+                        2 => options.double_kill,
+                        3 => options.triple_kill,
+                        4 => options.quadra_kill,
+                        _ => options.penta_kill,
+                    };
+                    (
+                        e.event_id,
+                        e.event_time,
+                        HighlightReason::Multikill {
+                            kill_streak: e.kill_streak,
+                        },
+                        Some(e.killer_name.clone()),
+                        score,
+                    )
+                }
+                GameEvent::Ace(e) => (
+                    e.event_id,
+                    e.event_time,
+                    HighlightReason::Ace,
+                    Some(e.acer.clone()),
+                    options.ace,
+                ),
+                GameEvent::BaronKill(e) => (
+                    e.event_id,
+                    e.event_time,
+                    HighlightReason::BaronKill { stolen: e.stolen },
+                    summoner(&e.killer_name),
+                    options.baron + steal_bonus(e.stolen),
+                ),
+                GameEvent::DragonKill(e) => {
+                    let score = match e.dragon_type {
+                        DragonType::Elder => options.elder_dragon,
+                        _ => options.dragon,
+                    };
+                    (
+                        e.event_id,
+                        e.event_time,
+                        HighlightReason::DragonKill {
+                            dragon_type: e.dragon_type.clone(),
+                            stolen: e.stolen,
+                        },
+                        summoner(&e.killer_name),
+                        score + steal_bonus(e.stolen),
+                    )
+                }
+                _ => return None,
+            };
+            Some(HighlightMoment {
+                event_id,
+                time,
+                reason,
+                player,
+                score,
+            })
+        })
+        .collect()
+}
+
+/// Groups the moments of the events into clips, ranked by their score \
+/// Clips with the same score are ordered by game time
+pub fn plan_highlights(events: &[GameEvent], options: &HighlightOptions) -> Vec<HighlightClip> {
+    let mut moments = score_moments(events, options);
+    moments.sort_by(|a, b| a.time.total_cmp(&b.time));
+
+    let mut groups: Vec<Vec<HighlightMoment>> = Vec::new();
+    for moment in moments {
+        match groups.last_mut() {
+            Some(group)
+                if group
+                    .last()
+                    .is_some_and(|last| moment.time - last.time <= options.merge_gap) =>
+            {
+                group.push(moment)
+            }
+            _ => groups.push(vec![moment]),
+        }
+    }
+
+    let mut clips: Vec<HighlightClip> = groups
+        .into_iter()
+        .filter_map(|moments| clip(moments, options))
+        .filter(|clip| clip.score >= options.min_score)
+        .collect();
+    clips.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(a.start.total_cmp(&b.start))
+    });
+    clips
+}
+
+fn clip(moments: Vec<HighlightMoment>, options: &HighlightOptions) -> Option<HighlightClip> {
+    let first = moments.first()?.time;
+    let last = moments.last()?.time;
+
+    // the player with the highest score, the first one to reach it on a tie
+    let mut player_scores: HashMap<&SummonerName, f32> = HashMap::new();
+    let mut focus_player: Option<(&SummonerName, f32)> = None;
+    for moment in &moments {
+        let Some(player) = &moment.player else {
+            continue;
+        };
+        let score = player_scores.entry(player).or_default();
+        *score += moment.score;
+        let is_top = match focus_player {
+            Some((_, top)) => *score > top,
+            None => true,
+        };
+        if is_top {
+            focus_player = Some((player, *score));
+        }
+    }
+    let focus_player = focus_player.map(|(player, _)| player.clone());
+
+    let reason = moments
+        .iter()
+        .reduce(|top, m| if m.score > top.score { m } else { top })?
+        .reason
+        .clone();
+
+    Some(HighlightClip {
+        start: (first - options.lead_in).max(0.0),
+        end: last + options.lead_out,
+        focus_player,
+        reason,
+        score: moments.iter().map(|m| m.score).sum(),
+        moments,
+    })
+}
+
+/// The camera settings to watch the clip, following the champion of the focus player
+pub fn clip_render(clip: &HighlightClip, players: &[Player]) -> Render {
+    match focus_champion(clip, players) {
+        Some(champion_name) => Render {
+            camera_mode: Some(CameraMode::Focus),
+            camera_attached: Some(true),
+            selection_name: Some(champion_name),
+            ..Default::default()
+        },
+        None => Render {
+            camera_mode: Some(CameraMode::Top),
+            camera_attached: Some(false),
+            ..Default::default()
+        },
+    }
+}
+
+/// A [Sequence] for the Replay API that selects the champion of the focus player
+/// and plays the top moment of the clip in slow motion
+pub fn clip_sequence(
+    clip: &HighlightClip,
+    players: &[Player],
+    options: &HighlightOptions,
+) -> Sequence {
+    let mut playback_speed = vec![KeyFrame::new(clip.start, 1.0, EasingType::Linear)];
+    if let (Some(top), true) = (clip.top_moment(), options.slow_motion_speed < 1.0) {
+        let half = options.slow_motion_duration / 2.0;
+        playback_speed.extend([
+            KeyFrame::new(
+                (top.time - half).max(clip.start),
+                1.0,
+                EasingType::SmoothStep,
+            ),
+            KeyFrame::new(top.time, options.slow_motion_speed, EasingType::SmoothStep),
+            KeyFrame::new((top.time + half).min(clip.end), 1.0, EasingType::Linear),
+        ]);
+    }
+
+    Sequence {
+        playback_speed: Some(playback_speed),
+        selection_name: focus_champion(clip, players)
+            .map(|champion_name| vec![KeyFrame::new(clip.start, champion_name, EasingType::Snap)]),
+        ..Default::default()
+    }
+}
+
+/// Loads the render settings and the sequence of the clip into the replay and plays it from the start \
+/// The replay keeps playing after the end of the clip, stop it with [ReplayClient::pause] or seek to the next clip
+pub async fn play_clip(
+    client: &ReplayClient,
+    clip: &HighlightClip,
+    players: &[Player],
+    options: &HighlightOptions,
+) -> Result<Playback, ReplayClientError> {
+    client.set_render(&clip_render(clip, players)).await?;
+    client
+        .set_sequence(&clip_sequence(clip, players, options))
+        .await?;
+    client.seek(clip.start).await?;
+    client.play().await
+}
+
+fn focus_champion(clip: &HighlightClip, players: &[Player]) -> Option<String> {
+    let focus_player = clip.focus_player.as_ref()?;
+    players
+        .iter()
+        .find(|p| &p.summoner_name == focus_player)
+        .map(|p| p.champion_name.to_string())
+}
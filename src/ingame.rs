@@ -8,29 +8,37 @@ use tokio::{
     task::JoinHandle,
 };
 
-use crate::{error::IngameClientError, model::ingame::*, utils::request::build_reqwest_client};
+use crate::{error::IngameClientError, model::ingame::*};
 
 const PORT: u16 = 2999;
+const DEFAULT_TIMEOUT_MILLIS: u64 = 500;
 
 /// A client for the LoL-Ingame API
-pub struct IngameClient(reqwest::Client);
+#[derive(Clone)]
+pub struct IngameClient {
+    pub(crate) client: reqwest::Client,
+    pub(crate) base_url: String,
+    pub(crate) timeout: Duration,
+}
 
 impl IngameClient {
     /// Create a new connection to the ingame api. This will return an error if a game is not running
     pub fn new() -> Result<Self, IngameClientError> {
-        Ok(Self(build_reqwest_client(None)))
+        Self::builder().build()
+    }
+
+    /// Create an [IngameClientBuilder] to configure the connection
+    pub fn builder() -> IngameClientBuilder {
+        IngameClientBuilder::default()
     }
 
     /// Checks if there is an active game \
     /// Returns true only after the loading screen
     pub async fn active_game(&self) -> bool {
         let req = self
-            .0
+            .client
             // HEAD doesn't work with "/liveclientdata/allgamedata" for some reason
-            .head(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataAllgamedata",
-                PORT
-            ))
+            .head(format!("{}/GetLiveclientdataAllgamedata", self.base_url))
             // set a custom timeout so the function doesn't take forever to complete when the server is not reachable
             .timeout(Duration::from_millis(100))
             .send()
@@ -47,8 +55,8 @@ impl IngameClient {
     /// Returns true even in loading screen while other API calls still return Error
     pub async fn active_game_loadingscreen(&self) -> bool {
         let req = self
-            .0
-            .head(format!("https://127.0.0.1:{}/Help", PORT))
+            .client
+            .head(format!("{}/Help", self.base_url))
             // set a custom timeout so the function doesn't take forever to complete when the server is not reachable
             .timeout(Duration::from_millis(100))
             .send()
@@ -64,11 +72,9 @@ impl IngameClient {
     /// Checks if the game is a livegame or in spectatormode
     pub async fn is_spectator_mode(&self) -> Result<bool, IngameClientError> {
         let req = self
-            .0
-            .head(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataActiveplayer",
-                PORT
-            ))
+            .client
+            .head(format!("{}/GetLiveclientdataActiveplayer", self.base_url))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -86,12 +92,34 @@ impl IngameClient {
         &self,
         event_id: Option<u32>,
     ) -> Result<AllGameData, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataAllgamedata?eventID={}",
-                PORT,
+                "{}/GetLiveclientdataAllgamedata?eventID={}",
+                self.base_url,
                 event_id.unwrap_or(0) // an event_id of 0 returns all events
             ))
+            .timeout(self.timeout)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(IngameClientError::from)?
+            .json()
+            .await
+            .map_err(IngameClientError::from)
+    }
+
+    /// Get all current game data as the untyped JSON returned by the API, e.g. to record it
+    pub async fn all_game_data_raw(
+        &self,
+        event_id: Option<u32>,
+    ) -> Result<serde_json::Value, IngameClientError> {
+        self.client
+            .get(format!(
+                "{}/GetLiveclientdataAllgamedata?eventID={}",
+                self.base_url,
+                event_id.unwrap_or(0) // an event_id of 0 returns all events
+            ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -106,12 +134,13 @@ impl IngameClient {
         &self,
         event_id: Option<u32>,
     ) -> Result<Vec<GameEvent>, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataEventdata?eventID={}",
-                PORT,
+                "{}/GetLiveclientdataEventdata?eventID={}",
+                self.base_url,
                 event_id.unwrap_or(0) // an event_id of 0 returns all events
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -124,11 +153,9 @@ impl IngameClient {
 
     /// Get the active games stats
     pub async fn game_stats(&self) -> Result<GameStats, IngameClientError> {
-        self.0
-            .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataGamestats",
-                PORT
-            ))
+        self.client
+            .get(format!("{}/GetLiveclientdataGamestats", self.base_url))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -143,12 +170,13 @@ impl IngameClient {
         &self,
         summoner_name: S,
     ) -> Result<Vec<PlayerItem>, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataPlayeritems?summonerName={}",
-                PORT,
+                "{}/GetLiveclientdataPlayeritems?summonerName={}",
+                self.base_url,
                 summoner_name.as_ref()
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -163,12 +191,13 @@ impl IngameClient {
         &self,
         team_id: Option<TeamId>,
     ) -> Result<Vec<Player>, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataPlayerlist?teamID={}",
-                PORT,
+                "{}/GetLiveclientdataPlayerlist?teamID={}",
+                self.base_url,
                 team_id.unwrap_or(TeamId::All)
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -183,12 +212,13 @@ impl IngameClient {
         &self,
         summoner_name: S,
     ) -> Result<PlayerRunes, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataPlayermainrunes?summonerName={}",
-                PORT,
+                "{}/GetLiveclientdataPlayermainrunes?summonerName={}",
+                self.base_url,
                 summoner_name.as_ref()
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -203,12 +233,13 @@ impl IngameClient {
         &self,
         summoner_name: S,
     ) -> Result<PlayerScores, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataPlayerscores?summonerName={}",
-                PORT,
+                "{}/GetLiveclientdataPlayerscores?summonerName={}",
+                self.base_url,
                 summoner_name.as_ref()
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -223,12 +254,13 @@ impl IngameClient {
         &self,
         summoner_name: S,
     ) -> Result<SummonerSpells, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataPlayersummonerspells?summonerName={}",
-                PORT,
+                "{}/GetLiveclientdataPlayersummonerspells?summonerName={}",
+                self.base_url,
                 summoner_name.as_ref()
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -241,11 +273,9 @@ impl IngameClient {
     /// Get active players data \
     /// Only available during livegame
     pub async fn active_player(&self) -> Result<ActivePlayer, IngameClientError> {
-        self.0
-            .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataActiveplayer",
-                PORT
-            ))
+        self.client
+            .get(format!("{}/GetLiveclientdataActiveplayer", self.base_url))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -264,11 +294,12 @@ impl IngameClient {
     /// Get the active players abilities \
     /// Only available during livegame
     pub async fn active_player_abilities(&self) -> Result<PlayerAbilities, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataActiveplayerabilities",
-                PORT
+                "{}/GetLiveclientdataActiveplayerabilities",
+                self.base_url
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -281,11 +312,12 @@ impl IngameClient {
     /// Get the active players name \
     /// Only available during livegame
     pub async fn active_player_name(&self) -> Result<String, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataActiveplayername",
-                PORT
+                "{}/GetLiveclientdataActiveplayername",
+                self.base_url
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -305,11 +337,12 @@ impl IngameClient {
     /// Get the active players runes \
     /// Only available during livegames
     pub async fn active_player_runes(&self) -> Result<FullPlayerRunes, IngameClientError> {
-        self.0
+        self.client
             .get(format!(
-                "https://127.0.0.1:{}/GetLiveclientdataActiveplayerrunes",
-                PORT
+                "{}/GetLiveclientdataActiveplayerrunes",
+                self.base_url
             ))
+            .timeout(self.timeout)
             .send()
             .await
             .and_then(|r| r.error_for_status())
@@ -320,7 +353,133 @@ impl IngameClient {
     }
 }
 
-const DEFAULT_POLLING_RATE_MILLIS: u64 = 500;
+pub(crate) const DEFAULT_POLLING_RATE_MILLIS: u64 = 500;
+pub(crate) const DEFAULT_MAX_RETRIES: u32 = 5;
+
+/// A builder to configure the connection of an [IngameClient] \
+/// By default the API of the game running on this machine gets used
+#[derive(Clone)]
+pub struct IngameClientBuilder {
+    host: String,
+    port: u16,
+    secure: bool,
+    use_bundled_certificate: bool,
+    root_certificates: Vec<reqwest::Certificate>,
+    accept_invalid_certs: bool,
+    accept_invalid_hostnames: bool,
+    timeout: Duration,
+    client: Option<reqwest::Client>,
+}
+
+impl Default for IngameClientBuilder {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            port: PORT,
+            secure: true,
+            use_bundled_certificate: true,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            accept_invalid_hostnames: false,
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MILLIS),
+            client: None,
+        }
+    }
+}
+
+impl IngameClientBuilder {
+    /// The host to connect to, e.g. a game machine in the LAN that forwards the API \
+    /// The default is `127.0.0.1`
+    pub fn host<S: Into<String>>(mut self, host: S) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    /// The port to connect to \
+    /// The default is `2999`
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Use `https://` (default) or plain `http://` e.g. for a local mock server
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Trust the Riot Games root certificate that is bundled with the library (default)
+    pub fn use_bundled_certificate(mut self, use_bundled_certificate: bool) -> Self {
+        self.use_bundled_certificate = use_bundled_certificate;
+        self
+    }
+
+    /// Trust an additional root certificate
+    pub fn add_root_certificate(mut self, certificate: reqwest::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Disable the certificate verification \
+    /// **Only use this for local test servers**
+    pub fn danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
+    /// Disable the hostname verification, needed when connecting to a forwarded game under a different host
+    pub fn danger_accept_invalid_hostnames(mut self, accept_invalid_hostnames: bool) -> Self {
+        self.accept_invalid_hostnames = accept_invalid_hostnames;
+        self
+    }
+
+    /// Fail a request if it takes longer than the given [Duration] \
+    /// The default is 500ms
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Send the requests with the given [reqwest::Client] \
+    /// The certificate options are ignored, they have to be configured on the client instead
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.client = Some(client);
+        self
+    }
+
+    /// Builds the [IngameClient], no connection is established until the first request \
+    /// Returns an [IngameClientError::ConnectionError] if the TLS configuration is invalid
+    pub fn build(self) -> Result<IngameClient, IngameClientError> {
+        let scheme = if self.secure { "https" } else { "http" };
+        let base_url = format!("{}://{}:{}", scheme, self.host, self.port);
+
+        let client = match self.client {
+            Some(client) => client,
+            None => {
+                let mut builder = reqwest::ClientBuilder::new();
+                if self.use_bundled_certificate {
+                    let cert = reqwest::Certificate::from_pem(include_bytes!("./riotgames.pem"))
+                        .map_err(|e| IngameClientError::ConnectionError(e.to_string()))?;
+                    builder = builder.add_root_certificate(cert);
+                }
+                for cert in self.root_certificates {
+                    builder = builder.add_root_certificate(cert);
+                }
+                builder
+                    .danger_accept_invalid_certs(self.accept_invalid_certs)
+                    .danger_accept_invalid_hostnames(self.accept_invalid_hostnames)
+                    .build()
+                    .map_err(|e| IngameClientError::ConnectionError(e.to_string()))?
+            }
+        };
+
+        Ok(IngameClient {
+            client,
+            base_url,
+            timeout: self.timeout,
+        })
+    }
+}
 
 /// A wrapper around a [IngameClient] that regularly polls the ingame events
 pub struct EventStream {
@@ -402,3 +561,149 @@ impl Drop for EventStream {
         self.poll_task_handle.abort()
     }
 }
+
+/// A wrapper around a [IngameClient] that regularly polls the ingame events of all games \
+/// Unlike the [EventStream] it doesn't end when a game ends but waits for the next game to start \
+/// If the same game answers again after it was considered ended, only the events that weren't sent yet follow its [IngameStreamEvent::GameStarted]
+pub struct ContinuousEventStream {
+    start_tx: Option<Sender<()>>,
+    poll_task_handle: JoinHandle<()>,
+    events_rx: UnboundedReceiver<IngameStreamEvent>,
+}
+
+impl ContinuousEventStream {
+    /// Create a [ContinuousEventStream] from an [IngameClient] \
+    /// Takes an [Option] that specifies the polling rate of the [IngameClient] that's being wrapped \
+    /// The default [Duration] is 500ms \
+    /// Takes an [Option] that specifies how many API calls in a row can fail before the game is considered ended \
+    /// The default is 5
+    pub fn from_ingame_client(
+        ingame_client: IngameClient,
+        polling_rate: Option<Duration>,
+        max_retries: Option<u32>,
+    ) -> Self {
+        let (start_tx, start_rx) = oneshot::channel::<()>();
+        let (events_tx, events_rx) = unbounded_channel();
+
+        let poll_task_handle = tokio::spawn(async move {
+            let polling_rate =
+                polling_rate.unwrap_or(Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS));
+            let max_retries = max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
+            let mut timer = tokio::time::interval(polling_rate);
+
+            // await start, but return on error (start_tx got dropped)
+            if start_rx.await.is_err() {
+                return;
+            }
+
+            let mut cursor = EventCursor::default();
+
+            loop {
+                // wait for a game to start
+                let events = loop {
+                    timer.tick().await;
+                    if let Ok(events) = ingame_client.event_data(None).await {
+                        break events;
+                    };
+                };
+                // the API might only have been unreachable for a while, the events of the same game are not sent again
+                cursor.sync(&events);
+                if events_tx.send(IngameStreamEvent::GameStarted).is_err() {
+                    return;
+                }
+
+                let mut failed_requests = 0;
+
+                // loop until the api calls fail more than max_retries times in a row
+                loop {
+                    timer.tick().await;
+                    match ingame_client.event_data(Some(cursor.next_event_id())).await {
+                        Ok(mut events) => {
+                            failed_requests = 0;
+                            cursor.advance(&events);
+                            events.drain(..).for_each(|e| {
+                                let _ = events_tx.send(IngameStreamEvent::GameEvent(e));
+                            })
+                        }
+                        Err(_) => {
+                            failed_requests += 1;
+                            if failed_requests > max_retries {
+                                break;
+                            }
+                        }
+                    }
+                }
+                if events_tx.send(IngameStreamEvent::GameEnded).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            start_tx: Some(start_tx),
+            poll_task_handle,
+            events_rx,
+        }
+    }
+}
+
+impl Stream for ContinuousEventStream {
+    type Item = IngameStreamEvent;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if let Some(start_tx) = self.start_tx.take() {
+            if start_tx.send(()).is_err() {
+                return Poll::Ready(None);
+            }
+        }
+        self.events_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for ContinuousEventStream {
+    fn drop(&mut self) {
+        self.poll_task_handle.abort()
+    }
+}
+
+/// The position of a poller in the events of a game \
+/// Kept across failed requests, so a game that answers again after an outage doesn't get its events sent twice
+#[derive(Debug, Default)]
+pub(crate) struct EventCursor {
+    next_event_id: EventId,
+    last_event: Option<(EventId, Time)>,
+}
+
+impl EventCursor {
+    /// The id to request the events from, all earlier events were already seen
+    pub(crate) fn next_event_id(&self) -> EventId {
+        self.next_event_id
+    }
+
+    /// Marks the polled events as seen
+    pub(crate) fn advance(&mut self, events: &[GameEvent]) {
+        if let Some(last_event) = events.last() {
+            self.next_event_id = last_event.get_event_id() + 1;
+            self.last_event = Some((last_event.get_event_id(), last_event.get_event_time()));
+        }
+    }
+
+    /// Takes all events of the running game and resets the cursor if they belong to a new game \
+    /// The event ids start at 0 for every game, so it's the same game only if it still has the last seen event \
+    /// Returns true if the cursor was reset
+    pub(crate) fn sync(&mut self, events: &[GameEvent]) -> bool {
+        let same_game = match self.last_event {
+            Some((id, time)) => events
+                .iter()
+                .any(|e| e.get_event_id() == id && e.get_event_time() == time),
+            None => true,
+        };
+        if !same_game {
+            *self = Self::default();
+        }
+        !same_game
+    }
+}
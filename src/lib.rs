@@ -5,18 +5,66 @@
 //! - [RESTClient](rest::RESTClient): A REST client for the League-Client(LCU) API
 //! - [LcuWebsocketClient](ws::LcuWebsocketClient): Subscription based Websocket API for the League-Client(LCU) API
 //! - [IngameClient](ingame::IngameClient): A REST client for the LoL-Ingame API
+//! - [ProcessWatcher](utils::process_info::ProcessWatcher): Notifications when the League client starts, restarts with new credentials or exits
+//! - [discover_client_info](utils::lockfile::discover_client_info) / [LockfileWatcher](utils::lockfile::LockfileWatcher): Read the credentials from the lockfiles with a fallback to the process arguments and watch the lockfiles
+//! - [LeagueSession](session::LeagueSession): Discovers the League client, keeps the connections alive and combines LCU and ingame events into one stream
+//! - [ReplayClient](replay::ReplayClient): A REST client for the Replay API of the game to control the playback, camera and recordings
 //! - [EventStream](ingame::EventStream): A wrapper around polling ingame events implementing the [futures_util::Stream] Trait
+//! - [ContinuousEventStream](ingame::ContinuousEventStream): Like the [EventStream](ingame::EventStream) but keeps polling across games
+//! - [DiffEventStream](diff::DiffEventStream): Ingame events interleaved with events derived from changes between [AllGameData](model::ingame::AllGameData) snapshots
+//! - [ObjectiveTimerStream](timers::ObjectiveTimerStream): Spawn, respawn and buff timers of epic monsters and inhibitors
+//! - [CooldownTracker](cooldowns::CooldownTracker): Summoner spell and ultimate cooldown estimates from reported uses
+//! - [game_summary](stats::game_summary): Team aggregates, item recipe gold, kill participation, KDA and CS per minute
+//! - [plan_highlights](highlights::plan_highlights): A ranked list of highlight clips with sequences for the [ReplayClient](replay::ReplayClient)
+//! - [TimelineRecorder](timeline::TimelineRecorder) / [Timeline](timeline::Timeline): Record a game to a compressed timeline file and reconstruct the [AllGameData](model::ingame::AllGameData) at any game time
+//! - [player_ticks_table](export::player_ticks_table): Flat tables of recorded games, written as CSV (`csv` feature) or converted to Arrow record batches (`arrow` feature)
+//! - [MetricsExporter](metrics::MetricsExporter): A Prometheus `/metrics` endpoint with gauges of the ingame API and the LCU (`metrics` feature)
+//! - [OverlayServer](overlay::OverlayServer): A local WebSocket, Server-Sent Events and HTTP endpoint re-publishing game state and events for stream overlays (`overlay` feature)
+//! - [MockIngameServer](mock::MockIngameServer): A local mock of the LoL-Ingame API for tests without a running game (`mock-server` feature)
+//! - [LcuStateCache](cache::LcuStateCache): An in-memory mirror of LCU resources kept up to date by the websocket API
+//! - [LcuEventRecorder](ws_recording::LcuEventRecorder) / [LcuEventReplayer](ws_recording::LcuEventReplayer): Record websocket sessions to a file and replay them later
 //!
 //! If you are looking for a Rust library for the Riot Games API see [Riven](https://docs.rs/riven/latest/riven/)
 
+/// Contains the [LcuStateCache](cache::LcuStateCache)
+pub mod cache;
+/// Contains the [CooldownTracker](cooldowns::CooldownTracker)
+pub mod cooldowns;
+/// Contains the [GameDataDiffer](diff::GameDataDiffer) and [DiffEventStream](diff::DiffEventStream)
+pub mod diff;
 /// Error types for the whole library
 pub mod error;
-/// Contains the [IngameClient](ingame::IngameClient) and [IngameClient](ingame::EventStream)
+/// Contains the [player_ticks_table](export::player_ticks_table), [events_table](export::events_table) and [active_player_stats_table](export::active_player_stats_table) exports
+pub mod export;
+/// Contains the [plan_highlights](highlights::plan_highlights) planner
+pub mod highlights;
+/// Contains the [IngameClient](ingame::IngameClient), [EventStream](ingame::EventStream) and [ContinuousEventStream](ingame::ContinuousEventStream)
 pub mod ingame;
+/// Contains the [MetricsExporter](metrics::MetricsExporter), requires the `metrics` feature
+#[cfg(feature = "metrics")]
+pub mod metrics;
+/// Contains the [MockIngameServer](mock::MockIngameServer), requires the `mock-server` feature
+#[cfg(feature = "mock-server")]
+pub mod mock;
 /// Contains all the type definitions for the data returned by the library
 pub mod model;
+/// Contains the [OverlayServer](overlay::OverlayServer), requires the `overlay` feature
+#[cfg(feature = "overlay")]
+pub mod overlay;
+/// Contains the [ReplayClient](replay::ReplayClient)
+pub mod replay;
 /// Contains the [RESTClient](rest::RESTClient)
 pub mod rest;
+/// Contains the [LeagueSession](session::LeagueSession) and [LeagueSessionBuilder](session::LeagueSessionBuilder)
+pub mod session;
+/// Contains the [game_summary](stats::game_summary) analytics
+pub mod stats;
+/// Contains the [TimelineRecorder](timeline::TimelineRecorder), [TimelineWriter](timeline::TimelineWriter) and [Timeline](timeline::Timeline)
+pub mod timeline;
+/// Contains the [ObjectiveTracker](timers::ObjectiveTracker) and [ObjectiveTimerStream](timers::ObjectiveTimerStream)
+pub mod timers;
 pub mod utils;
 /// Contains the [LcuWebsocketClient](ws::LcuWebsocketClient)
 pub mod ws;
+/// Contains the [LcuEventRecorder](ws_recording::LcuEventRecorder) and [LcuEventReplayer](ws_recording::LcuEventReplayer)
+pub mod ws_recording;
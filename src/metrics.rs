@@ -0,0 +1,405 @@
+use std::{
+    fmt::Write,
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use serde_json::Value;
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    task::JoinHandle,
+};
+
+use crate::{
+    cache::{GAMEFLOW_PHASE, MATCHMAKING_SEARCH, RANKED_STATS},
+    ingame::IngameClient,
+    model::ingame::AllGameData,
+    rest::RESTClient,
+    stats::game_summary,
+    utils::http::{read_request, write_response},
+};
+
+const DEFAULT_POLLING_RATE_MILLIS: u64 = 1000;
+
+/// The content type of the Prometheus text exposition format
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Debug, Clone)]
+pub struct MetricsOptions {
+    /// How often the ingame API and the LCU are polled, scrapes are answered from the last poll \
+    /// The default is 1s
+    pub polling_rate: Duration,
+}
+
+impl Default for MetricsOptions {
+    fn default() -> Self {
+        Self {
+            polling_rate: Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS),
+        }
+    }
+}
+
+/// Serves a Prometheus `/metrics` endpoint with gauges of the ingame API and the LCU \
+/// Both sources are optional, a source that can't be reached reports `shaco_ingame_up 0` / `shaco_lcu_up 0`
+/// and no other metrics until it is reachable again
+pub struct MetricsExporter {
+    addr: SocketAddr,
+    poll_task_handle: JoinHandle<()>,
+    server_task_handle: JoinHandle<()>,
+}
+
+impl MetricsExporter {
+    /// Binds the endpoint to the address, e.g. `"127.0.0.1:9090"` \
+    /// Use port 0 to let the OS choose a free port, see [MetricsExporter::addr]
+    pub async fn start<A: ToSocketAddrs>(
+        addr: A,
+        ingame_client: Option<IngameClient>,
+        rest_client: Option<RESTClient>,
+        options: MetricsOptions,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let metrics = Arc::new(Mutex::new(String::new()));
+
+        let poll_metrics = metrics.clone();
+        let poll_task_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(options.polling_rate);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let mut text = String::new();
+                if let Some(client) = &ingame_client {
+                    text.push_str(&match client.all_game_data(None).await {
+                        Ok(data) => ingame_metrics(&data),
+                        Err(_) => ingame_down_metrics(),
+                    });
+                }
+                if let Some(client) = &rest_client {
+                    text.push_str(&poll_lcu(client).await);
+                }
+                *poll_metrics.lock().unwrap() = text;
+            }
+        });
+
+        let server_task_handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_connection(stream, metrics.clone()));
+            }
+        });
+
+        Ok(Self {
+            addr,
+            poll_task_handle,
+            server_task_handle,
+        })
+    }
+
+    /// The address the endpoint is bound to
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for MetricsExporter {
+    fn drop(&mut self) {
+        self.poll_task_handle.abort();
+        self.server_task_handle.abort();
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, metrics: Arc<Mutex<String>>) {
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+
+    let (status, content_type, body) = match (request.method.as_str(), request.path()) {
+        ("GET", "/metrics") => (200, CONTENT_TYPE, metrics.lock().unwrap().clone()),
+        (_, "/metrics") => (405, "text/plain", String::new()),
+        _ => (404, "text/plain", String::new()),
+    };
+    write_response(
+        &mut stream,
+        status,
+        &[("Content-Type", content_type)],
+        body.as_bytes(),
+    )
+    .await;
+}
+
+async fn poll_lcu(client: &RESTClient) -> String {
+    let Ok(gameflow_phase) = client.get(GAMEFLOW_PHASE.to_string()).await else {
+        let mut metrics = Metrics::default();
+        metrics.gauge("shaco_lcu_up", "Whether the LCU is reachable", &[], 0.0);
+        return metrics.encode();
+    };
+    // both fail with an error body while not queueing / not logged in
+    let search = client
+        .get(MATCHMAKING_SEARCH.to_string())
+        .await
+        .unwrap_or_default();
+    let ranked_stats = client
+        .get(RANKED_STATS.to_string())
+        .await
+        .unwrap_or_default();
+    lcu_metrics(&gameflow_phase, &search, &ranked_stats)
+}
+
+fn ingame_down_metrics() -> String {
+    let mut metrics = Metrics::default();
+    metrics.gauge(
+        "shaco_ingame_up",
+        "Whether a game is running and the ingame API is reachable",
+        &[],
+        0.0,
+    );
+    metrics.encode()
+}
+
+/// Encodes the gauges of an [AllGameData] in the Prometheus text format \
+/// Players are labeled with `summoner_name`, `champion_name` and `team`, the champion stats of the active player with `stat`
+pub fn ingame_metrics(data: &AllGameData) -> String {
+    let mut metrics = Metrics::default();
+    metrics.gauge(
+        "shaco_ingame_up",
+        "Whether a game is running and the ingame API is reachable",
+        &[],
+        1.0,
+    );
+    metrics.gauge(
+        "shaco_game_time_seconds",
+        "The current game time",
+        &[],
+        data.game_data.game_time,
+    );
+
+    for player in &data.all_players {
+        let champion_name = player.champion_name.to_string();
+        let team = player.team.to_string();
+        let labels = [
+            ("summoner_name", player.summoner_name.as_str()),
+            ("champion_name", champion_name.as_str()),
+            ("team", team.as_str()),
+        ];
+        let scores = &player.scores;
+        metrics.gauge(
+            "shaco_player_kills",
+            "The kills of the player",
+            &labels,
+            scores.kills as f64,
+        );
+        metrics.gauge(
+            "shaco_player_deaths",
+            "The deaths of the player",
+            &labels,
+            scores.deaths as f64,
+        );
+        metrics.gauge(
+            "shaco_player_assists",
+            "The assists of the player",
+            &labels,
+            scores.assists as f64,
+        );
+        metrics.gauge(
+            "shaco_player_creep_score",
+            "The creep score of the player",
+            &labels,
+            scores.creep_score as f64,
+        );
+        metrics.gauge(
+            "shaco_player_level",
+            "The champion level of the player",
+            &labels,
+            player.level as f64,
+        );
+    }
+
+    let summary = game_summary(data);
+    // the summary lists the players in the order of all_players
+    for (player, stats) in data.all_players.iter().zip(&summary.players) {
+        let champion_name = player.champion_name.to_string();
+        let team = player.team.to_string();
+        let labels = [
+            ("summoner_name", player.summoner_name.as_str()),
+            ("champion_name", champion_name.as_str()),
+            ("team", team.as_str()),
+        ];
+        metrics.gauge(
+            "shaco_player_kda",
+            "(kills + assists) / max(deaths, 1) of the player",
+            &labels,
+            stats.kda as f64,
+        );
+    }
+    for team in &summary.teams {
+        let team_name = team.team.to_string();
+        let labels = [("team", team_name.as_str())];
+        metrics.gauge(
+            "shaco_team_kills",
+            "The kills of the team",
+            &labels,
+            team.kills as f64,
+        );
+        metrics.gauge(
+            "shaco_team_recipe_gold",
+            "The sum of the recipe costs of the items of the team, see stats::recipe_gold",
+            &labels,
+            team.recipe_gold as f64,
+        );
+    }
+
+    if let Some(active_player) = &data.active_player {
+        let labels = [("summoner_name", active_player.summoner_name.as_str())];
+        metrics.gauge(
+            "shaco_active_player_current_gold",
+            "The unspent gold of the active player",
+            &labels,
+            active_player.current_gold as f64,
+        );
+        metrics.gauge(
+            "shaco_active_player_level",
+            "The champion level of the active player",
+            &labels,
+            active_player.level as f64,
+        );
+        // every numeric stat, so stats added to the API show up without changes here
+        if let Ok(Value::Object(stats)) = serde_json::to_value(&active_player.champion_stats) {
+            for (stat, value) in stats {
+                let Some(value) = value.as_f64() else {
+                    continue;
+                };
+                metrics.gauge(
+                    "shaco_active_player_champion_stat",
+                    "The champion stats of the active player by their API name",
+                    &[
+                        ("summoner_name", active_player.summoner_name.as_str()),
+                        ("stat", stat.as_str()),
+                    ],
+                    value,
+                );
+            }
+        }
+    }
+
+    metrics.encode()
+}
+
+/// Encodes the gauges of the raw LCU responses of [GAMEFLOW_PHASE], [MATCHMAKING_SEARCH] and [RANKED_STATS]
+/// in the Prometheus text format \
+/// Responses that aren't in the expected shape, e.g. the error bodies while not queueing, are skipped
+pub fn lcu_metrics(gameflow_phase: &Value, search: &Value, ranked_stats: &Value) -> String {
+    let mut metrics = Metrics::default();
+    metrics.gauge("shaco_lcu_up", "Whether the LCU is reachable", &[], 1.0);
+
+    if let Some(phase) = gameflow_phase.as_str() {
+        metrics.gauge(
+            "shaco_gameflow_phase",
+            "The current gameflow phase, always 1",
+            &[("phase", phase)],
+            1.0,
+        );
+    }
+
+    if let Some(time_in_queue) = search["timeInQueue"].as_f64() {
+        metrics.gauge(
+            "shaco_queue_time_seconds",
+            "The time spent in the matchmaking queue",
+            &[],
+            time_in_queue,
+        );
+    }
+    if let Some(estimated_queue_time) = search["estimatedQueueTime"].as_f64() {
+        metrics.gauge(
+            "shaco_queue_estimated_time_seconds",
+            "The estimated time of the matchmaking queue",
+            &[],
+            estimated_queue_time,
+        );
+    }
+
+    for queue in ranked_stats["queues"].as_array().into_iter().flatten() {
+        let (Some(queue_type), Some(league_points)) =
+            (queue["queueType"].as_str(), queue["leaguePoints"].as_f64())
+        else {
+            continue;
+        };
+        metrics.gauge(
+            "shaco_ranked_league_points",
+            "The league points per ranked queue",
+            &[
+                ("queue_type", queue_type),
+                ("tier", queue["tier"].as_str().unwrap_or_default()),
+                ("division", queue["division"].as_str().unwrap_or_default()),
+            ],
+            league_points,
+        );
+    }
+
+    metrics.encode()
+}
+
+/// Collects the samples per metric family, so every family is written once with its HELP and TYPE lines
+#[derive(Default)]
+struct Metrics {
+    families: Vec<Family>,
+}
+
+struct Family {
+    name: &'static str,
+    help: &'static str,
+    samples: Vec<String>,
+}
+
+impl Metrics {
+    fn gauge(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&str, &str)],
+        value: f64,
+    ) {
+        let family = match self.families.iter().position(|f| f.name == name) {
+            Some(index) => &mut self.families[index],
+            None => {
+                self.families.push(Family {
+                    name,
+                    help,
+                    samples: Vec::new(),
+                });
+                self.families.last_mut().unwrap()
+            }
+        };
+
+        let mut sample = name.to_string();
+        if !labels.is_empty() {
+            let labels = labels
+                .iter()
+                .map(|(key, value)| format!("{key}=\"{}\"", escape(value)))
+                .collect::<Vec<_>>()
+                .join(",");
+            let _ = write!(sample, "{{{labels}}}");
+        }
+        let _ = write!(sample, " {value}");
+        family.samples.push(sample);
+    }
+
+    fn encode(&self) -> String {
+        let mut text = String::new();
+        for family in &self.families {
+            let _ = writeln!(text, "# HELP {} {}", family.name, family.help);
+            let _ = writeln!(text, "# TYPE {} gauge", family.name);
+            for sample in &family.samples {
+                let _ = writeln!(text, "{sample}");
+            }
+        }
+        text
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
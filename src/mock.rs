@@ -0,0 +1,404 @@
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde_json::{json, Value};
+use tokio::{
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+
+use crate::{
+    ingame::IngameClient,
+    model::ingame::{AllGameData, Time},
+    utils::http::{read_request, write_response, Request},
+};
+
+/// The state of the game served by a [MockIngameServer]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockGamePhase {
+    /// No game is running, every request fails
+    NoGame,
+    /// Only `/Help` is available, every other API returns 404
+    LoadingScreen,
+    InGame,
+}
+
+/// A scripted or recorded game served by a [MockIngameServer] \
+/// Events are only served once the game time passed their `EventTime`,
+/// so a recording of a whole game is replayed by advancing the game time
+#[derive(Debug, Clone)]
+pub struct MockGame {
+    /// The raw `/liveclientdata/allgamedata` response
+    data: Value,
+    spectator: bool,
+    replay: bool,
+}
+
+impl MockGame {
+    /// Takes a raw `/liveclientdata/allgamedata` response, e.g. a recorded one \
+    /// Returns an error if it isn't a valid [AllGameData]
+    pub fn from_all_game_data(data: Value) -> Result<Self, serde_json::Error> {
+        serde_json::from_value::<AllGameData>(data.clone())?;
+        Ok(Self {
+            data,
+            spectator: false,
+            replay: false,
+        })
+    }
+
+    /// Serve the game in spectator mode \
+    /// The active player APIs return 400 like the real API does when spectating
+    pub fn spectator(mut self, spectator: bool) -> Self {
+        self.spectator = spectator;
+        self
+    }
+
+    /// Serve the Replay API, disabled by default like in the game \
+    /// Seeking, pausing and the playback speed control the game time of the [MockIngameServer]
+    pub fn replay(mut self, replay: bool) -> Self {
+        self.replay = replay;
+        self
+    }
+}
+
+#[derive(Debug)]
+struct MockState {
+    game: MockGame,
+    phase: MockGamePhase,
+    /// The game time when the clock was anchored
+    game_time: Time,
+    anchor: Instant,
+    time_scale: f64,
+    /// The playback speed of the Replay API, kept while the game is paused
+    speed: f64,
+    render: Value,
+    recording: Value,
+    sequence: Value,
+}
+
+impl MockState {
+    fn game_time(&self) -> Time {
+        self.game_time + self.anchor.elapsed().as_secs_f64() * self.time_scale
+    }
+
+    fn anchor(&mut self) {
+        self.game_time = self.game_time();
+        self.anchor = Instant::now();
+    }
+}
+
+/// A local HTTP server imitating the LoL-Ingame API for tests without a running game \
+/// Serves the `/GetLiveclientdata*` and `/liveclientdata/*` endpoints on a random port of 127.0.0.1,
+/// and the `/replay/*` endpoints if enabled with [MockGame::replay] \
+/// The game time is paused at 0 until it's advanced or [MockIngameServer::set_time_scale] is called
+pub struct MockIngameServer {
+    addr: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    server_task_handle: JoinHandle<()>,
+}
+
+impl MockIngameServer {
+    /// Start serving the game, the server is in [MockGamePhase::InGame]
+    pub async fn start(game: MockGame) -> io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = listener.local_addr()?;
+        let state = Arc::new(Mutex::new(MockState {
+            game,
+            phase: MockGamePhase::InGame,
+            game_time: 0.0,
+            anchor: Instant::now(),
+            time_scale: 0.0,
+            speed: 1.0,
+            render: json!({
+                "cameraMode": "top",
+                "fieldOfView": 45.0,
+                "fogOfWar": true,
+                "healthBarChampions": true,
+                "interfaceAll": true,
+            }),
+            recording: json!({
+                "codec": "webm",
+                "framesPerSecond": 60,
+                "recording": false,
+            }),
+            sequence: json!({}),
+        }));
+
+        let server_state = state.clone();
+        let server_task_handle = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(handle_connection(stream, server_state.clone()));
+            }
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            server_task_handle,
+        })
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The base url to point an [IngameClient] at, e.g. `http://127.0.0.1:12345`
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.addr)
+    }
+
+    /// An [IngameClient] connected to the server
+    pub fn client(&self) -> IngameClient {
+        IngameClient::builder()
+            .secure(false)
+            .port(self.addr.port())
+            .build()
+            .expect("a plain http client is always valid")
+    }
+
+    pub fn set_phase(&self, phase: MockGamePhase) {
+        self.state.lock().unwrap().phase = phase;
+    }
+
+    /// Replace the served game, e.g. to start the next game
+    pub fn set_game(&self, game: MockGame) {
+        self.state.lock().unwrap().game = game;
+    }
+
+    pub fn game_time(&self) -> Time {
+        self.state.lock().unwrap().game_time()
+    }
+
+    pub fn set_game_time(&self, game_time: Time) {
+        let mut state = self.state.lock().unwrap();
+        state.game_time = game_time;
+        state.anchor = Instant::now();
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.anchor();
+        state.game_time += duration.as_secs_f64();
+    }
+
+    /// How fast the game time advances on its own, 1.0 is real time and 0.0 pauses the game
+    pub fn set_time_scale(&self, time_scale: f64) {
+        let mut state = self.state.lock().unwrap();
+        state.anchor();
+        state.time_scale = time_scale;
+        if time_scale > 0.0 {
+            state.speed = time_scale;
+        }
+    }
+
+    /// The render state of the Replay API as set by the client
+    pub fn replay_render(&self) -> Value {
+        self.state.lock().unwrap().render.clone()
+    }
+
+    /// The recording state of the Replay API as set by the client
+    pub fn replay_recording(&self) -> Value {
+        self.state.lock().unwrap().recording.clone()
+    }
+
+    /// The keyframe sequence of the Replay API as set by the client
+    pub fn replay_sequence(&self) -> Value {
+        self.state.lock().unwrap().sequence.clone()
+    }
+}
+
+impl Drop for MockIngameServer {
+    fn drop(&mut self) {
+        self.server_task_handle.abort()
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, state: Arc<Mutex<MockState>>) {
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+    // only the Replay API takes a body
+    let request_body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+
+    let (status, body) = {
+        let mut state = state.lock().unwrap();
+        if let Some(endpoint) = request.path().strip_prefix("/replay/") {
+            respond_replay(&mut state, &request.method, endpoint, request_body)
+        } else {
+            respond(&state, &request)
+        }
+    };
+
+    let body = if request.method == "HEAD" {
+        String::new()
+    } else {
+        body.to_string()
+    };
+    write_response(
+        &mut stream,
+        status,
+        &[("Content-Type", "application/json")],
+        body.as_bytes(),
+    )
+    .await;
+}
+
+/// Routes a request target like `/GetLiveclientdataPlayeritems?summonerName=name` or `/liveclientdata/playeritems?summonerName=name`
+fn respond(state: &MockState, request: &Request) -> (u16, Value) {
+    let path = request.path();
+    let param = |name: &str| request.param(name);
+
+    match state.phase {
+        MockGamePhase::NoGame => return (503, json!({ "error": "no game running" })),
+        MockGamePhase::LoadingScreen if path != "/Help" => {
+            return (404, json!({ "error": "loading screen" }))
+        }
+        _ => {}
+    }
+
+    let endpoint = if let Some(endpoint) = path.strip_prefix("/GetLiveclientdata") {
+        endpoint.to_lowercase()
+    } else if let Some(endpoint) = path.strip_prefix("/liveclientdata/") {
+        endpoint.to_string()
+    } else if path == "/Help" {
+        return (200, json!({}));
+    } else {
+        return (404, json!({ "error": "unknown endpoint" }));
+    };
+
+    let data = &state.game.data;
+    let game_time = state.game_time();
+    let event_id = param("eventID")
+        .and_then(|id| id.parse::<u64>().ok())
+        .unwrap_or(0);
+    let events: Vec<Value> = data["events"]["Events"]
+        .as_array()
+        .map(|events| {
+            events
+                .iter()
+                .filter(|e| e["EventTime"].as_f64().unwrap_or_default() <= game_time)
+                .filter(|e| e["EventID"].as_u64().unwrap_or_default() >= event_id)
+                .cloned()
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut game_stats = data["gameData"].clone();
+    game_stats["gameTime"] = json!(game_time);
+
+    let player = |field: &str| {
+        let name = param("summonerName").unwrap_or_default();
+        data["allPlayers"]
+            .as_array()
+            .and_then(|players| players.iter().find(|p| p["summonerName"] == name))
+            .map(|p| (200, p[field].clone()))
+            .unwrap_or((500, json!({ "error": "unknown summoner" })))
+    };
+
+    let spectator = state.game.spectator;
+    if spectator && endpoint.starts_with("activeplayer") {
+        return (400, json!({ "error": "not available in spectator mode" }));
+    }
+
+    match endpoint.as_str() {
+        "allgamedata" => {
+            let mut all_game_data = data.clone();
+            all_game_data["events"] = json!({ "Events": events });
+            all_game_data["gameData"] = game_stats;
+            if spectator {
+                all_game_data["activePlayer"] =
+                    json!({ "error": "Spectator mode doesn't currently support this feature" });
+            }
+            (200, all_game_data)
+        }
+        "eventdata" => (200, json!({ "Events": events })),
+        "gamestats" => (200, game_stats),
+        "playerlist" => {
+            let team = param("teamID").unwrap_or_else(|| "ALL".to_string());
+            let players: Vec<Value> = data["allPlayers"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter(|p| team == "ALL" || p["team"] == team.as_str())
+                .cloned()
+                .collect();
+            (200, json!(players))
+        }
+        "playeritems" => player("items"),
+        "playermainrunes" => player("runes"),
+        "playerscores" => player("scores"),
+        "playersummonerspells" => player("summonerSpells"),
+        "activeplayer" => (200, data["activePlayer"].clone()),
+        "activeplayerabilities" => (200, data["activePlayer"]["abilities"].clone()),
+        "activeplayername" => (200, data["activePlayer"]["summonerName"].clone()),
+        "activeplayerrunes" => (200, data["activePlayer"]["fullRunes"].clone()),
+        _ => (404, json!({ "error": "unknown endpoint" })),
+    }
+}
+
+/// Routes a request of the Replay API like `/replay/playback`, a POST changes the state and returns the new state
+fn respond_replay(
+    state: &mut MockState,
+    method: &str,
+    endpoint: &str,
+    body: Value,
+) -> (u16, Value) {
+    if !state.game.replay || state.phase != MockGamePhase::InGame {
+        return (404, json!({ "error": "replay api not enabled" }));
+    }
+
+    let post = method == "POST";
+    match endpoint {
+        "game" => (200, json!({ "processID": std::process::id() })),
+        "playback" => {
+            if post {
+                state.anchor();
+                if let Some(time) = body["time"].as_f64() {
+                    state.game_time = time;
+                }
+                if let Some(speed) = body["speed"].as_f64() {
+                    state.speed = speed;
+                    if state.time_scale > 0.0 {
+                        state.time_scale = speed;
+                    }
+                }
+                if let Some(paused) = body["paused"].as_bool() {
+                    state.time_scale = if paused { 0.0 } else { state.speed };
+                }
+            }
+            let length = state.game.data["gameData"]["gameTime"]
+                .as_f64()
+                .unwrap_or_default();
+            (
+                200,
+                json!({
+                    "length": length,
+                    "paused": state.time_scale == 0.0,
+                    "seeking": false,
+                    "speed": state.speed,
+                    "time": state.game_time(),
+                }),
+            )
+        }
+        "render" => (200, replay_resource(&mut state.render, post, body)),
+        "recording" => (200, replay_resource(&mut state.recording, post, body)),
+        // a sequence replaces the previous one instead of being merged into it
+        "sequence" => {
+            if post {
+                state.sequence = body;
+            }
+            (200, state.sequence.clone())
+        }
+        _ => (404, json!({ "error": "unknown endpoint" })),
+    }
+}
+
+fn replay_resource(resource: &mut Value, post: bool, body: Value) -> Value {
+    if let (true, Value::Object(changes), Some(resource)) = (post, body, resource.as_object_mut()) {
+        resource.extend(changes);
+    }
+    resource.clone()
+}
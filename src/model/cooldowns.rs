@@ -0,0 +1,32 @@
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::model::ingame::{AbilityHaste, SummonerName, Time};
+
+/// The abilities tracked by the [CooldownTracker](crate::cooldowns::CooldownTracker)
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CooldownSlot {
+    SummonerSpellOne,
+    SummonerSpellTwo,
+    Ultimate,
+}
+
+/// A reported use of an ability and the estimated time it is available again
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cooldown {
+    pub summoner_name: SummonerName,
+    pub slot: CooldownSlot,
+    /// the spell key for summoner spells, e.g. `SummonerFlash` - None for ultimates
+    pub spell: Option<String>,
+    pub used_at: Time,
+    /// the haste that was applied to the base cooldown
+    pub haste: AbilityHaste,
+    pub ready_at: Time,
+}
+
+impl Cooldown {
+    /// The seconds until the ability is available again at the given game time
+    pub fn remaining(&self, game_time: Time) -> Time {
+        (self.ready_at - game_time).max(0.0)
+    }
+}
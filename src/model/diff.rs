@@ -0,0 +1,111 @@
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::model::ingame::{
+    AbilityLevel, CreepScore, GameEvent, Gold, ItemCount, Level, PlayerItem, SummonerName, Time,
+    WardScore,
+};
+
+/// The items of a [DiffEventStream](crate::diff::DiffEventStream) \
+/// Native events of the ingame API interleaved with the events derived from the changes between two snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum IngameDiffEvent {
+    Native(GameEvent),
+    Derived(DerivedEvent),
+}
+
+impl IngameDiffEvent {
+    pub fn get_event_time(&self) -> Time {
+        match self {
+            IngameDiffEvent::Native(e) => e.get_event_time(),
+            IngameDiffEvent::Derived(e) => e.get_event_time(),
+        }
+    }
+}
+
+/// An event derived from the changes between two consecutive [AllGameData](crate::model::ingame::AllGameData) snapshots
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum DerivedEvent {
+    /// A change of any player in the game
+    Player {
+        summoner_name: SummonerName,
+        /// the game time of the snapshot the change was detected in
+        game_time: Time,
+        change: PlayerChange,
+    },
+    /// A change only visible for the active player \
+    /// only available in live game - never emitted in spectator mode
+    ActivePlayer {
+        /// the game time of the snapshot the change was detected in
+        game_time: Time,
+        change: ActivePlayerChange,
+    },
+}
+
+impl DerivedEvent {
+    pub fn get_event_time(&self) -> Time {
+        match self {
+            DerivedEvent::Player { game_time, .. } => *game_time,
+            DerivedEvent::ActivePlayer { game_time, .. } => *game_time,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum PlayerChange {
+    LevelUp {
+        level: Level,
+    },
+    /// A new item appeared in the inventory
+    ItemPurchased {
+        item: PlayerItem,
+    },
+    /// An item disappeared from the inventory without anything being bought
+    ItemSold {
+        item: PlayerItem,
+    },
+    /// An item disappeared from the inventory shortly after it was bought
+    ItemUndone {
+        item: PlayerItem,
+    },
+    /// An item disappeared from the inventory while another item was bought, e.g. a component that got combined
+    ItemCombined {
+        item: PlayerItem,
+    },
+    /// The count of a consumable item decreased, e.g. a potion was used
+    ItemConsumed {
+        item: PlayerItem,
+        /// the count before the item was consumed
+        previous_count: ItemCount,
+    },
+    Died {
+        respawn_timer: Time,
+    },
+    Respawned,
+    WardScoreChanged {
+        previous: WardScore,
+        current: WardScore,
+    },
+    /// The creep score reached a multiple of the configured milestone interval
+    CreepScoreMilestone {
+        creep_score: CreepScore,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ActivePlayerChange {
+    AbilityLeveled {
+        ability: AbilitySlot,
+        level: AbilityLevel,
+    },
+    /// The current gold increased by at least the configured threshold between two snapshots
+    GoldSpike { amount: Gold, current_gold: Gold },
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AbilitySlot {
+    Q,
+    W,
+    E,
+    R,
+}
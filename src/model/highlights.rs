@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::ingame::{DragonType, EventId, KillStreak, SummonerName, Time};
+
+/// Why a moment is worth a highlight
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum HighlightReason {
+    Kill,
+    FirstBlood,
+    Multikill {
+        kill_streak: KillStreak,
+    },
+    Ace,
+    BaronKill {
+        stolen: bool,
+    },
+    DragonKill {
+        dragon_type: DragonType,
+        stolen: bool,
+    },
+}
+
+/// A scored ingame event, see [score_moments](crate::highlights::score_moments)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightMoment {
+    pub event_id: EventId,
+    pub time: Time,
+    pub reason: HighlightReason,
+    /// The player that made the play - None if it wasn't a summoner, e.g. a dragon secured by minions
+    pub player: Option<SummonerName>,
+    pub score: f32,
+}
+
+/// A range of game time worth watching, see [plan_highlights](crate::highlights::plan_highlights)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HighlightClip {
+    /// The game time the clip starts at, including the lead-in
+    pub start: Time,
+    /// The game time the clip ends at, including the lead-out
+    pub end: Time,
+    /// The player with the highest score of the clip
+    pub focus_player: Option<SummonerName>,
+    /// The reason of the moment with the highest score
+    pub reason: HighlightReason,
+    /// The sum of the scores of the moments
+    pub score: f32,
+    /// The moments of the clip ordered by game time
+    pub moments: Vec<HighlightMoment>,
+}
+
+impl HighlightClip {
+    pub fn duration(&self) -> Time {
+        self.end - self.start
+    }
+
+    /// The moment with the highest score, the first one on a tie
+    pub fn top_moment(&self) -> Option<&HighlightMoment> {
+        self.moments
+            .iter()
+            .reduce(|top, m| if m.score > top.score { m } else { top })
+    }
+}
@@ -1,14 +1,14 @@
 use std::{fmt, str::FromStr};
 
 use derive_more::Display;
-use serde::{Deserialize, Deserializer, Serialize};
-use serde_with::DeserializeFromStr;
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_with::{DeserializeFromStr, SerializeDisplay};
 
 pub type SummonerName = String;
 pub type Time = f64;
 
 #[derive(Debug, Clone, Serialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct AllGameData {
     /// only available in live game - None in spectator mode
@@ -25,7 +25,7 @@ impl<'de> Deserialize<'de> for AllGameData {
         D: Deserializer<'de>,
     {
         #[derive(Debug, Clone, Serialize, Deserialize)]
-        #[serde(deny_unknown_fields)]
+        #[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
         #[serde(rename_all = "camelCase")]
         struct Holder {
             /// only available in live game - None in spectator mode
@@ -62,7 +62,7 @@ pub(crate) enum ActivePlayerInfo {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct ActivePlayer {
     pub abilities: PlayerAbilities,
@@ -76,7 +76,7 @@ pub struct ActivePlayer {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct PlayerAbilities {
     pub e: Ability,
@@ -90,7 +90,7 @@ pub type AbilityLevel = i32;
 pub type AbilityName = String;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct Ability {
     pub ability_level: AbilityLevel,
@@ -101,7 +101,7 @@ pub struct Ability {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct Passive {
     pub display_name: AbilityName,
@@ -142,7 +142,7 @@ pub type SpellVamp = f32;
 pub type Tenacity = f32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerChampionStats {
     pub ability_haste: AbilityHaste,
@@ -179,7 +179,7 @@ pub struct PlayerChampionStats {
 }
 
 #[derive(Debug, Display, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "UPPERCASE")]
 pub enum ResourceType {
     Mana,
@@ -203,7 +203,7 @@ pub enum ResourceType {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct FullPlayerRunes {
     pub general_runes: Vec<Rune>,
@@ -214,7 +214,7 @@ pub struct FullPlayerRunes {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerRunes {
     pub keystone: Rune,
@@ -226,7 +226,7 @@ pub type RuneId = i32;
 pub type RuneName = String;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct Rune {
     pub display_name: RuneName,
@@ -239,7 +239,7 @@ pub type RuneTreeId = i32;
 pub type RuneTreeName = String;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct RuneTree {
     pub display_name: RuneTreeName,
@@ -251,7 +251,7 @@ pub struct RuneTree {
 pub type StatRuneId = i32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct StatRune {
     pub id: StatRuneId,
@@ -263,7 +263,7 @@ pub type SkinName = String;
 pub type SkinId = i32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct Player {
     pub champion_name: ChampionName,
@@ -285,6 +285,93 @@ pub struct Player {
     pub summoner_name: SummonerName,
     pub summoner_spells: SummonerSpells,
     pub team: TeamId,
+    /// only available in Arena games - the duo the player belongs to \
+    /// Not confirmed against the live API yet, the name (`subteamId` or `subteamID`) is modelled after a synthetic fixture
+    #[serde(default, alias = "subteamID", skip_serializing_if = "Option::is_none")]
+    pub subteam_id: Option<SubteamId>,
+    /// only available in Arena games
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub augments: Vec<Augment>,
+    /// only available in Swarm games \
+    /// Not confirmed against the live API yet, see [PveStats]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pve_stats: Option<PveStats>,
+}
+
+impl Player {
+    /// The Arena subteam of the player - None outside of Arena games
+    pub fn subteam(&self) -> Option<Subteam> {
+        self.subteam_id.map(Subteam::from_id)
+    }
+}
+
+pub type SubteamId = i32;
+
+/// The duos of an Arena game, named after their icons \
+/// The ids of the duos are not confirmed against the live API yet
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Subteam {
+    Poro,
+    Minion,
+    Scuttle,
+    Krug,
+    Raptor,
+    Sentinel,
+    Wolf,
+    Gromp,
+    Unknown,
+}
+
+impl Subteam {
+    pub fn from_id(id: SubteamId) -> Self {
+        match id {
+            1 => Subteam::Poro,
+            2 => Subteam::Minion,
+            3 => Subteam::Scuttle,
+            4 => Subteam::Krug,
+            5 => Subteam::Raptor,
+            6 => Subteam::Sentinel,
+            7 => Subteam::Wolf,
+            8 => Subteam::Gromp,
+            _ => Subteam::Unknown,
+        }
+    }
+}
+
+pub type AugmentId = i32;
+pub type AugmentName = String;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "camelCase")]
+pub struct Augment {
+    pub display_name: AugmentName,
+    pub id: AugmentId,
+    pub rarity: AugmentRarity,
+    pub raw_description: String,
+    pub raw_display_name: String,
+}
+
+#[derive(Debug, Display, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "lowercase")]
+pub enum AugmentRarity {
+    Silver,
+    Gold,
+    Prismatic,
+    #[serde(other)]
+    Unknown,
+}
+
+/// Player vs. Environment stats of a Swarm game \
+/// Not confirmed against the live API yet, the fields are modelled after a synthetic fixture.
+/// Missing fields default to 0 and unknown fields are ignored even with the `strict` feature
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct PveStats {
+    pub enemies_killed: Kills,
+    pub bosses_killed: Kills,
+    pub damage_dealt: f32,
 }
 
 pub type ItemCount = i32;
@@ -294,7 +381,7 @@ pub type Price = i32;
 pub type ItemSlot = i32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerItem {
     pub can_use: bool,
@@ -303,6 +390,7 @@ pub struct PlayerItem {
     pub display_name: ItemName,
     #[serde(alias = "itemID")]
     pub item_id: ItemId,
+    /// The gold paid to combine the item from its components, not its total cost
     pub price: Price,
     pub raw_description: String,
     pub raw_display_name: String,
@@ -310,7 +398,7 @@ pub struct PlayerItem {
 }
 
 #[derive(Debug, Display, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "UPPERCASE")]
 pub enum Position {
     Top,
@@ -330,7 +418,7 @@ pub type CreepScore = i32;
 pub type WardScore = f32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct PlayerScores {
     pub kills: Kills,
@@ -341,7 +429,7 @@ pub struct PlayerScores {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct SummonerSpells {
     pub summoner_spell_one: SummonerSpell,
@@ -351,7 +439,7 @@ pub struct SummonerSpells {
 pub type SummonerSpellName = String;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct SummonerSpell {
     pub display_name: SummonerSpellName,
@@ -359,9 +447,18 @@ pub struct SummonerSpell {
     pub raw_display_name: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-#[serde(rename_all = "UPPERCASE")]
+impl SummonerSpell {
+    /// The language independent name of the spell, e.g. `SummonerFlash` \
+    /// Parsed from the raw display name `GeneratedTip_SummonerSpell_SummonerFlash_DisplayName`
+    pub fn spell_key(&self) -> Option<&str> {
+        self.raw_display_name
+            .strip_prefix("GeneratedTip_SummonerSpell_")?
+            .strip_suffix("_DisplayName")
+    }
+}
+
+/// Teams beyond ORDER and CHAOS, e.g. in Arena games, are kept as [TeamId::Other]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, DeserializeFromStr, SerializeDisplay)]
 pub enum TeamId {
     All,
     /// Blue / Left Side
@@ -369,14 +466,32 @@ pub enum TeamId {
     /// Red / Right Side
     Chaos,
     Neutral,
-    #[serde(other)]
-    Unknown,
+    Other(String),
+}
+
+impl FromStr for TeamId {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "ALL" => TeamId::All,
+            "ORDER" => TeamId::Order,
+            "CHAOS" => TeamId::Chaos,
+            "NEUTRAL" => TeamId::Neutral,
+            _ => TeamId::Other(s.to_string()),
+        })
+    }
 }
 
 impl fmt::Display for TeamId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let uppercase = format!("{:?}", self).to_uppercase();
-        write!(f, "{uppercase}")
+        match self {
+            TeamId::Other(team) => write!(f, "{team}"),
+            team => {
+                let uppercase = format!("{:?}", team).to_uppercase();
+                write!(f, "{uppercase}")
+            }
+        }
     }
 }
 
@@ -384,78 +499,160 @@ pub type EventId = u32;
 
 /// only pub(crate) since this is an intermediate result. The API only returns the Vec<GameEvent>
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub(crate) struct IngameEvents {
     pub events: Vec<GameEvent>,
 }
 
+/// The items of a [ContinuousEventStream](crate::ingame::ContinuousEventStream) \
+/// Every game is framed by a [IngameStreamEvent::GameStarted] and a [IngameStreamEvent::GameEnded]
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
-#[serde(tag = "EventName")]
-pub enum GameEvent {
-    Ace(Ace),
-    BaronKill(BaronKill),
-    ChampionKill(ChampionKill),
-    DragonKill(DragonKill),
-    FirstBlood(FirstBlood),
-    FirstBrick(FirstBrick),
-    GameEnd(GameEnd),
-    GameStart(GameStart),
-    HeraldKill(HeraldKill),
-    InhibKilled(InhibKilled),
-    InhibRespawned(InhibRespawned),
-    InhibRespawningSoon(InhibRespawningSoon),
-    MinionsSpawning(MinionsSpawning),
-    Multikill(Multikill),
-    TurretKilled(TurretKilled),
-}
-
-/// event_id and event_time are the only fields all enum variants have in common
-impl GameEvent {
-    pub fn get_event_id(&self) -> EventId {
-        match self {
-            GameEvent::Ace(e) => e.event_id,
-            GameEvent::BaronKill(e) => e.event_id,
-            GameEvent::ChampionKill(e) => e.event_id,
-            GameEvent::DragonKill(e) => e.event_id,
-            GameEvent::FirstBlood(e) => e.event_id,
-            GameEvent::FirstBrick(e) => e.event_id,
-            GameEvent::GameEnd(e) => e.event_id,
-            GameEvent::GameStart(e) => e.event_id,
-            GameEvent::HeraldKill(e) => e.event_id,
-            GameEvent::InhibKilled(e) => e.event_id,
-            GameEvent::InhibRespawned(e) => e.event_id,
-            GameEvent::InhibRespawningSoon(e) => e.event_id,
-            GameEvent::MinionsSpawning(e) => e.event_id,
-            GameEvent::Multikill(e) => e.event_id,
-            GameEvent::TurretKilled(e) => e.event_id,
+pub enum IngameStreamEvent {
+    /// The ingame API became available
+    GameStarted,
+    GameEvent(GameEvent),
+    /// The ingame API is no longer available
+    GameEnded,
+}
+
+/// Defines [GameEvent] with a variant for every known event and the mirrors used to (de)serialize it
+macro_rules! game_events {
+    ($($name:ident),* $(,)?) => {
+        /// Events that are not known to the library are returned as [GameEvent::Unknown] instead of failing the deserialization
+        #[derive(Debug, Clone)]
+        pub enum GameEvent {
+            $($name($name),)*
+            /// An event that is not known to the library (yet) \
+            /// Without the `strict` feature known events that don't match their definition are returned as Unknown as well
+            Unknown {
+                name: String,
+                event_id: EventId,
+                event_time: Time,
+                /// The event as it was returned by the API
+                raw: serde_json::Value,
+                /// Why a known event didn't match its definition, None if the name of the event is not known
+                error: Option<String>,
+            },
         }
-    }
 
-    pub fn get_event_time(&self) -> Time {
-        match self {
-            GameEvent::Ace(e) => e.event_time,
-            GameEvent::BaronKill(e) => e.event_time,
-            GameEvent::ChampionKill(e) => e.event_time,
-            GameEvent::DragonKill(e) => e.event_time,
-            GameEvent::FirstBlood(e) => e.event_time,
-            GameEvent::FirstBrick(e) => e.event_time,
-            GameEvent::GameEnd(e) => e.event_time,
-            GameEvent::GameStart(e) => e.event_time,
-            GameEvent::HeraldKill(e) => e.event_time,
-            GameEvent::InhibKilled(e) => e.event_time,
-            GameEvent::InhibRespawned(e) => e.event_time,
-            GameEvent::InhibRespawningSoon(e) => e.event_time,
-            GameEvent::MinionsSpawning(e) => e.event_time,
-            GameEvent::Multikill(e) => e.event_time,
-            GameEvent::TurretKilled(e) => e.event_time,
+        /// Mirror of the known [GameEvent] variants to derive the deserialization
+        #[derive(Deserialize)]
+        #[serde(tag = "EventName")]
+        enum KnownGameEvent {
+            $($name($name),)*
+        }
+
+        impl From<KnownGameEvent> for GameEvent {
+            fn from(event: KnownGameEvent) -> Self {
+                match event {
+                    $(KnownGameEvent::$name(e) => GameEvent::$name(e),)*
+                }
+            }
+        }
+
+        /// Mirror of the known [GameEvent] variants to derive the serialization
+        #[derive(Serialize)]
+        #[serde(tag = "EventName")]
+        enum KnownGameEventRef<'a> {
+            $($name(&'a $name),)*
+        }
+
+        const KNOWN_EVENT_NAMES: &[&str] = &[$(stringify!($name),)*];
+
+        /// event_id and event_time are the only fields all enum variants have in common
+        impl GameEvent {
+            pub fn get_event_id(&self) -> EventId {
+                match self {
+                    $(GameEvent::$name(e) => e.event_id,)*
+                    GameEvent::Unknown { event_id, .. } => *event_id,
+                }
+            }
+
+            pub fn get_event_time(&self) -> Time {
+                match self {
+                    $(GameEvent::$name(e) => e.event_time,)*
+                    GameEvent::Unknown { event_time, .. } => *event_time,
+                }
+            }
+        }
+
+        /// [GameEvent::Unknown] gets serialized as the raw event, so it can be deserialized again
+        impl Serialize for GameEvent {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                match self {
+                    $(GameEvent::$name(e) => KnownGameEventRef::$name(e).serialize(serializer),)*
+                    GameEvent::Unknown { raw, .. } => raw.serialize(serializer),
+                }
+            }
         }
+    };
+}
+
+game_events! {
+    Ace,
+    AtakhanKill,
+    BaronKill,
+    ChampionKill,
+    DragonKill,
+    FeatUpdate,
+    FirstBlood,
+    FirstBrick,
+    GameEnd,
+    GameStart,
+    HeraldKill,
+    HordeKill,
+    InhibKilled,
+    InhibRespawned,
+    InhibRespawningSoon,
+    MinionsSpawning,
+    Multikill,
+    RoundEnd,
+    RoundStart,
+    TurretKilled,
+}
+
+impl<'de> Deserialize<'de> for GameEvent {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = serde_json::Value::deserialize(deserializer)?;
+        let name = raw
+            .get("EventName")
+            .and_then(|name| name.as_str())
+            .ok_or_else(|| de::Error::missing_field("EventName"))?
+            .to_string();
+
+        let error = match serde_json::from_value::<KnownGameEvent>(raw.clone()) {
+            Ok(known_event) => return Ok(known_event.into()),
+            // events with an unknown name are expected to fail
+            Err(_) if !KNOWN_EVENT_NAMES.contains(&name.as_str()) => None,
+            Err(e) if cfg!(feature = "strict") => return Err(de::Error::custom(e)),
+            Err(e) => Some(e.to_string()),
+        };
+
+        Ok(GameEvent::Unknown {
+            name,
+            event_id: raw
+                .get("EventID")
+                .and_then(|id| id.as_u64())
+                .unwrap_or_default() as EventId,
+            event_time: raw
+                .get("EventTime")
+                .and_then(|time| time.as_f64())
+                .unwrap_or_default(),
+            raw,
+            error,
+        })
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct Ace {
     pub acer: SummonerName,
@@ -465,8 +662,25 @@ pub struct Ace {
     pub event_time: Time,
 }
 
+/// Atakhan, the epic monster that spawns at 20 minutes
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "PascalCase")]
+pub struct AtakhanKill {
+    pub assisters: Vec<SummonerName>,
+    #[serde(rename = "EventID")]
+    pub event_id: EventId,
+    pub event_time: Time,
+    pub killer_name: Killer,
+    #[serde(
+        serialize_with = "serialize_bool",
+        deserialize_with = "deserialize_bool"
+    )]
+    pub stolen: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct BaronKill {
     pub assisters: Vec<SummonerName>,
@@ -474,12 +688,15 @@ pub struct BaronKill {
     pub event_id: EventId,
     pub event_time: Time,
     pub killer_name: Killer,
-    #[serde(deserialize_with = "deserialize_bool")]
+    #[serde(
+        serialize_with = "serialize_bool",
+        deserialize_with = "deserialize_bool"
+    )]
     pub stolen: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct ChampionKill {
     pub assisters: Vec<SummonerName>,
@@ -491,7 +708,7 @@ pub struct ChampionKill {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct DragonKill {
     pub assisters: Vec<String>,
@@ -500,12 +717,51 @@ pub struct DragonKill {
     pub event_id: EventId,
     pub event_time: Time,
     pub killer_name: Killer,
-    #[serde(deserialize_with = "deserialize_bool")]
+    #[serde(
+        serialize_with = "serialize_bool",
+        deserialize_with = "deserialize_bool"
+    )]
     pub stolen: bool,
 }
 
+/// The duration of the buff the killing team gets from the Elder Dragon
+pub const ELDER_BUFF_DURATION: Time = 150.0;
+
+impl DragonKill {
+    /// Killing the Elder Dragon grants the buff to the team of the killer for [ELDER_BUFF_DURATION] seconds
+    pub fn grants_elder_buff(&self) -> bool {
+        matches!(self.dragon_type, DragonType::Elder)
+    }
+
+    /// The game time at which the Elder Dragon buff of the event expires
+    pub fn elder_buff_expiry(&self) -> Option<Time> {
+        self.grants_elder_buff()
+            .then_some(self.event_time + ELDER_BUFF_DURATION)
+    }
+}
+
+pub type FeatType = i32;
+pub type FeatValue = i32;
+
+/// Progress of the Feats of Strength, the first team to complete two of them gets upgraded boots
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "PascalCase")]
+pub struct FeatUpdate {
+    #[serde(rename = "EventID")]
+    pub event_id: EventId,
+    pub event_time: Time,
+    /// The team that progressed the feat
+    #[serde(deserialize_with = "deserialize_team_id")]
+    pub feat_team: TeamId,
+    /// The feat that progressed: first blood, first turret or epic monsters
+    pub feat_type: FeatType,
+    /// The progress of the feat
+    pub feat_value: FeatValue,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct FirstBlood {
     #[serde(rename = "EventID")]
@@ -515,7 +771,7 @@ pub struct FirstBlood {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct FirstBrick {
     #[serde(rename = "EventID")]
@@ -525,7 +781,7 @@ pub struct FirstBrick {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct GameEnd {
     #[serde(rename = "EventID")]
@@ -535,7 +791,7 @@ pub struct GameEnd {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct GameStart {
     #[serde(rename = "EventID")]
@@ -544,7 +800,7 @@ pub struct GameStart {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct HeraldKill {
     pub assisters: Vec<String>,
@@ -552,12 +808,32 @@ pub struct HeraldKill {
     pub event_id: EventId,
     pub event_time: Time,
     pub killer_name: Killer,
-    #[serde(deserialize_with = "deserialize_bool")]
+    #[serde(
+        serialize_with = "serialize_bool",
+        deserialize_with = "deserialize_bool"
+    )]
+    pub stolen: bool,
+}
+
+/// A voidgrub of the horde that spawns in the top side pit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
+#[serde(rename_all = "PascalCase")]
+pub struct HordeKill {
+    pub assisters: Vec<SummonerName>,
+    #[serde(rename = "EventID")]
+    pub event_id: EventId,
+    pub event_time: Time,
+    pub killer_name: Killer,
+    #[serde(
+        serialize_with = "serialize_bool",
+        deserialize_with = "deserialize_bool"
+    )]
     pub stolen: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct InhibKilled {
     pub assisters: Vec<String>,
@@ -569,7 +845,7 @@ pub struct InhibKilled {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct InhibRespawned {
     #[serde(rename = "EventID")]
@@ -579,7 +855,7 @@ pub struct InhibRespawned {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct InhibRespawningSoon {
     #[serde(rename = "EventID")]
@@ -589,7 +865,7 @@ pub struct InhibRespawningSoon {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct MinionsSpawning {
     #[serde(rename = "EventID")]
@@ -600,7 +876,7 @@ pub struct MinionsSpawning {
 pub type KillStreak = i32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct Multikill {
     #[serde(rename = "EventID")]
@@ -610,8 +886,36 @@ pub struct Multikill {
     pub killer_name: SummonerName,
 }
 
+pub type RoundNumber = i32;
+
+/// A combat round of an Arena game ended \
+/// Not confirmed against the live API yet, the shape is modelled after a synthetic fixture.
+/// A missing `Round` defaults to 0 and unknown fields are ignored even with the `strict` feature
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[serde(rename_all = "PascalCase")]
+pub struct RoundEnd {
+    #[serde(rename = "EventID")]
+    pub event_id: EventId,
+    pub event_time: Time,
+    #[serde(default)]
+    pub round: RoundNumber,
+}
+
+/// A combat round of an Arena game started \
+/// Not confirmed against the live API yet, the shape is modelled after a synthetic fixture.
+/// A missing `Round` defaults to 0 and unknown fields are ignored even with the `strict` feature
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct RoundStart {
+    #[serde(rename = "EventID")]
+    pub event_id: EventId,
+    pub event_time: Time,
+    #[serde(default)]
+    pub round: RoundNumber,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub struct TurretKilled {
     pub assisters: Vec<String>,
@@ -622,8 +926,8 @@ pub struct TurretKilled {
     pub turret_killed: Turret,
 }
 
-#[derive(Debug, Display, Clone, Serialize, DeserializeFromStr)]
-#[serde(deny_unknown_fields)]
+/// Serialized by its [Display](std::fmt::Display), which parses back to the same type
+#[derive(Debug, Display, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
 pub enum DragonType {
     Infernal,
     Ocean,
@@ -632,97 +936,142 @@ pub enum DragonType {
     Hextech,
     Chemtech,
     Elder,
+    /// A dragon type that is not known to the library (yet)
+    Unknown(String),
 }
 
 impl FromStr for DragonType {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let dragon_type = match s {
-            "Fire" | "SRU_Dragon_Fire" => DragonType::Infernal,
-            "Water" | "SRU_Dragon_Water" => DragonType::Ocean,
-            "Earth" | "SRU_Dragon_Earth" => DragonType::Mountain,
-            "Air" | "SRU_Dragon_Air" => DragonType::Cloud,
-            "Hextech" | "SRU_Dragon_Hextech" => DragonType::Hextech,
-            "Chemtech" | "SRU_Dragon_Chemtech" => DragonType::Chemtech,
-            "Elder" | "SRU_Dragon_Elder" => DragonType::Elder,
-            _ => return Err(s.to_string()),
+        // the API uses the old names, e.g. `Fire` or `SRU_Dragon_Fire`
+        let dragon_type = match s.trim_start_matches("SRU_Dragon_") {
+            "Fire" | "Infernal" => DragonType::Infernal,
+            "Water" | "Ocean" => DragonType::Ocean,
+            "Earth" | "Mountain" => DragonType::Mountain,
+            "Air" | "Cloud" => DragonType::Cloud,
+            "Hextech" => DragonType::Hextech,
+            "Chemtech" => DragonType::Chemtech,
+            "Elder" => DragonType::Elder,
+            dragon_type => DragonType::Unknown(dragon_type.to_string()),
         };
         Ok(dragon_type)
     }
 }
 
+impl DragonType {
+    /// The name the API uses for the dragon type, e.g. `Fire` for [DragonType::Infernal]
+    pub fn api_name(&self) -> &str {
+        match self {
+            DragonType::Infernal => "Fire",
+            DragonType::Ocean => "Water",
+            DragonType::Mountain => "Earth",
+            DragonType::Cloud => "Air",
+            DragonType::Hextech => "Hextech",
+            DragonType::Chemtech => "Chemtech",
+            DragonType::Elder => "Elder",
+            DragonType::Unknown(name) => name,
+        }
+    }
+}
+
 #[derive(Debug, Display, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub enum GameResult {
     Win,
     Lose,
 }
 
-#[derive(Debug, Display, Clone, Serialize, DeserializeFromStr)]
-#[serde(deny_unknown_fields)]
+/// Displayed and serialized like the API names the killer (e.g. `SRU_Baron`, `SRU_Dragon_Fire` or the summoner name),
+/// so it parses back to the same killer \
+/// The numbered suffixes of the API names (e.g. `SRU_Baron12.1.1`) are not kept
+#[derive(Debug, Display, Clone, SerializeDisplay, DeserializeFromStr)]
 pub enum Killer {
     Minion,
+    #[display(fmt = "SRU_Dragon_{}", "_0.api_name()")]
     Dragon(DragonType),
+    #[display(fmt = "SRU_Gromp")]
     Gromp,
+    #[display(fmt = "SRU_Blue")]
     Blue,
+    #[display(fmt = "SRU_Murkwolf")]
     Murkwolf,
+    #[display(fmt = "SRU_Razorbeak")]
     Razorbeak,
+    #[display(fmt = "SRU_Red")]
     Red,
+    #[display(fmt = "SRU_Krug")]
     Krug,
+    #[display(fmt = "SRU_RiftHerald")]
     RiftHerald,
+    #[display(fmt = "SRU_Baron")]
     Baron,
+    /// Voidgrubs
+    #[display(fmt = "SRU_Horde")]
+    Horde,
+    #[display(fmt = "SRU_Atakhan")]
+    Atakhan,
     Turret(Turret),
     Summoner(SummonerName),
 }
 
+impl Killer {
+    /// The team of a summoner killer - None for monsters, minions and turrets
+    pub fn team(&self, players: &[Player]) -> Option<TeamId> {
+        let Killer::Summoner(name) = self else {
+            return None;
+        };
+        players
+            .iter()
+            .find(|p| &p.summoner_name == name)
+            .map(|p| p.team.clone())
+    }
+}
+
 impl FromStr for Killer {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         // SRU_Dragon_{type}
-        if let Ok(dragon) = DragonType::from_str(s) {
-            return Ok(Killer::Dragon(dragon));
+        if s.starts_with("SRU_Dragon") {
+            return DragonType::from_str(s).map(Killer::Dragon);
         }
         // Turret_{}_{}_{}_{}
-        if let Ok(turret) = Turret::from_str(s) {
-            return Ok(Killer::Turret(turret));
+        if s.starts_with("Turret") || s == "Obelisk" {
+            return Turret::from_str(s).map(Killer::Turret);
         }
         // Minion_{}
         if s.starts_with("Minion") {
             return Ok(Killer::Minion);
         }
-        // SRU_RiftHerald{}
-        if s.contains("RiftHerald") {
-            return Ok(Killer::RiftHerald);
-        }
-        // SRU_Baron{}
-        if s.contains("Baron") {
-            return Ok(Killer::RiftHerald);
-        }
-        // SRU_Gromp{}
-        if s.contains("Gromp") {
-            return Ok(Killer::Gromp);
-        }
-        // SRU_Blue{}
-        if s.contains("Blue") {
-            return Ok(Killer::Blue);
-        }
-        // SRU_Murkwolf{}
-        if s.contains("Murkwolf") {
-            return Ok(Killer::Murkwolf);
-        }
-        // SRU_Razorbeak{}
-        if s.contains("Razorbeak") {
-            return Ok(Killer::Razorbeak);
-        }
-        // SRU_Red{}
-        if s.contains("Red") {
-            return Ok(Killer::Red);
-        }
-        // SRU_Krug{}
-        if s.contains("Krug") {
-            return Ok(Killer::Krug);
+        // all the monsters are prefixed with SRU_ (or Sru_), everything else could be a summoner name
+        if s.to_uppercase().starts_with("SRU_") {
+            let monster = match s {
+                // SRU_RiftHerald{}
+                _ if s.contains("RiftHerald") => Some(Killer::RiftHerald),
+                // SRU_Baron{}
+                _ if s.contains("Baron") => Some(Killer::Baron),
+                // SRU_Horde{}
+                _ if s.contains("Horde") => Some(Killer::Horde),
+                // SRU_Atakhan{}
+                _ if s.contains("Atakhan") => Some(Killer::Atakhan),
+                // SRU_Gromp{}
+                _ if s.contains("Gromp") => Some(Killer::Gromp),
+                // SRU_Blue{}
+                _ if s.contains("Blue") => Some(Killer::Blue),
+                // SRU_Murkwolf{}
+                _ if s.contains("Murkwolf") => Some(Killer::Murkwolf),
+                // SRU_Razorbeak{}
+                _ if s.contains("Razorbeak") => Some(Killer::Razorbeak),
+                // SRU_Red{}
+                _ if s.contains("Red") => Some(Killer::Red),
+                // SRU_Krug{}
+                _ if s.contains("Krug") => Some(Killer::Krug),
+                _ => None,
+            };
+            if let Some(monster) = monster {
+                return Ok(monster);
+            }
         }
 
         // if none of the others its 99% the summoner name
@@ -730,179 +1079,236 @@ impl FromStr for Killer {
     }
 }
 
-/// Team 1 refers to Blue / Left Side \
-/// Team 2 refers to Red / Right Side
-#[derive(Debug, Display, Clone, Serialize, DeserializeFromStr)]
-#[serde(deny_unknown_fields)]
-pub enum Turret {
-    // --- TEAM1 ---
-    /// *Summoner's Rift*: Team 1 Upper Nexus Turret
-    Team1C01A,
-    /// *Summoner's Rift*: Team 1 Lower Nexus Turret
-    Team1C02A,
-    /// *Summoner's Rift*: Team 1 Mid Inhib Turret
-    Team1C03A,
-    /// *Summoner's Rift*: Team 1 Mid Inner Turret
-    Team1C04A,
-    /// *Summoner's Rift*: Team 1 Mid Outer Turret
-    Team1C05A,
-    /// *Summoner's Rift*: Team 1 Top Inihb Turret
-    Team1C06A,
-    /// *Summoner's Rift*: Team 1 Bot Inhib Turret \
-    /// *ARAM*: Team 1 Inner Turret
-    Team1C07A,
-    /// *ARAM*: Team 1 Outer Turret
-    Team1C08A,
-    /// *ARAM*: Team 1 Bot Nexus Turret
-    Team1C09A,
-    /// *ARAM*: Team 1 Top Nexus Turret
-    Team1C10A,
-    /// *Summoner's Rift*: Team 1 Top Inner Turret
-    Team1L02A,
-    /// *Summoner's Rift*: Team 1 Top Outer Turret
-    Team1L03A,
-    /// *Summoner's Rift*: Team 1 Bot Inner Turret
-    Team1R02A,
-    /// *Summoner's Rift*: Team 1 Bot Outer Turret
-    Team1R03A,
-    /// *Summoner's Rift*: Team 1 Fountain
-    /// *ARAM*: Team 1 Fountain
-    Team1Fountain,
-    // --- TEAM2 ---
-    /// *Summoner's Rift*: Team 2 Lower Nexus Turret
-    Team2C01A,
-    /// *Summoner's Rift*: Team 2 Upper Nexus Turret
-    Team2C02A,
-    /// *Summoner's Rift*: Team 2 Mid Inhib Turret
-    Team2C03A,
-    /// *Summoner's Rift*: Team 2 Mid Inner Turret
-    Team2C04A,
-    /// *Summoner's Rift*: Team 2 Mid Outer Turret
-    Team2C05A,
-    /// *Summoner's Rift*: Team 2 Top Inihb Turret
-    /// *ARAM*: Team 2 Outer Turret
-    Team2L01A,
-    /// *Summoner's Rift*: Team 2 Top Inner Turret
-    /// *ARAM*: Team 2 Inner Turret
-    Team2L02A,
-    /// *Summoner's Rift*: Team 2 Top Outer Turret
-    /// *ARAM*: Team 2 Lower Nexus Turret
-    Team2L03A,
-    /// *ARAM*: Team 2 Upper Nexus Turret
-    Team2L04A,
-    /// *Summoner's Rift*: Team 2 Bot Inhib Turret
-    Team2R01A,
-    /// *Summoner's Rift*: Team 2 Bot Inner Turret
-    Team2R02A,
-    /// *Summoner's Rift*: Team 2 Bot Outer Turret
-    Team2R03A,
-    /// *Summoner's Rift*: Team 2 Fountain
-    /// *ARAM*: Team 2 Fountain
-    Team2Fountain,
-    // --- OTHER ---
+/// A turret parsed from its raw name, e.g. `Turret_T1_C_05_A` \
+/// T1 refers to ORDER (Blue / Left Side) and T2 refers to CHAOS (Red / Right Side) \
+/// The meaning of the lane and number depends on the map, see [Turret::location]
+#[derive(Debug, Display, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
+#[display(fmt = "{raw}")]
+pub struct Turret {
+    /// The name as it was returned by the API
+    pub raw: String,
+    pub team: Option<TeamId>,
+    pub kind: TurretKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TurretKind {
+    /// A lane or nexus turret, e.g. `Turret_T1_C_05_A` is in lane group [LaneCode::C] with number 5
+    Turret { lane_code: LaneCode, number: u8 },
+    /// The turret guarding the fountain, e.g. `Turret_OrderTurretShrine_A`
+    Fountain,
     /// Azir Turret
     Obelisk,
     /// The riot documentation specifying the turrets is incomplete => add Unknown to catch deserialization errors
-    #[serde(other)]
     Unknown,
 }
 
+/// The lane letter of a raw turret or inhibitor name \
+/// It doesn't always match the lane of the structure, e.g. `Turret_T1_C_06_A` is the top inhibitor turret on Summoner's Rift
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum LaneCode {
+    L,
+    C,
+    R,
+}
+
+impl FromStr for LaneCode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "L" => Ok(LaneCode::L),
+            "C" => Ok(LaneCode::C),
+            "R" => Ok(LaneCode::R),
+            _ => Err(format!("unknown lane code: {s}")),
+        }
+    }
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Lane {
+    Top,
+    Mid,
+    Bot,
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TurretTier {
+    Outer,
+    Inner,
+    Inhibitor,
+    Nexus,
+    Fountain,
+}
+
+/// Where a [Turret] is located on a map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TurretLocation {
+    /// None for turrets that don't belong to a lane, e.g. nexus turrets
+    pub lane: Option<Lane>,
+    pub tier: TurretTier,
+}
+
+impl Turret {
+    /// Locates the turret on the given map \
+    /// Returns None for maps whose turret layout isn't known and for Azir turrets
+    pub fn location(&self, map: &MapName) -> Option<TurretLocation> {
+        use LaneCode::*;
+        use TurretTier::*;
+
+        let (lane_code, number) = match self.kind {
+            TurretKind::Turret { lane_code, number } => (lane_code, number),
+            TurretKind::Fountain => {
+                return Some(TurretLocation {
+                    lane: None,
+                    tier: Fountain,
+                })
+            }
+            TurretKind::Obelisk | TurretKind::Unknown => return None,
+        };
+        let order = self.team.as_ref() == Some(&TeamId::Order);
+
+        let (lane, tier) = match map {
+            MapName::Map1 | MapName::Map2 | MapName::Map11 => match (order, lane_code, number) {
+                (_, C, 1 | 2) => (None, Nexus),
+                (_, C, 3) => (Some(Lane::Mid), Inhibitor),
+                (_, C, 4) => (Some(Lane::Mid), Inner),
+                (_, C, 5) => (Some(Lane::Mid), Outer),
+                (true, C, 6) | (false, L, 1) => (Some(Lane::Top), Inhibitor),
+                (true, C, 7) | (false, R, 1) => (Some(Lane::Bot), Inhibitor),
+                (_, L, 2) => (Some(Lane::Top), Inner),
+                (_, L, 3) => (Some(Lane::Top), Outer),
+                (_, R, 2) => (Some(Lane::Bot), Inner),
+                (_, R, 3) => (Some(Lane::Bot), Outer),
+                _ => return None,
+            },
+            // ARAM maps only have a single lane
+            MapName::Map12 | MapName::Map14 => match (order, lane_code, number) {
+                (true, C, 7) | (false, L, 2) => (Some(Lane::Mid), Inner),
+                (true, C, 8) | (false, L, 1) => (Some(Lane::Mid), Outer),
+                (true, C, 9 | 10) | (false, L, 3 | 4) => (None, Nexus),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(TurretLocation { lane, tier })
+    }
+}
+
 impl FromStr for Turret {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let turret = match s {
-            // --- TEAM1 ---
-            "Turret_T1_C_01_A" => Turret::Team1C01A,
-            "Turret_T1_C_02_A" => Turret::Team1C02A,
-            "Turret_T1_C_03_A" => Turret::Team1C03A,
-            "Turret_T1_C_04_A" => Turret::Team1C04A,
-            "Turret_T1_C_05_A" => Turret::Team1C05A,
-            "Turret_T1_C_06_A" => Turret::Team1C06A,
-            "Turret_T1_C_07_A" => Turret::Team1C07A,
-            "Turret_T1_C_08_A" => Turret::Team1C08A,
-            "Turret_T1_C_09_A" => Turret::Team1C09A,
-            "Turret_T1_C_010_A" => Turret::Team1C10A,
-            "Turret_T1_L_02_A" => Turret::Team1L02A,
-            "Turret_T1_L_03_A" => Turret::Team1L03A,
-            "Turret_T1_R_02_A" => Turret::Team1R02A,
-            "Turret_T1_R_03_A" => Turret::Team1R03A,
-            "Turret_OrderTurretShrine_A" => Turret::Team1Fountain,
-            // --- TEAM2 ---
-            "Turret_T2_C_01_A" => Turret::Team2C01A,
-            "Turret_T2_C_02_A" => Turret::Team2C02A,
-            "Turret_T2_C_03_A" => Turret::Team2C03A,
-            "Turret_T2_C_04_A" => Turret::Team2C04A,
-            "Turret_T2_C_05_A" => Turret::Team2C05A,
-            "Turret_T2_L_01_A" => Turret::Team2L01A,
-            "Turret_T2_L_02_A" => Turret::Team2L02A,
-            "Turret_T2_L_03_A" => Turret::Team2L03A,
-            "Turret_T2_L_04_A" => Turret::Team2L04A,
-            "Turret_T2_R_01_A" => Turret::Team2R01A,
-            "Turret_T2_R_02_A" => Turret::Team2R02A,
-            "Turret_T2_R_03_A" => Turret::Team2R03A,
-            "Turret_ChaosTurretShrine_A" => Turret::Team2Fountain,
-            // --- OTHER ---
-            "Obelisk" => Turret::Obelisk,
-            _ => Turret::Unknown,
+        let (team, kind) = match s {
+            "Obelisk" => (None, TurretKind::Obelisk),
+            "Turret_OrderTurretShrine_A" => (Some(TeamId::Order), TurretKind::Fountain),
+            "Turret_ChaosTurretShrine_A" => (Some(TeamId::Chaos), TurretKind::Fountain),
+            _ => {
+                // Turret_<team>_<lane>_<number>_<variant>
+                let parts: Vec<&str> = s.split('_').collect();
+                match parts.as_slice() {
+                    ["Turret", team, lane_code, number, _] => {
+                        let kind = match (lane_code.parse(), number.parse()) {
+                            (Ok(lane_code), Ok(number)) => TurretKind::Turret { lane_code, number },
+                            _ => TurretKind::Unknown,
+                        };
+                        (parse_structure_team(team), kind)
+                    }
+                    _ => (None, TurretKind::Unknown),
+                }
+            }
         };
-        Ok(turret)
+        Ok(Turret {
+            raw: s.to_string(),
+            team,
+            kind,
+        })
     }
 }
 
-/// Team 1 refers to Blue / Left Side \
-/// Team 2 refers to Red / Right Side
-#[derive(Debug, Display, Clone, Serialize, DeserializeFromStr)]
-#[serde(deny_unknown_fields)]
-pub enum Inhibitor {
-    /// *Summoner's Rift*: Team 1 Top Inhibitor
-    Team1L1,
-    /// *Summoner's Rift*: Team 1 Mid Inhibitor \
-    /// **ARAM**: Team 1 Inhibitor
-    Team1C1,
-    /// *Summoner's Rift*: Team 1 Bot Inhibitor
-    Team1R1,
-    /// *Summoner's Rift*: Team 2 Top Inhibitor
-    Team2L1,
-    /// *Summoner's Rift*: Team 1 Mid Inhibitor \
-    /// **ARAM**: Team 2 Inhibitor
-    Team2C1,
-    /// *Summoner's Rift*: Team 2 Bot Inhibitor
-    Team2R1,
-    #[serde(other)]
-    Unknown,
+/// An inhibitor parsed from its raw name, e.g. `Barracks_T1_L1` \
+/// T1 refers to ORDER (Blue / Left Side) and T2 refers to CHAOS (Red / Right Side) \
+/// *Summoner's Rift*: L, C and R are the top, mid and bot inhibitor \
+/// *ARAM*: C is the only inhibitor of a team
+#[derive(Debug, Display, Clone, PartialEq, Eq, Hash, SerializeDisplay, DeserializeFromStr)]
+#[display(fmt = "{raw}")]
+pub struct Inhibitor {
+    /// The name as it was returned by the API
+    pub raw: String,
+    pub team: Option<TeamId>,
+    /// None if the name couldn't be parsed
+    pub lane: Option<Lane>,
 }
 
 impl FromStr for Inhibitor {
     type Err = String;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let inhib = match s {
-            "Barracks_T1_L1" => Inhibitor::Team1L1,
-            "Barracks_T1_C1" => Inhibitor::Team1C1,
-            "Barracks_T1_R1" => Inhibitor::Team1R1,
-            "Barracks_T2_L1" => Inhibitor::Team2L1,
-            "Barracks_T2_C1" => Inhibitor::Team2C1,
-            "Barracks_T2_R1" => Inhibitor::Team2R1,
-            _ => Inhibitor::Unknown,
+        // Barracks_<team>_<lane><number>
+        let parts: Vec<&str> = s.split('_').collect();
+        let (team, lane) = match parts.as_slice() {
+            ["Barracks", team, lane] => {
+                let lane = match lane.get(..1) {
+                    Some("L") => Some(Lane::Top),
+                    Some("C") => Some(Lane::Mid),
+                    Some("R") => Some(Lane::Bot),
+                    _ => None,
+                };
+                (parse_structure_team(team), lane)
+            }
+            _ => (None, None),
         };
-        Ok(inhib)
+        Ok(Inhibitor {
+            raw: s.to_string(),
+            team,
+            lane,
+        })
+    }
+}
+
+/// Structures refer to their team as T1 for ORDER and T2 for CHAOS
+fn parse_structure_team(team: &str) -> Option<TeamId> {
+    match team {
+        "T1" => Some(TeamId::Order),
+        "T2" => Some(TeamId::Chaos),
+        _ => None,
+    }
+}
+
+/// Teams are sometimes referred to by their id: 100 for ORDER, 200 for CHAOS and 300 for NEUTRAL
+fn deserialize_team_id<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TeamId, D::Error> {
+    let value = serde_json::Value::deserialize(deserializer)?;
+    match value {
+        serde_json::Value::Number(n) => Ok(match n.as_i64() {
+            Some(100) => TeamId::Order,
+            Some(200) => TeamId::Chaos,
+            Some(300) => TeamId::Neutral,
+            _ => TeamId::Other(n.to_string()),
+        }),
+        value => TeamId::deserialize(value).map_err(de::Error::custom),
     }
 }
 
+/// The API sends bools as `"True"` and `"False"`
+fn serialize_bool<S: Serializer>(value: &bool, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(if *value { "True" } else { "False" })
+}
+
 fn deserialize_bool<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
-    let string = Deserialize::deserialize(deserializer)?;
-    Ok(match string {
-        "True" => true,
-        "False" => false,
-        _ => panic!("unknown value for bool"),
-    })
+    let string: String = Deserialize::deserialize(deserializer)?;
+    match string.as_str() {
+        "True" => Ok(true),
+        "False" => Ok(false),
+        _ => Err(de::Error::custom(format!(
+            "unknown value for bool: {string}"
+        ))),
+    }
 }
 
 pub type MapNumber = i32;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "camelCase")]
 pub struct GameStats {
     pub game_mode: GameMode,
@@ -913,7 +1319,7 @@ pub struct GameStats {
 }
 
 #[derive(Debug, Display, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "UPPERCASE")]
 pub enum GameMode {
     /// Classic Summoner's Rift and Twisted Treeline games
@@ -959,13 +1365,19 @@ pub enum GameMode {
     Odyssey,
     /// Ultimate Spellbook
     UltBook,
+    /// Arena games
+    #[serde(rename = "CHERRY")]
+    Arena,
+    /// Swarm games
+    #[serde(rename = "STRAWBERRY")]
+    Swarm,
     /// The riot documentation specifying the game modes is incomplete => add Unknown to catch deserialization errors
     #[serde(other)]
     Unknown,
 }
 
 #[derive(Debug, Display, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 #[serde(rename_all = "PascalCase")]
 pub enum MapName {
     /// Summoner's Rift - Original Summer variant
@@ -998,13 +1410,17 @@ pub enum MapName {
     Map21,
     /// Convergence - Teamfight Tactics map
     Map22,
+    /// Rings of Wrath - Arena map
+    Map30,
+    /// Swarm map
+    Map33,
     /// The riot documentation specifying the maps is incomplete => add Unknown to catch deserialization errors
     #[serde(other)]
     Unknown,
 }
 
 #[derive(Debug, Display, Clone, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[cfg_attr(feature = "strict", serde(deny_unknown_fields))]
 pub enum MapTerrain {
     Default,
     Infernal,
@@ -0,0 +1,55 @@
+use std::str::FromStr;
+
+use base64::{engine::general_purpose, Engine};
+
+/// The content of a `lockfile` written by the League client or the Riot Client while it is running: \
+/// `name:pid:port:password:protocol`, e.g. `LeagueClient:1234:52437:Xyz-pass:https`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lockfile {
+    /// `LeagueClient` or `Riot Client`
+    pub name: String,
+    pub pid: u32,
+    pub port: u16,
+    pub password: String,
+    pub protocol: String,
+}
+
+impl Lockfile {
+    /// The password encoded the same way as the tokens of [LCUClientInfo](crate::rest::LCUClientInfo): base64 of `riot:{password}`
+    pub fn token(&self) -> String {
+        general_purpose::STANDARD.encode(format!("riot:{}", self.password))
+    }
+}
+
+impl FromStr for Lockfile {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.trim().split(':').collect();
+        let [name, pid, port, password, protocol] = parts[..] else {
+            return Err(format!(
+                "expected 5 fields separated by ':', got {}",
+                parts.len()
+            ));
+        };
+
+        Ok(Self {
+            name: name.to_string(),
+            pid: pid.parse().map_err(|_| format!("invalid pid: {pid}"))?,
+            port: port.parse().map_err(|_| format!("invalid port: {port}"))?,
+            password: password.to_string(),
+            protocol: protocol.to_string(),
+        })
+    }
+}
+
+/// The items of a [LockfileWatcher](crate::utils::lockfile::LockfileWatcher)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockfileEvent {
+    /// A lockfile was created, or it already existed when the watcher was created
+    Created(Lockfile),
+    /// The lockfile was rewritten with other content, e.g. by a restarted client
+    Changed(Lockfile),
+    /// The lockfile was removed, the client was closed
+    Removed,
+}
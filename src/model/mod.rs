@@ -1,4 +1,28 @@
+/// Type definitions for the cooldowns estimated from the LoL-Ingame API
+pub mod cooldowns;
+/// Type definitions for the events derived from the LoL-Ingame API
+pub mod diff;
+/// Type definitions for the tables exported from the LoL-Ingame API
+pub mod export;
+/// Type definitions for the highlight clips planned from the LoL-Ingame API
+pub mod highlights;
 /// Type defintions for the LoL-Ingame API
 pub mod ingame;
+/// Type definitions for the lockfiles of the League client and the Riot Client
+pub mod lockfile;
+/// Type definitions for the messages of the overlay server
+pub mod overlay;
+/// Type definitions for the League client process watcher
+pub mod process;
+/// Type definitions for the Replay API of the game
+pub mod replay;
+/// Type definitions for the events of a League session
+pub mod session;
+/// Type definitions for the aggregates computed from the LoL-Ingame API
+pub mod stats;
+/// Type definitions for the timelines recorded from the LoL-Ingame API
+pub mod timeline;
+/// Type definitions for the objective timers derived from the LoL-Ingame API
+pub mod timers;
 /// Type definitions for the League-Client(LCU) Websocket API
 pub mod ws;
@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::{
+    ingame::{
+        Assists, ChampionName, CreepScore, Deaths, GameEvent, GameMode, ItemId, Kills, Level,
+        MapName, SummonerName, TeamId, Time,
+    },
+    stats::TeamStats,
+    ws::JsonApiEvent,
+};
+
+/// A message published by the [OverlayServer](crate::overlay::OverlayServer) \
+/// Serialized as JSON with a `type` tag, e.g. `{"type":"gameEvent","event":{...}}`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum OverlayMessage {
+    /// The current state, sent on connect and whenever it changed
+    State { state: OverlayState },
+    /// An event of the ingame API, sent once per event
+    GameEvent { event: GameEvent },
+    /// A forwarded event of the LCU websocket API
+    LcuEvent { event: JsonApiEvent },
+    /// A message published by the application embedding the server
+    Custom { data: serde_json::Value },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayState {
+    /// The gameflow phase of the LCU (e.g. `"ChampSelect"`), None if no LCU events are forwarded
+    pub gameflow_phase: Option<String>,
+    /// None while no game is running
+    pub game: Option<OverlayGame>,
+}
+
+/// The normalized state of a running game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayGame {
+    pub game_mode: GameMode,
+    pub map_name: MapName,
+    pub game_time: Time,
+    pub players: Vec<OverlayPlayer>,
+    /// See [game_summary](crate::stats::game_summary)
+    pub teams: Vec<TeamStats>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OverlayPlayer {
+    pub summoner_name: SummonerName,
+    pub champion_name: ChampionName,
+    pub team: TeamId,
+    pub level: Level,
+    pub kills: Kills,
+    pub deaths: Deaths,
+    pub assists: Assists,
+    pub creep_score: CreepScore,
+    pub items: Vec<ItemId>,
+    pub is_dead: bool,
+    pub respawn_timer: Time,
+}
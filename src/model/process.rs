@@ -0,0 +1,12 @@
+use crate::rest::LCUClientInfo;
+
+/// The items of a [ProcessWatcher](crate::utils::process_info::ProcessWatcher)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LcuProcessEvent {
+    /// The League client was started, or it was already running when the watcher was created
+    Started(LCUClientInfo),
+    /// The League client was restarted and got a new port and token, clients have to re-authenticate
+    Restarted(LCUClientInfo),
+    /// The League client was closed
+    Exited,
+}
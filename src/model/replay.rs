@@ -0,0 +1,316 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::ingame::Time;
+
+/// The game the Replay API is attached to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayGame {
+    #[serde(rename = "processID")]
+    pub process_id: u32,
+}
+
+/// The playback state of the replay or spectated game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Playback {
+    /// The length of the replay in seconds
+    pub length: Time,
+    pub paused: bool,
+    pub seeking: bool,
+    pub speed: f32,
+    /// The current game time in seconds
+    pub time: Time,
+}
+
+/// A change of the [Playback], fields that are None are left unchanged
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaybackUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paused: Option<bool>,
+    /// Skip the seeking animation when jumping forward
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seeking_fast_forward: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+    /// The game time in seconds to seek to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time: Option<Time>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Vector3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+/// A color with channels from 0.0 to 1.0
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CameraMode {
+    /// The default top down camera
+    Top,
+    /// First person
+    Fps,
+    /// Third person
+    Tps,
+    /// Follows the selected unit
+    Focus,
+    /// Follows the camera keyframes of the [Sequence]
+    Path,
+}
+
+/// The camera and the render flags of the game \
+/// Every field is optional, the same type is used to read the current state and to change parts of it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Render {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_attached: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_look_speed: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_mode: Option<CameraMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_move_speed: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_position: Option<Vector3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_rotation: Option<Vector3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub character_light: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_end: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_intensity: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_start: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_of_field_enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub far_clip: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_of_view: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub floating_text: Option<bool>,
+    /// Hide everything that is not visible to the [Render::selection_name] team when enabled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fog_of_war: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_champions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_minions: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_pets: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_structures: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health_bar_wards: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height_fog_color: Option<Color>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height_fog_enabled: Option<bool>,
+    /// Shows or hides the whole HUD, takes precedence over the other `interface_*` flags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_all: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_announce: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_chat: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_frames: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_kill_callouts: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_minimap: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_neutral_timers: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_quests: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_replay: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_score: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_scoreboard: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_target: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interface_timeline: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nav_grid_offset: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub near_clip: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_hover: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub outline_select: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub particles: Option<bool>,
+    /// The name of the selected unit, e.g. a champion name the [CameraMode::Focus] follows
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_offset: Option<Vector3>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skybox_offset: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skybox_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skybox_radius: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skybox_rotation: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sun_direction: Option<Vector3>,
+}
+
+/// The state of the video recording \
+/// Every field is optional, the same type is used to read the current state and to change parts of it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Recording {
+    /// `webm` for a video or `png` for single frames
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_time: Option<Time>,
+    /// The game time to stop the recording at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_time: Option<Time>,
+    /// Render every frame even if the game can't keep up with real time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enforce_frame_rate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub frames_per_second: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lossless: Option<bool>,
+    /// The file or directory the recording is written to on the game machine
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recording: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replay_speed: Option<f32>,
+    /// The game time to start the recording at
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_time: Option<Time>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+}
+
+/// How the value is interpolated between a [KeyFrame] and the next one
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum EasingType {
+    #[default]
+    Linear,
+    Snap,
+    SmoothStep,
+    SmootherStep,
+    QuadraticEaseIn,
+    QuadraticEaseOut,
+    QuadraticEaseInOut,
+    CubicEaseIn,
+    CubicEaseOut,
+    CubicEaseInOut,
+    QuarticEaseIn,
+    QuarticEaseOut,
+    QuarticEaseInOut,
+    QuinticEaseIn,
+    QuinticEaseOut,
+    QuinticEaseInOut,
+    SineEaseIn,
+    SineEaseOut,
+    SineEaseInOut,
+    CircularEaseIn,
+    CircularEaseOut,
+    CircularEaseInOut,
+    ExponentialEaseIn,
+    ExponentialEaseOut,
+    ExponentialEaseInOut,
+    ElasticEaseIn,
+    ElasticEaseOut,
+    ElasticEaseInOut,
+    BackEaseIn,
+    BackEaseOut,
+    BackEaseInOut,
+    BounceEaseIn,
+    BounceEaseOut,
+    BounceEaseInOut,
+}
+
+/// The value of a [Sequence] track at a game time
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyFrame<T> {
+    pub blend: EasingType,
+    pub time: Time,
+    pub value: T,
+}
+
+impl<T> KeyFrame<T> {
+    pub fn new(time: Time, value: T, blend: EasingType) -> Self {
+        Self { blend, time, value }
+    }
+}
+
+/// Keyframe tracks the game plays back, e.g. a camera path for a highlight \
+/// Tracks that are None are not animated
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Sequence {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_position: Option<Vec<KeyFrame<Vector3>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub camera_rotation: Option<Vec<KeyFrame<Vector3>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_color: Option<Vec<KeyFrame<Color>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_enabled: Option<Vec<KeyFrame<bool>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_end: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_intensity: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub depth_fog_start: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub far_clip: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field_of_view: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height_fog_color: Option<Vec<KeyFrame<Color>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub height_fog_enabled: Option<Vec<KeyFrame<bool>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub near_clip: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub playback_speed: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_name: Option<Vec<KeyFrame<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub selection_offset: Option<Vec<KeyFrame<Vector3>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skybox_offset: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skybox_radius: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub skybox_rotation: Option<Vec<KeyFrame<f32>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sun_direction: Option<Vec<KeyFrame<Vector3>>>,
+}
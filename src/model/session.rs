@@ -0,0 +1,18 @@
+use crate::model::{ingame::IngameStreamEvent, ws::LcuEvent};
+
+/// The items of a [LeagueSession](crate::session::LeagueSession)
+#[derive(Debug, Clone)]
+pub enum SessionEvent {
+    /// The websocket connection to the League client is established, also after the client was restarted
+    ClientConnected,
+    /// The websocket connection to the League client was lost, e.g. because the client was closed \
+    /// The session keeps looking for the client
+    ClientDisconnected,
+    /// The gameflow phase changed, e.g. `"Lobby"`, `"ChampSelect"`, `"InProgress"` or `"EndOfGame"`
+    PhaseChanged(String),
+    /// An event of a subscription added with [LeagueSessionBuilder::subscribe](crate::session::LeagueSessionBuilder::subscribe)
+    Lcu(LcuEvent),
+    /// An event of the ingame API, polled while a game is in progress \
+    /// Every game is framed by a [IngameStreamEvent::GameStarted] and a [IngameStreamEvent::GameEnded]
+    Ingame(IngameStreamEvent),
+}
@@ -0,0 +1,59 @@
+use serde::{Deserialize, Serialize};
+
+use crate::model::ingame::{
+    Assists, CreepScore, Deaths, DragonType, Kills, Price, SummonerName, TeamId, WardScore,
+};
+
+/// Aggregates of one [AllGameData](crate::model::ingame::AllGameData) snapshot, see [game_summary](crate::stats::game_summary)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub teams: Vec<TeamStats>,
+    pub players: Vec<PlayerStats>,
+}
+
+impl GameSummary {
+    pub fn team(&self, team: &TeamId) -> Option<&TeamStats> {
+        self.teams.iter().find(|t| &t.team == team)
+    }
+
+    pub fn player(&self, summoner_name: &str) -> Option<&PlayerStats> {
+        self.players
+            .iter()
+            .find(|p| p.summoner_name == summoner_name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamStats {
+    pub team: TeamId,
+    pub kills: Kills,
+    pub deaths: Deaths,
+    pub assists: Assists,
+    pub creep_score: CreepScore,
+    pub ward_score: WardScore,
+    /// The sum of the recipe gold of all players of the team, see [recipe_gold](crate::stats::recipe_gold)
+    pub recipe_gold: Price,
+    /// The elemental dragons and elder dragons slain by the team
+    pub dragons: Vec<DragonType>,
+    pub barons: i32,
+    pub heralds: i32,
+    pub voidgrubs: i32,
+    /// The turrets of the other team destroyed by the team
+    pub turrets: i32,
+    /// The inhibitors of the other team destroyed by the team
+    pub inhibitors: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerStats {
+    pub summoner_name: SummonerName,
+    pub team: TeamId,
+    /// (kills + assists) / deaths, deaths count as at least 1
+    pub kda: f32,
+    /// The share of the team kills the player took part in - None while the team has no kills
+    pub kill_participation: Option<f32>,
+    pub cs_per_minute: f32,
+    /// The sum of the recipe costs of the inventory, see [recipe_gold](crate::stats::recipe_gold) \
+    /// The ingame API doesn't expose the gold of other players
+    pub recipe_gold: Price,
+}
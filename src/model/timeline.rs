@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::model::ingame::Time;
+
+/// A single line of a timeline written by a [TimelineWriter](crate::timeline::TimelineWriter)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum TimelineEntry {
+    /// A full `/liveclientdata/allgamedata` response without the events
+    Snapshot { game_time: Time, data: Value },
+    /// The changes since the previous snapshot or delta
+    Delta {
+        game_time: Time,
+        patch: Vec<PatchOperation>,
+    },
+    /// An ingame event as it was returned by the API
+    Event { event: Value },
+}
+
+/// A change of a JSON document, the path is a JSON pointer like `/allPlayers/3/scores/kills`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+pub enum PatchOperation {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
@@ -0,0 +1,124 @@
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+use crate::model::ingame::{DragonType, Inhibitor, TeamId, Time};
+
+/// The epic monsters tracked by the [ObjectiveTracker](crate::timers::ObjectiveTracker)
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Objective {
+    /// An elemental dragon
+    Dragon,
+    ElderDragon,
+    Baron,
+    Herald,
+}
+
+/// The buffs granted by killing an epic monster
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ObjectiveBuff {
+    /// Hand of Baron
+    Baron,
+    /// Aspect of the Dragon
+    Elder,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpawnTimer {
+    pub objective: Objective,
+    /// the objective is alive once the game time passed this
+    pub spawns_at: Time,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuffTimer {
+    pub buff: ObjectiveBuff,
+    pub team: TeamId,
+    pub expires_at: Time,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InhibitorTimer {
+    pub inhibitor: Inhibitor,
+    pub respawns_at: Time,
+}
+
+/// The elemental dragons a team has slain
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DragonSoulProgress {
+    pub dragons: Vec<DragonType>,
+    /// Set once the team has slain enough dragons to claim the soul
+    pub soul: Option<DragonType>,
+}
+
+/// A snapshot of all timers of the [ObjectiveTracker](crate::timers::ObjectiveTracker)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ObjectiveTimers {
+    /// the game time of the last update
+    pub game_time: Time,
+    /// The next dragon, [Objective::ElderDragon] once a team claimed the soul \
+    /// None on maps without dragons
+    pub dragon: Option<SpawnTimer>,
+    /// None on maps without baron
+    pub baron: Option<SpawnTimer>,
+    /// None on maps without herald and after it has been slain
+    pub herald: Option<SpawnTimer>,
+    /// Buffs are lost when a player dies, only the latest possible expiry is tracked
+    pub buffs: Vec<BuffTimer>,
+    pub inhibitors: Vec<InhibitorTimer>,
+    pub order_dragons: DragonSoulProgress,
+    pub chaos_dragons: DragonSoulProgress,
+}
+
+impl ObjectiveTimers {
+    /// The timers of the objectives that have not spawned yet
+    pub fn upcoming(&self) -> impl Iterator<Item = &SpawnTimer> {
+        [&self.dragon, &self.baron, &self.herald]
+            .into_iter()
+            .flatten()
+            .filter(|timer| timer.spawns_at > self.game_time)
+    }
+
+    /// Whether the objective has spawned and not been slain yet
+    pub fn is_alive(&self, objective: Objective) -> bool {
+        [&self.dragon, &self.baron, &self.herald]
+            .into_iter()
+            .flatten()
+            .any(|timer| timer.objective == objective && timer.spawns_at <= self.game_time)
+    }
+
+    pub fn dragon_soul_progress(&self, team: &TeamId) -> Option<&DragonSoulProgress> {
+        match team {
+            TeamId::Order => Some(&self.order_dragons),
+            TeamId::Chaos => Some(&self.chaos_dragons),
+            _ => None,
+        }
+    }
+}
+
+/// The items of a [ObjectiveTimerStream](crate::timers::ObjectiveTimerStream)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum TimerNotification {
+    /// The objective spawns within the configured lead time
+    SpawningSoon {
+        objective: Objective,
+        spawns_at: Time,
+    },
+    Spawned {
+        objective: Objective,
+    },
+    InhibitorRespawningSoon {
+        inhibitor: Inhibitor,
+        respawns_at: Time,
+    },
+    InhibitorRespawned {
+        inhibitor: Inhibitor,
+    },
+    BuffExpired {
+        buff: ObjectiveBuff,
+        team: TeamId,
+    },
+    DragonSoulClaimed {
+        team: TeamId,
+        soul: DragonType,
+    },
+}
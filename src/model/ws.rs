@@ -1,14 +1,70 @@
-use std::{fmt, fmt::Display};
+use std::{
+    fmt,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    str::FromStr,
+    sync::Arc,
+};
 
-use serde::{de, Deserialize, Deserializer};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
 
 /// The Websocket connection returns LcuEvents
 #[derive(Debug, Clone)]
 pub struct LcuEvent {
     pub subscription_type: LcuSubscriptionType,
+    pub payload: LcuEventPayload,
+    raw_message: Option<Arc<str>>,
+}
+
+impl LcuEvent {
+    /// Parses a websocket message (e.g. `[8, "OnLog", "..."]`) and keeps its original text
+    pub fn from_message(message: &str) -> Result<Self, serde_json::Error> {
+        let mut event: Self = serde_json::from_str(message)?;
+        event.raw_message = Some(message.into());
+        Ok(event)
+    }
+
+    /// The websocket message the event was parsed from \
+    /// Returns None if the event was not created with [LcuEvent::from_message]
+    pub fn raw_message(&self) -> Option<&str> {
+        self.raw_message.as_deref()
+    }
+}
+
+/// The data of an [LcuEvent] \
+/// The shape of the data depends on the topic the event was published on
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+pub enum LcuEventPayload {
+    /// Published on `OnJsonApiEvent` topics
+    JsonApi(JsonApiEvent),
+    /// Published on `OnLcdsEvent` topics
+    Lcds(LcdsEvent),
+    /// All other topics (`OnLog`, `OnServiceProxy*`, ...) and payloads that don't match the expected shape of their topic
+    Other(Value),
+}
+
+/// A change to a resource of the LCU REST API
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JsonApiEvent {
     pub data: Value,
+    /// `Create`, `Update` or `Delete`
     pub event_type: String,
+    pub uri: String,
+}
+
+/// A message that was forwarded from the League Client Data Service (LCDS)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LcdsEvent {
+    #[serde(default)]
+    pub body: Value,
+    #[serde(default)]
+    pub subtype: Option<String>,
+    #[serde(default)]
+    pub type_name: Option<String>,
 }
 
 /// LcuEvents first get deserialized to deserialize::DeEvent and then to LcuEvent
@@ -23,21 +79,28 @@ impl<'de> Deserialize<'de> for LcuEvent {
         pub struct DeEvent {
             _opcode: i64,
             pub(crate) subscription_type: LcuSubscriptionType,
-            pub(crate) data: Data,
-        }
-        /// Intermediate data-structure for deserializing LcuEvents
-        #[derive(Deserialize, Debug)]
-        #[serde(rename_all = "camelCase")]
-        pub struct Data {
             pub(crate) data: Value,
-            pub(crate) event_type: String,
         }
 
         let de_event = DeEvent::deserialize(deserializer)?;
+        let payload = match de_event.subscription_type {
+            LcuSubscriptionType::AllJsonApiEvents | LcuSubscriptionType::JsonApiEvent(_) => {
+                serde_json::from_value(de_event.data.clone())
+                    .map(LcuEventPayload::JsonApi)
+                    .unwrap_or(LcuEventPayload::Other(de_event.data))
+            }
+            LcuSubscriptionType::AllLcdsEvents | LcuSubscriptionType::LcdsEvent(_) => {
+                serde_json::from_value(de_event.data.clone())
+                    .map(LcuEventPayload::Lcds)
+                    .unwrap_or(LcuEventPayload::Other(de_event.data))
+            }
+            _ => LcuEventPayload::Other(de_event.data),
+        };
+
         Ok(Self {
             subscription_type: de_event.subscription_type,
-            data: de_event.data.data,
-            event_type: de_event.data.event_type,
+            payload,
+            raw_message: None,
         })
     }
 }
@@ -50,17 +113,33 @@ impl<'de> Deserialize<'de> for LcuEvent {
 /// e.g.: [LcuSubscriptionType::JsonApiEvent]\("/lol-gameflow/v1/gameflow-phase".to_string())
 #[derive(Debug, Clone)]
 pub enum LcuSubscriptionType {
+    /// `OnJsonApiEvent`
     AllJsonApiEvents,
+    /// `OnLcdsEvent`
     AllLcdsEvents,
+    /// `OnJsonApiEvent_{endpoint}`
     JsonApiEvent(String),
+    /// `OnLcdsEvent_{destination}`
     LcdsEvent(String),
+    /// `OnLog`
+    Log,
+    /// `OnRegionLocaleChanged`
+    RegionLocaleChanged,
+    /// `OnServiceProxyAsyncEvent`
+    ServiceProxyAsyncEvent,
+    /// `OnServiceProxyMethodEvent`
+    ServiceProxyMethodEvent,
+    /// `OnServiceProxyUuidEvent`
+    ServiceProxyUuidEvent,
+    /// Any other topic, used as is
+    Other(String),
 }
 
 impl Display for LcuSubscriptionType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             LcuSubscriptionType::AllJsonApiEvents => f.write_str("OnJsonApiEvent"),
-            LcuSubscriptionType::AllLcdsEvents => f.write_str("OnJsonApiEvent"),
+            LcuSubscriptionType::AllLcdsEvents => f.write_str("OnLcdsEvent"),
             LcuSubscriptionType::JsonApiEvent(s) => f.write_str(&format!(
                 "OnJsonApiEvent_{}",
                 s.trim_start_matches('/').replace('/', "_")
@@ -69,10 +148,67 @@ impl Display for LcuSubscriptionType {
                 "OnLcdsEvent_{}",
                 s.trim_start_matches('/').replace('/', "_")
             )),
+            LcuSubscriptionType::Log => f.write_str("OnLog"),
+            LcuSubscriptionType::RegionLocaleChanged => f.write_str("OnRegionLocaleChanged"),
+            LcuSubscriptionType::ServiceProxyAsyncEvent => f.write_str("OnServiceProxyAsyncEvent"),
+            LcuSubscriptionType::ServiceProxyMethodEvent => {
+                f.write_str("OnServiceProxyMethodEvent")
+            }
+            LcuSubscriptionType::ServiceProxyUuidEvent => f.write_str("OnServiceProxyUuidEvent"),
+            LcuSubscriptionType::Other(s) => f.write_str(s),
         }
     }
 }
 
+/// Two subscription types are equal if they refer to the same topic \
+/// e.g. [LcuSubscriptionType::JsonApiEvent]\("/lol-gameflow/v1/session") and [LcuSubscriptionType::JsonApiEvent]\("lol-gameflow_v1_session")
+impl PartialEq for LcuSubscriptionType {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_string() == other.to_string()
+    }
+}
+
+impl Eq for LcuSubscriptionType {}
+
+impl Hash for LcuSubscriptionType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_string().hash(state)
+    }
+}
+
+/// Parses a topic name as it is sent by the LCU \
+/// Endpoints of [LcuSubscriptionType::JsonApiEvent] and [LcuSubscriptionType::LcdsEvent] keep the
+/// underscore separated form of the topic (e.g. `lol-gameflow_v1_gameflow-phase`)
+impl FromStr for LcuSubscriptionType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err("Empty SubscriptionType".to_string());
+        }
+
+        let subscription_type = match s {
+            "OnJsonApiEvent" => LcuSubscriptionType::AllJsonApiEvents,
+            "OnLcdsEvent" => LcuSubscriptionType::AllLcdsEvents,
+            "OnLog" => LcuSubscriptionType::Log,
+            "OnRegionLocaleChanged" => LcuSubscriptionType::RegionLocaleChanged,
+            "OnServiceProxyAsyncEvent" => LcuSubscriptionType::ServiceProxyAsyncEvent,
+            "OnServiceProxyMethodEvent" => LcuSubscriptionType::ServiceProxyMethodEvent,
+            "OnServiceProxyUuidEvent" => LcuSubscriptionType::ServiceProxyUuidEvent,
+            _ => {
+                if let Some(endpoint) = s.strip_prefix("OnJsonApiEvent_") {
+                    LcuSubscriptionType::JsonApiEvent(endpoint.to_string())
+                } else if let Some(destination) = s.strip_prefix("OnLcdsEvent_") {
+                    LcuSubscriptionType::LcdsEvent(destination.to_string())
+                } else {
+                    LcuSubscriptionType::Other(s.to_string())
+                }
+            }
+        };
+        Ok(subscription_type)
+    }
+}
+
 /// Custom deserializer to differentiate between the different subscription types
 impl<'de> Deserialize<'de> for LcuSubscriptionType {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
@@ -80,24 +216,15 @@ impl<'de> Deserialize<'de> for LcuSubscriptionType {
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
+        LcuSubscriptionType::from_str(&s).map_err(de::Error::custom)
+    }
+}
 
-        if s.starts_with("OnJsonApiEvent") {
-            if s.len() > 14 {
-                Ok(LcuSubscriptionType::JsonApiEvent(s[15..].to_string()))
-            } else {
-                Ok(LcuSubscriptionType::AllJsonApiEvents)
-            }
-        } else if s.starts_with("OnLcdsApiEvent") {
-            if s.len() > 14 {
-                Ok(LcuSubscriptionType::LcdsEvent(s[15..].to_string()))
-            } else {
-                Ok(LcuSubscriptionType::AllLcdsEvents)
-            }
-        } else {
-            Err(de::Error::custom(format!(
-                "Unknown SubscriptionType: {}",
-                s
-            )))
-        }
+impl Serialize for LcuSubscriptionType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
     }
 }
@@ -0,0 +1,449 @@
+use std::{
+    io,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use futures_util::{SinkExt, Stream, StreamExt};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    sync::broadcast::{self, error::RecvError},
+    task::{JoinHandle, JoinSet},
+};
+use tokio_tungstenite::{
+    tungstenite::{handshake::derive_accept_key, protocol::Role, Message},
+    WebSocketStream,
+};
+
+use crate::{
+    cache::{CHAMP_SELECT_SESSION, GAMEFLOW_PHASE, LOBBY},
+    ingame::{EventCursor, IngameClient, DEFAULT_MAX_RETRIES, DEFAULT_POLLING_RATE_MILLIS},
+    model::{
+        ingame::AllGameData,
+        overlay::*,
+        ws::{JsonApiEvent, LcuEvent, LcuEventPayload},
+    },
+    stats::game_summary,
+    utils::http::{read_request, write_response, Request},
+};
+
+const DEFAULT_THROTTLE_MILLIS: u64 = 250;
+const SSE_KEEPALIVE_SECS: u64 = 15;
+/// Messages a slow client can fall behind before it skips messages
+const CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone)]
+pub struct OverlayOptions {
+    /// The values of the `Origin` header that are allowed to connect and get CORS headers \
+    /// An origin without a port (e.g. `http://localhost`) allows every port, `"*"` allows every origin \
+    /// Requests without an `Origin` header (e.g. curl) are always allowed \
+    /// The default is `["http://localhost", "http://127.0.0.1"]`, so other websites opened in a browser can't read the game state
+    pub allowed_origins: Vec<String>,
+    /// The minimum time between two state messages and between two forwarded events of the same LCU URI \
+    /// Updates in between are coalesced, only the latest one is sent. Game events are not throttled \
+    /// The default is 250ms
+    pub throttle: Duration,
+    /// How often the [IngameClient] is polled \
+    /// The default is 500ms
+    pub polling_rate: Duration,
+    /// How many polls of the [IngameClient] in a row can fail before the game is considered ended \
+    /// The default is 5
+    pub max_retries: u32,
+    /// The URIs of the LCU events that are forwarded, every JSON API event if empty \
+    /// The default is [GAMEFLOW_PHASE], [LOBBY] and [CHAMP_SELECT_SESSION]
+    pub lcu_uris: Vec<String>,
+}
+
+impl Default for OverlayOptions {
+    fn default() -> Self {
+        Self {
+            allowed_origins: vec![
+                "http://localhost".to_string(),
+                "http://127.0.0.1".to_string(),
+            ],
+            throttle: Duration::from_millis(DEFAULT_THROTTLE_MILLIS),
+            polling_rate: Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS),
+            max_retries: DEFAULT_MAX_RETRIES,
+            lcu_uris: vec![
+                GAMEFLOW_PHASE.to_string(),
+                LOBBY.to_string(),
+                CHAMP_SELECT_SESSION.to_string(),
+            ],
+        }
+    }
+}
+
+/// A local server that re-publishes the game state, game events and LCU events for browser-source overlays \
+/// Every [OverlayMessage] is sent as JSON on
+/// - `GET /ws`: a WebSocket, incoming messages are ignored
+/// - `GET /events`: Server-Sent Events
+///
+/// Both start with the current state. `GET /state` returns the current [OverlayState]
+pub struct OverlayServer {
+    addr: SocketAddr,
+    shared: Arc<Shared>,
+    task_handles: Vec<JoinHandle<()>>,
+}
+
+struct Shared {
+    options: OverlayOptions,
+    state: Mutex<OverlayState>,
+    pending: Mutex<Pending>,
+    messages_tx: broadcast::Sender<Arc<str>>,
+}
+
+/// The throttled updates that are sent on the next flush
+#[derive(Default)]
+struct Pending {
+    state: bool,
+    lcu_events: Vec<JsonApiEvent>,
+}
+
+impl OverlayServer {
+    /// Binds the server to the address, e.g. `"127.0.0.1:7878"` \
+    /// Use port 0 to let the OS choose a free port, see [OverlayServer::addr]
+    pub async fn start<A: ToSocketAddrs>(addr: A, options: OverlayOptions) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        let addr = listener.local_addr()?;
+        let (messages_tx, _) = broadcast::channel(CHANNEL_CAPACITY);
+        let shared = Arc::new(Shared {
+            options,
+            state: Mutex::default(),
+            pending: Mutex::default(),
+            messages_tx,
+        });
+
+        let server_shared = shared.clone();
+        let server_task_handle = tokio::spawn(async move {
+            // owned by the task, so open WebSockets and event streams are closed when the server is dropped
+            let mut connections = JoinSet::new();
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => {
+                            connections.spawn(handle_connection(stream, server_shared.clone()));
+                        }
+                        Err(_) => break,
+                    },
+                    Some(_) = connections.join_next(), if !connections.is_empty() => {}
+                }
+            }
+        });
+
+        let flush_shared = shared.clone();
+        let flush_task_handle = tokio::spawn(async move {
+            let mut timer =
+                tokio::time::interval(flush_shared.options.throttle.max(Duration::from_millis(1)));
+            loop {
+                timer.tick().await;
+                flush_shared.flush();
+            }
+        });
+
+        Ok(Self {
+            addr,
+            shared,
+            task_handles: vec![server_task_handle, flush_task_handle],
+        })
+    }
+
+    /// The address the server is bound to
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The current state as it is sent to newly connected clients
+    pub fn state(&self) -> OverlayState {
+        self.shared.state.lock().unwrap().clone()
+    }
+
+    /// Sends the message to every connected client right away
+    pub fn publish(&self, message: &OverlayMessage) {
+        self.shared.send(message);
+    }
+
+    /// Polls the [IngameClient] to publish the state of the running game and every [GameEvent](crate::model::ingame::GameEvent) once \
+    /// The past events of a game that was already running when it is first seen are published as well \
+    /// The game is removed from the state after [OverlayOptions::max_retries] failed polls in a row,
+    /// if the same game answers again only its new events are published
+    pub fn forward_ingame(&mut self, ingame_client: IngameClient) {
+        let shared = self.shared.clone();
+        let polling_rate = shared.options.polling_rate.max(Duration::from_millis(1));
+        let max_retries = shared.options.max_retries;
+        self.task_handles.push(tokio::spawn(async move {
+            let mut timer = tokio::time::interval(polling_rate);
+            let mut cursor = EventCursor::default();
+            let mut failed_requests = 0;
+            loop {
+                timer.tick().await;
+                match ingame_client.all_game_data(None).await {
+                    Ok(data) => {
+                        failed_requests = 0;
+                        // every poll returns all events, a new game starts from the first one again
+                        cursor.sync(&data.events);
+                        for event in &data.events {
+                            if event.get_event_id() >= cursor.next_event_id() {
+                                shared.send(&OverlayMessage::GameEvent {
+                                    event: event.clone(),
+                                });
+                            }
+                        }
+                        cursor.advance(&data.events);
+                        let game = overlay_game(&data);
+                        shared.update_state(|state| state.game = Some(game));
+                    }
+                    Err(_) => {
+                        failed_requests += 1;
+                        if failed_requests > max_retries
+                            && shared.state.lock().unwrap().game.is_some()
+                        {
+                            shared.update_state(|state| state.game = None);
+                        }
+                    }
+                }
+            }
+        }));
+    }
+
+    /// Publishes the JSON API events of the stream whose URI is in [OverlayOptions::lcu_uris] and tracks the gameflow phase \
+    /// The events have to be subscribed to beforehand, e.g. with [LcuSubscriptionType::AllJsonApiEvents](crate::model::ws::LcuSubscriptionType::AllJsonApiEvents)
+    pub fn forward_lcu_events<S>(&mut self, mut events: S)
+    where
+        S: Stream<Item = LcuEvent> + Send + Unpin + 'static,
+    {
+        let shared = self.shared.clone();
+        self.task_handles.push(tokio::spawn(async move {
+            while let Some(event) = events.next().await {
+                let LcuEventPayload::JsonApi(event) = event.payload else {
+                    continue;
+                };
+                if event.uri == GAMEFLOW_PHASE {
+                    let phase = event.data.as_str().map(str::to_string);
+                    shared.update_state(|state| state.gameflow_phase = phase);
+                }
+
+                let uris = &shared.options.lcu_uris;
+                if !uris.is_empty() && !uris.contains(&event.uri) {
+                    continue;
+                }
+                let mut pending = shared.pending.lock().unwrap();
+                match pending.lcu_events.iter().position(|e| e.uri == event.uri) {
+                    Some(index) => pending.lcu_events[index] = event,
+                    None => pending.lcu_events.push(event),
+                }
+            }
+        }));
+    }
+}
+
+impl Drop for OverlayServer {
+    fn drop(&mut self) {
+        for handle in &self.task_handles {
+            handle.abort();
+        }
+    }
+}
+
+impl Shared {
+    fn send(&self, message: &OverlayMessage) {
+        if let Ok(json) = serde_json::to_string(message) {
+            // fails if no client is connected
+            let _ = self.messages_tx.send(json.into());
+        }
+    }
+
+    fn update_state<F: FnOnce(&mut OverlayState)>(&self, update: F) {
+        update(&mut self.state.lock().unwrap());
+        self.pending.lock().unwrap().state = true;
+    }
+
+    fn flush(&self) {
+        let (state, lcu_events) = {
+            let mut pending = self.pending.lock().unwrap();
+            (
+                std::mem::take(&mut pending.state),
+                std::mem::take(&mut pending.lcu_events),
+            )
+        };
+        for event in lcu_events {
+            self.send(&OverlayMessage::LcuEvent { event });
+        }
+        if state {
+            self.send(&self.state_message());
+        }
+    }
+
+    fn state_message(&self) -> OverlayMessage {
+        OverlayMessage::State {
+            state: self.state.lock().unwrap().clone(),
+        }
+    }
+
+    fn is_allowed(&self, origin: &str) -> bool {
+        self.options.allowed_origins.iter().any(|allowed| {
+            allowed == "*"
+                || allowed == origin
+                // an allowed origin without a port allows every port
+                || origin
+                    .strip_prefix(allowed.as_str())
+                    .and_then(|rest| rest.strip_prefix(':'))
+                    .is_some_and(|port| !port.is_empty() && port.bytes().all(|b| b.is_ascii_digit()))
+        })
+    }
+}
+
+fn overlay_game(data: &AllGameData) -> OverlayGame {
+    OverlayGame {
+        game_mode: data.game_data.game_mode.clone(),
+        map_name: data.game_data.map_name.clone(),
+        game_time: data.game_data.game_time,
+        players: data
+            .all_players
+            .iter()
+            .map(|player| OverlayPlayer {
+                summoner_name: player.summoner_name.clone(),
+                champion_name: player.champion_name.clone(),
+                team: player.team.clone(),
+                level: player.level,
+                kills: player.scores.kills,
+                deaths: player.scores.deaths,
+                assists: player.scores.assists,
+                creep_score: player.scores.creep_score,
+                items: player.items.iter().map(|item| item.item_id).collect(),
+                is_dead: player.is_dead,
+                respawn_timer: player.respawn_timer,
+            })
+            .collect(),
+        teams: game_summary(data).teams,
+    }
+}
+
+async fn handle_connection(mut stream: TcpStream, shared: Arc<Shared>) {
+    let Some(request) = read_request(&mut stream).await else {
+        return;
+    };
+
+    let mut headers = Vec::new();
+    if let Some(origin) = request.header("Origin") {
+        if !shared.is_allowed(origin) {
+            write_response(&mut stream, 403, &[], &[]).await;
+            return;
+        }
+        if shared
+            .options
+            .allowed_origins
+            .iter()
+            .any(|allowed| allowed == "*")
+        {
+            headers.push(("Access-Control-Allow-Origin", "*".to_string()));
+        } else {
+            headers.push(("Access-Control-Allow-Origin", origin.to_string()));
+            headers.push(("Vary", "Origin".to_string()));
+        }
+    }
+
+    match (request.method.as_str(), request.path()) {
+        ("OPTIONS", _) => {
+            headers.push(("Access-Control-Allow-Methods", "GET, OPTIONS".to_string()));
+            headers.push(("Access-Control-Allow-Headers", "Content-Type".to_string()));
+            write_response(&mut stream, 204, &borrow(&headers), &[]).await;
+        }
+        ("GET", "/state") => {
+            let body = serde_json::to_vec(&*shared.state.lock().unwrap()).unwrap_or_default();
+            headers.push(("Content-Type", "application/json".to_string()));
+            write_response(&mut stream, 200, &borrow(&headers), &body).await;
+        }
+        ("GET", "/events") => serve_events(stream, shared, &borrow(&headers)).await,
+        ("GET", "/ws") => serve_websocket(stream, shared, &request).await,
+        (_, "/state" | "/events" | "/ws") => {
+            write_response(&mut stream, 405, &borrow(&headers), &[]).await
+        }
+        _ => write_response(&mut stream, 404, &borrow(&headers), &[]).await,
+    }
+}
+
+fn borrow<'a>(headers: &'a [(&'a str, String)]) -> Vec<(&'a str, &'a str)> {
+    headers
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect()
+}
+
+async fn serve_events(mut stream: TcpStream, shared: Arc<Shared>, headers: &[(&str, &str)]) {
+    // subscribe before the state is sent, so no update gets lost in between
+    let mut messages_rx = shared.messages_tx.subscribe();
+    let mut head =
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n"
+            .to_string();
+    for (name, value) in headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+    let state = serde_json::to_string(&shared.state_message()).unwrap_or_default();
+    head.push_str(&format!("data: {state}\n\n"));
+    if stream.write_all(head.as_bytes()).await.is_err() {
+        return;
+    }
+
+    // comments keep proxies from closing the connection and detect closed clients
+    let keepalive = Duration::from_secs(SSE_KEEPALIVE_SECS);
+    let mut keepalive_timer =
+        tokio::time::interval_at(tokio::time::Instant::now() + keepalive, keepalive);
+    loop {
+        let chunk = tokio::select! {
+            message = messages_rx.recv() => match message {
+                Ok(json) => format!("data: {json}\n\n"),
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            },
+            _ = keepalive_timer.tick() => ":\n\n".to_string(),
+        };
+        if stream.write_all(chunk.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+async fn serve_websocket(mut stream: TcpStream, shared: Arc<Shared>, request: &Request) {
+    let upgrade = request
+        .header("Upgrade")
+        .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"));
+    let Some(key) = request.header("Sec-WebSocket-Key").filter(|_| upgrade) else {
+        write_response(&mut stream, 400, &[], &[]).await;
+        return;
+    };
+    let head = format!(
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    );
+    if stream.write_all(head.as_bytes()).await.is_err() {
+        return;
+    }
+
+    let mut messages_rx = shared.messages_tx.subscribe();
+    let mut socket = WebSocketStream::from_raw_socket(stream, Role::Server, None).await;
+    let state = serde_json::to_string(&shared.state_message()).unwrap_or_default();
+    if socket.send(Message::text(state)).await.is_err() {
+        return;
+    }
+    loop {
+        tokio::select! {
+            message = messages_rx.recv() => match message {
+                Ok(json) => {
+                    if socket.send(Message::text(json.to_string())).await.is_err() {
+                        return;
+                    }
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return,
+            },
+            // pings are answered by tungstenite, other messages are ignored
+            incoming = socket.next() => match incoming {
+                Some(Ok(Message::Close(_)) | Err(_)) | None => return,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
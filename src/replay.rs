@@ -0,0 +1,176 @@
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    error::ReplayClientError,
+    ingame::IngameClient,
+    model::{ingame::Time, replay::*},
+};
+
+/// Render and recording requests take noticeably longer than the requests of the LoL-Ingame API
+const DEFAULT_TIMEOUT_MILLIS: u64 = 5000;
+
+/// A client for the Replay API of the game \
+/// The API is served next to the LoL-Ingame API while watching a replay or spectating,
+/// it has to be enabled with `EnableReplayApi=1` in the `game.cfg`
+pub struct ReplayClient {
+    client: reqwest::Client,
+    base_url: String,
+    timeout: Duration,
+}
+
+impl ReplayClient {
+    /// Create a new connection to the replay api of the game running on this machine
+    pub fn new() -> Result<Self, ReplayClientError> {
+        IngameClient::new()
+            .map(|client| Self::from_ingame_client(&client))
+            .map_err(|e| ReplayClientError::ConnectionError(e.to_string()))
+    }
+
+    /// Use the connection settings of the [IngameClient], e.g. one configured with an [IngameClientBuilder](crate::ingame::IngameClientBuilder) \
+    /// The timeout of the [IngameClient] is not used, see [ReplayClient::timeout]
+    pub fn from_ingame_client(ingame_client: &IngameClient) -> Self {
+        Self {
+            client: ingame_client.client.clone(),
+            base_url: ingame_client.base_url.clone(),
+            timeout: Duration::from_millis(DEFAULT_TIMEOUT_MILLIS),
+        }
+    }
+
+    /// Fail a request if it takes longer than the given [Duration] \
+    /// The default is 5 seconds
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Get the game the Replay API is attached to
+    pub async fn game(&self) -> Result<ReplayGame, ReplayClientError> {
+        self.get("/replay/game").await
+    }
+
+    /// Get the playback state
+    pub async fn playback(&self) -> Result<Playback, ReplayClientError> {
+        self.get("/replay/playback").await
+    }
+
+    /// Change the playback, returns the new playback state
+    pub async fn set_playback(
+        &self,
+        playback: &PlaybackUpdate,
+    ) -> Result<Playback, ReplayClientError> {
+        self.post("/replay/playback", playback).await
+    }
+
+    /// Jump to the game time in seconds
+    pub async fn seek(&self, time: Time) -> Result<Playback, ReplayClientError> {
+        self.set_playback(&PlaybackUpdate {
+            time: Some(time),
+            ..Default::default()
+        })
+        .await
+    }
+
+    pub async fn pause(&self) -> Result<Playback, ReplayClientError> {
+        self.set_playback(&PlaybackUpdate {
+            paused: Some(true),
+            ..Default::default()
+        })
+        .await
+    }
+
+    pub async fn play(&self) -> Result<Playback, ReplayClientError> {
+        self.set_playback(&PlaybackUpdate {
+            paused: Some(false),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Set the playback speed, 1.0 is real time
+    pub async fn set_speed(&self, speed: f32) -> Result<Playback, ReplayClientError> {
+        self.set_playback(&PlaybackUpdate {
+            speed: Some(speed),
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Get the camera and the render flags
+    pub async fn render(&self) -> Result<Render, ReplayClientError> {
+        self.get("/replay/render").await
+    }
+
+    /// Change the camera and the render flags, fields that are None are left unchanged \
+    /// Returns the new render state
+    pub async fn set_render(&self, render: &Render) -> Result<Render, ReplayClientError> {
+        self.post("/replay/render", render).await
+    }
+
+    /// Get the state of the video recording
+    pub async fn recording(&self) -> Result<Recording, ReplayClientError> {
+        self.get("/replay/recording").await
+    }
+
+    /// Start a video recording with the given settings, fields that are None use the settings of the game
+    pub async fn start_recording(
+        &self,
+        recording: Recording,
+    ) -> Result<Recording, ReplayClientError> {
+        let recording = Recording {
+            recording: Some(true),
+            ..recording
+        };
+        self.post("/replay/recording", &recording).await
+    }
+
+    pub async fn stop_recording(&self) -> Result<Recording, ReplayClientError> {
+        let recording = Recording {
+            recording: Some(false),
+            ..Default::default()
+        };
+        self.post("/replay/recording", &recording).await
+    }
+
+    /// Get the keyframe tracks that are played back
+    pub async fn sequence(&self) -> Result<Sequence, ReplayClientError> {
+        self.get("/replay/sequence").await
+    }
+
+    /// Replace the keyframe tracks that are played back, returns the new sequence
+    pub async fn set_sequence(&self, sequence: &Sequence) -> Result<Sequence, ReplayClientError> {
+        self.post("/replay/sequence", sequence).await
+    }
+
+    async fn get<T: DeserializeOwned>(&self, endpoint: &str) -> Result<T, ReplayClientError> {
+        self.client
+            .get(format!("{}{}", self.base_url, endpoint))
+            .timeout(self.timeout)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(ReplayClientError::from)?
+            .json()
+            .await
+            .map_err(ReplayClientError::from)
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        body: &B,
+    ) -> Result<T, ReplayClientError> {
+        self.client
+            .post(format!("{}{}", self.base_url, endpoint))
+            .json(body)
+            .timeout(self.timeout)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(ReplayClientError::from)?
+            .json()
+            .await
+            .map_err(ReplayClientError::from)
+    }
+}
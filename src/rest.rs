@@ -1,6 +1,6 @@
 use serde::{Serialize, Deserialize};
 
-use crate::utils::{process_info, request::build_reqwest_client};
+use crate::utils::request::build_reqwest_client;
 
 #[derive(Clone)]
 /// A client for the League-Client(LCU) REST API
@@ -10,7 +10,7 @@ pub struct RESTClient {
     pub lcu_client_info: LCUClientInfo
 }
 
-#[derive(Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct LCUClientInfo {
     pub port: u16,
     pub token: String,
@@ -0,0 +1,386 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_util::{Stream, StreamExt};
+use tokio::{
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        watch,
+    },
+    task::JoinHandle,
+};
+
+use crate::{
+    cache::GAMEFLOW_PHASE,
+    error::IngameClientError,
+    ingame::{ContinuousEventStream, IngameClient, DEFAULT_POLLING_RATE_MILLIS},
+    model::{
+        ingame::IngameStreamEvent,
+        session::SessionEvent,
+        ws::{LcuEventPayload, LcuSubscriptionType},
+    },
+    rest::{LCUClientInfo, RESTClient},
+    utils::process_info,
+    ws::{LcuWebsocketClient, LcuWebsocketClientBuilder, LcuWebsocketHandle},
+};
+
+const DEFAULT_DISCOVERY_INTERVAL_MILLIS: u64 = 2000;
+
+/// The gameflow phases during which the ingame API gets polled
+const INGAME_PHASES: &[&str] = &["InProgress", "Reconnect"];
+
+/// Combines the [RESTClient], [LcuWebsocketClient] and [IngameClient] into one stream of [SessionEvent]s \
+/// Looks for the League client until it is running, reconnects after it was restarted
+/// and polls the ingame API while the gameflow phase is `InProgress`
+pub struct LeagueSession {
+    state_rx: watch::Receiver<SessionState>,
+    session_task_handle: JoinHandle<()>,
+    events_rx: UnboundedReceiver<SessionEvent>,
+}
+
+#[derive(Clone, Default)]
+struct SessionState {
+    rest_client: Option<RESTClient>,
+    ws_handle: Option<LcuWebsocketHandle>,
+    phase: Option<String>,
+}
+
+impl LeagueSession {
+    /// Create a session with the default configuration, see [LeagueSessionBuilder]
+    pub fn new() -> Result<Self, IngameClientError> {
+        Self::builder().build()
+    }
+
+    /// Create a [LeagueSessionBuilder] to configure the session
+    pub fn builder() -> LeagueSessionBuilder {
+        LeagueSessionBuilder::default()
+    }
+
+    /// A REST client for the currently connected League client, None while it is not connected
+    pub fn rest_client(&self) -> Option<RESTClient> {
+        self.state_rx.borrow().rest_client.clone()
+    }
+
+    /// A handle of the websocket connection to the currently connected League client, None while it is not connected \
+    /// Events of subscriptions made with the handle are emitted as [SessionEvent::Lcu]
+    pub fn ws_handle(&self) -> Option<LcuWebsocketHandle> {
+        self.state_rx.borrow().ws_handle.clone()
+    }
+
+    /// The latest known gameflow phase, None while the client is not connected
+    pub fn phase(&self) -> Option<String> {
+        self.state_rx.borrow().phase.clone()
+    }
+}
+
+impl Stream for LeagueSession {
+    type Item = SessionEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for LeagueSession {
+    fn drop(&mut self) {
+        self.session_task_handle.abort()
+    }
+}
+
+/// A builder to configure a [LeagueSession] \
+/// By default the credentials of the running League client get used
+pub struct LeagueSessionBuilder {
+    client_info: Option<LCUClientInfo>,
+    websocket: LcuWebsocketClientBuilder,
+    ingame_client: Option<IngameClient>,
+    subscriptions: Vec<LcuSubscriptionType>,
+    discovery_interval: Duration,
+    polling_rate: Duration,
+}
+
+impl Default for LeagueSessionBuilder {
+    fn default() -> Self {
+        Self {
+            client_info: None,
+            websocket: LcuWebsocketClient::builder(),
+            ingame_client: None,
+            subscriptions: Vec::new(),
+            discovery_interval: Duration::from_millis(DEFAULT_DISCOVERY_INTERVAL_MILLIS),
+            polling_rate: Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS),
+        }
+    }
+}
+
+impl LeagueSessionBuilder {
+    /// Use the given credentials instead of reading them from the running League client
+    pub fn client_info(mut self, client_info: LCUClientInfo) -> Self {
+        self.client_info = Some(client_info);
+        self
+    }
+
+    /// The builder every websocket connection is made with, e.g. to set a connect timeout \
+    /// The discovered credentials are set with [LcuWebsocketClientBuilder::client_info],
+    /// an explicit port or token of the builder takes precedence
+    pub fn websocket(mut self, websocket: LcuWebsocketClientBuilder) -> Self {
+        self.websocket = websocket;
+        self
+    }
+
+    /// The client to poll the ingame API with \
+    /// The default is [IngameClient::new]
+    pub fn ingame_client(mut self, ingame_client: IngameClient) -> Self {
+        self.ingame_client = Some(ingame_client);
+        self
+    }
+
+    /// Subscribe to the websocket events, they are emitted as [SessionEvent::Lcu] \
+    /// The subscriptions are renewed on every connection, the gameflow phase is always subscribed
+    pub fn subscribe(mut self, subscription: LcuSubscriptionType) -> Self {
+        self.subscriptions.push(subscription);
+        self
+    }
+
+    /// How often to look for the League client while it is not connected \
+    /// The default is 2s
+    pub fn discovery_interval(mut self, discovery_interval: Duration) -> Self {
+        self.discovery_interval = discovery_interval;
+        self
+    }
+
+    /// How often the ingame API is polled while a game is in progress \
+    /// The default is 500ms
+    pub fn polling_rate(mut self, polling_rate: Duration) -> Self {
+        self.polling_rate = polling_rate;
+        self
+    }
+
+    /// Starts the session in the background
+    pub fn build(self) -> Result<LeagueSession, IngameClientError> {
+        let ingame_client = match self.ingame_client {
+            Some(ingame_client) => ingame_client,
+            None => IngameClient::new()?,
+        };
+        let (events_tx, events_rx) = unbounded_channel();
+        let (state_tx, state_rx) = watch::channel(SessionState::default());
+
+        let session_task_handle = tokio::spawn(session_task(
+            SessionConfig {
+                client_info: self.client_info,
+                websocket: self.websocket,
+                ingame_client,
+                subscriptions: self.subscriptions,
+                discovery_interval: self.discovery_interval,
+                polling_rate: self.polling_rate,
+            },
+            events_tx,
+            state_tx,
+        ));
+
+        Ok(LeagueSession {
+            state_rx,
+            session_task_handle,
+            events_rx,
+        })
+    }
+}
+
+struct SessionConfig {
+    client_info: Option<LCUClientInfo>,
+    websocket: LcuWebsocketClientBuilder,
+    ingame_client: IngameClient,
+    subscriptions: Vec<LcuSubscriptionType>,
+    discovery_interval: Duration,
+    polling_rate: Duration,
+}
+
+async fn session_task(
+    config: SessionConfig,
+    events_tx: UnboundedSender<SessionEvent>,
+    state_tx: watch::Sender<SessionState>,
+) {
+    let mut timer = tokio::time::interval(config.discovery_interval);
+    timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+    loop {
+        timer.tick().await;
+        let Some(client_info) = discover(&config).await else {
+            continue;
+        };
+        let Ok(ws_client) = config
+            .websocket
+            .clone()
+            .client_info(client_info.clone())
+            .connect()
+            .await
+        else {
+            continue;
+        };
+
+        let (ws_handle, mut lcu_events) = ws_client.split(None);
+        let gameflow_subscription = LcuSubscriptionType::JsonApiEvent(GAMEFLOW_PHASE.to_string());
+        let mut subscribed = ws_handle
+            .subscribe(gameflow_subscription.clone())
+            .await
+            .is_ok();
+        for subscription in &config.subscriptions {
+            subscribed &= ws_handle.subscribe(subscription.clone()).await.is_ok();
+        }
+        if !subscribed {
+            continue;
+        }
+
+        // the websocket only reports changes, the REST API is asked for the phase at the time of the connection
+        let rest_client = RESTClient::new(client_info, false).ok();
+        let phase = match &rest_client {
+            Some(rest_client) => rest_client
+                .get(GAMEFLOW_PHASE.to_string())
+                .await
+                .ok()
+                .and_then(|phase| phase.as_str().map(str::to_string)),
+            None => None,
+        };
+        state_tx.send_replace(SessionState {
+            rest_client,
+            ws_handle: Some(ws_handle),
+            phase: None,
+        });
+        if events_tx.send(SessionEvent::ClientConnected).is_err() {
+            return;
+        }
+
+        let mut tracker = GameTracker {
+            config: &config,
+            events_tx: &events_tx,
+            state_tx: &state_tx,
+            ingame_events: None,
+            in_game: false,
+            phase: None,
+        };
+        if let Some(phase) = phase {
+            tracker.set_phase(phase);
+        }
+
+        loop {
+            tokio::select! {
+                event = lcu_events.next() => {
+                    let Some(event) = event else {
+                        break;
+                    };
+                    if let LcuEventPayload::JsonApi(payload) = &event.payload {
+                        if payload.uri == GAMEFLOW_PHASE {
+                            if let Some(phase) = payload.data.as_str() {
+                                tracker.set_phase(phase.to_string());
+                            }
+                        }
+                    }
+                    // the gameflow phase is only forwarded if it was subscribed to
+                    if event.subscription_type != gameflow_subscription
+                        || config.subscriptions.contains(&gameflow_subscription)
+                    {
+                        tracker.send(SessionEvent::Lcu(event));
+                    }
+                }
+                Some(event) = next_ingame_event(&mut tracker.ingame_events) => {
+                    tracker.ingame_event(event);
+                }
+            }
+            if events_tx.is_closed() {
+                return;
+            }
+        }
+
+        // a game that is still running is framed like the ContinuousEventStream would have
+        if tracker.in_game {
+            tracker.send(SessionEvent::Ingame(IngameStreamEvent::GameEnded));
+        }
+        drop(tracker);
+        state_tx.send_replace(SessionState::default());
+        if events_tx.send(SessionEvent::ClientDisconnected).is_err() {
+            return;
+        }
+    }
+}
+
+async fn discover(config: &SessionConfig) -> Option<LCUClientInfo> {
+    if let Some(client_info) = &config.client_info {
+        return Some(client_info.clone());
+    }
+    // reading the processes blocks for a while
+    tokio::task::spawn_blocking(process_info::get_lcu_client_info)
+        .await
+        .ok()?
+        .ok()
+}
+
+async fn next_ingame_event(
+    ingame_events: &mut Option<ContinuousEventStream>,
+) -> Option<IngameStreamEvent> {
+    match ingame_events {
+        Some(ingame_events) => ingame_events.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+/// Switches the ingame poller on and off with the gameflow phase of one connection
+struct GameTracker<'a> {
+    config: &'a SessionConfig,
+    events_tx: &'a UnboundedSender<SessionEvent>,
+    state_tx: &'a watch::Sender<SessionState>,
+    ingame_events: Option<ContinuousEventStream>,
+    /// The poller reported a [IngameStreamEvent::GameStarted] without a [IngameStreamEvent::GameEnded] yet
+    in_game: bool,
+    phase: Option<String>,
+}
+
+impl GameTracker<'_> {
+    fn send(&self, event: SessionEvent) {
+        // a closed channel ends the session task
+        let _ = self.events_tx.send(event);
+    }
+
+    fn set_phase(&mut self, phase: String) {
+        if self.phase.as_ref() == Some(&phase) {
+            return;
+        }
+        self.phase = Some(phase.clone());
+        self.state_tx
+            .send_modify(|state| state.phase = Some(phase.clone()));
+
+        if self.is_ingame_phase() {
+            if self.ingame_events.is_none() {
+                self.ingame_events = Some(ContinuousEventStream::from_ingame_client(
+                    self.config.ingame_client.clone(),
+                    Some(self.config.polling_rate),
+                    None,
+                ));
+            }
+        } else if !self.in_game {
+            // a running game is polled until it ended, e.g. during `WaitingForStats`
+            self.ingame_events = None;
+        }
+        self.send(SessionEvent::PhaseChanged(phase));
+    }
+
+    fn ingame_event(&mut self, event: IngameStreamEvent) {
+        match event {
+            IngameStreamEvent::GameStarted => self.in_game = true,
+            IngameStreamEvent::GameEnded => {
+                self.in_game = false;
+                if !self.is_ingame_phase() {
+                    self.ingame_events = None;
+                }
+            }
+            IngameStreamEvent::GameEvent(_) => {}
+        }
+        self.send(SessionEvent::Ingame(event));
+    }
+
+    fn is_ingame_phase(&self) -> bool {
+        self.phase
+            .as_deref()
+            .is_some_and(|phase| INGAME_PHASES.contains(&phase))
+    }
+}
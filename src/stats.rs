@@ -0,0 +1,130 @@
+use crate::model::{
+    ingame::{AllGameData, GameEvent, Player, PlayerItem, Price, TeamId},
+    stats::*,
+};
+
+/// Aggregates the players of the snapshot per team and counts the objectives of the event list \
+/// Teams are listed in the order their first player appears in [AllGameData::all_players]
+pub fn game_summary(data: &AllGameData) -> GameSummary {
+    let mut teams: Vec<TeamStats> = Vec::new();
+    for player in &data.all_players {
+        let index = match teams.iter().position(|t| t.team == player.team) {
+            Some(index) => index,
+            None => {
+                teams.push(empty_team_stats(player.team.clone()));
+                teams.len() - 1
+            }
+        };
+        let team = &mut teams[index];
+        team.kills += player.scores.kills;
+        team.deaths += player.scores.deaths;
+        team.assists += player.scores.assists;
+        team.creep_score += player.scores.creep_score;
+        team.ward_score += player.scores.ward_score;
+        team.recipe_gold += recipe_gold(&player.items);
+    }
+
+    for event in &data.events {
+        let players = &data.all_players;
+        let team = match event {
+            GameEvent::DragonKill(e) => e.killer_name.team(players),
+            GameEvent::BaronKill(e) => e.killer_name.team(players),
+            GameEvent::HeraldKill(e) => e.killer_name.team(players),
+            GameEvent::HordeKill(e) => e.killer_name.team(players),
+            // structures are credited to the other team, they are often destroyed by minions
+            GameEvent::TurretKilled(e) => e
+                .turret_killed
+                .team
+                .as_ref()
+                .and_then(opponent)
+                .or_else(|| e.killer_name.team(players)),
+            GameEvent::InhibKilled(e) => e
+                .inhib_killed
+                .team
+                .as_ref()
+                .and_then(opponent)
+                .or_else(|| e.killer_name.team(players)),
+            _ => None,
+        };
+        let Some(stats) = team.and_then(|team| teams.iter_mut().find(|t| t.team == team)) else {
+            continue;
+        };
+
+        match event {
+            GameEvent::DragonKill(e) => stats.dragons.push(e.dragon_type.clone()),
+            GameEvent::BaronKill(_) => stats.barons += 1,
+            GameEvent::HeraldKill(_) => stats.heralds += 1,
+            GameEvent::HordeKill(_) => stats.voidgrubs += 1,
+            GameEvent::TurretKilled(_) => stats.turrets += 1,
+            GameEvent::InhibKilled(_) => stats.inhibitors += 1,
+            _ => {}
+        }
+    }
+
+    let minutes = (data.game_data.game_time / 60.0) as f32;
+    let players = data
+        .all_players
+        .iter()
+        .map(|player| {
+            let team_kills = teams
+                .iter()
+                .find(|t| t.team == player.team)
+                .map(|t| t.kills)
+                .unwrap_or_default();
+            player_stats(player, team_kills, minutes)
+        })
+        .collect();
+
+    GameSummary { teams, players }
+}
+
+/// The sum of the recipe costs of the items as reported by the ingame API \
+/// The API's [PlayerItem::price] is the gold paid to combine an item from its components, not its total cost
+/// (e.g. 625 for Infinity Edge), so completed items are valued far below what was spent on them \
+/// Consumables count once per stack
+pub fn recipe_gold(items: &[PlayerItem]) -> Price {
+    items.iter().map(|item| item.price * item.count).sum()
+}
+
+fn player_stats(player: &Player, team_kills: i32, minutes: f32) -> PlayerStats {
+    let scores = &player.scores;
+    let takedowns = (scores.kills + scores.assists) as f32;
+    PlayerStats {
+        summoner_name: player.summoner_name.clone(),
+        team: player.team.clone(),
+        kda: takedowns / scores.deaths.max(1) as f32,
+        kill_participation: (team_kills > 0).then(|| takedowns / team_kills as f32),
+        cs_per_minute: if minutes > 0.0 {
+            scores.creep_score as f32 / minutes
+        } else {
+            0.0
+        },
+        recipe_gold: recipe_gold(&player.items),
+    }
+}
+
+fn opponent(team: &TeamId) -> Option<TeamId> {
+    match team {
+        TeamId::Order => Some(TeamId::Chaos),
+        TeamId::Chaos => Some(TeamId::Order),
+        _ => None,
+    }
+}
+
+fn empty_team_stats(team: TeamId) -> TeamStats {
+    TeamStats {
+        team,
+        kills: 0,
+        deaths: 0,
+        assists: 0,
+        creep_score: 0,
+        ward_score: 0.0,
+        recipe_gold: 0,
+        dragons: Vec::new(),
+        barons: 0,
+        heralds: 0,
+        voidgrubs: 0,
+        turrets: 0,
+        inhibitors: 0,
+    }
+}
@@ -0,0 +1,457 @@
+use std::{
+    fs::File,
+    io,
+    io::{BufRead, BufReader, BufWriter, Read, Write},
+    path::Path,
+    time::Duration,
+};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde_json::{json, Value};
+use tokio::{
+    sync::{oneshot, oneshot::Sender},
+    task::JoinHandle,
+};
+
+use crate::{
+    ingame::{IngameClient, DEFAULT_MAX_RETRIES},
+    model::{
+        ingame::{AllGameData, GameEvent, Time},
+        timeline::*,
+    },
+};
+
+const DEFAULT_POLLING_RATE_MILLIS: u64 = 1000;
+
+/// How a [TimelineWriter] writes the timeline
+#[derive(Debug, Clone)]
+pub struct TimelineOptions {
+    /// The seconds of game time after which a full snapshot is written instead of a delta,
+    /// so the reader doesn't have to apply every delta since the start of the game \
+    /// The default is 60s
+    pub snapshot_interval: Time,
+    /// The default is [Compression::default]
+    pub compression: Compression,
+}
+
+impl Default for TimelineOptions {
+    fn default() -> Self {
+        Self {
+            snapshot_interval: 60.0,
+            compression: Compression::default(),
+        }
+    }
+}
+
+/// Writes `/liveclientdata/allgamedata` responses to a gzip compressed timeline of line-delimited [TimelineEntry]s \
+/// The first response is written as a full snapshot, the following ones as deltas to the previous one.
+/// Every event is written once
+pub struct TimelineWriter<W: Write = BufWriter<File>> {
+    encoder: GzEncoder<W>,
+    options: TimelineOptions,
+    previous: Option<Value>,
+    last_snapshot: Time,
+    next_event_id: u64,
+}
+
+impl TimelineWriter {
+    /// Create a writer that writes to a new file at `path`, truncating an existing one
+    pub fn to_file<P: AsRef<Path>>(path: P, options: TimelineOptions) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?), options))
+    }
+}
+
+impl<W: Write> TimelineWriter<W> {
+    /// Create a writer that writes to the given writer
+    pub fn new(writer: W, options: TimelineOptions) -> Self {
+        Self {
+            encoder: GzEncoder::new(writer, options.compression),
+            options,
+            previous: None,
+            last_snapshot: 0.0,
+            next_event_id: 0,
+        }
+    }
+
+    /// Writes the changes since the last response and the events that have not been written yet \
+    /// Takes the untyped response, see [IngameClient::all_game_data_raw]
+    pub fn write(&mut self, data: &Value) -> io::Result<()> {
+        let mut state = data.clone();
+        let events = state
+            .as_object_mut()
+            .and_then(|state| state.remove("events"))
+            .unwrap_or_default();
+        let game_time = state["gameData"]["gameTime"]
+            .as_f64()
+            .ok_or_else(|| invalid_data("the response has no game time"))?;
+
+        let entry = match &self.previous {
+            Some(previous)
+                if game_time >= self.last_snapshot
+                    && game_time - self.last_snapshot < self.options.snapshot_interval =>
+            {
+                let mut patch = Vec::new();
+                diff(previous, &state, &mut String::new(), &mut patch);
+                TimelineEntry::Delta { game_time, patch }
+            }
+            _ => {
+                self.last_snapshot = game_time;
+                TimelineEntry::Snapshot {
+                    game_time,
+                    data: state.clone(),
+                }
+            }
+        };
+        self.write_entry(&entry)?;
+        self.previous = Some(state);
+
+        for event in events["Events"].as_array().into_iter().flatten() {
+            let Some(event_id) = event["EventID"].as_u64() else {
+                continue;
+            };
+            if event_id >= self.next_event_id {
+                self.next_event_id = event_id + 1;
+                self.write_entry(&TimelineEntry::Event {
+                    event: event.clone(),
+                })?;
+            }
+        }
+
+        // flush after every response so the timeline is readable even if the process gets killed
+        self.encoder.flush()
+    }
+
+    /// Completes the compressed stream and returns the writer
+    pub fn finish(self) -> io::Result<W> {
+        let mut writer = self.encoder.finish()?;
+        writer.flush()?;
+        Ok(writer)
+    }
+
+    fn write_entry(&mut self, entry: &TimelineEntry) -> io::Result<()> {
+        serde_json::to_writer(&mut self.encoder, entry)?;
+        self.encoder.write_all(b"\n")
+    }
+}
+
+/// Records the next game the [IngameClient] connects to with a [TimelineWriter] \
+/// The recording ends when the game ends or [TimelineRecorder::stop] is called
+pub struct TimelineRecorder<W: Write = BufWriter<File>> {
+    stop_tx: Option<Sender<()>>,
+    record_task_handle: Option<JoinHandle<io::Result<W>>>,
+}
+
+impl TimelineRecorder {
+    /// Record to a new file at `path`, truncating an existing one \
+    /// Takes an [Option] that specifies the polling rate of the [IngameClient] that's being wrapped \
+    /// The default [Duration] is 1s \
+    /// Takes an [Option] that specifies how many API calls in a row can fail before the game is considered ended \
+    /// The default is 5
+    pub fn to_file<P: AsRef<Path>>(
+        ingame_client: IngameClient,
+        path: P,
+        polling_rate: Option<Duration>,
+        max_retries: Option<u32>,
+        options: TimelineOptions,
+    ) -> io::Result<Self> {
+        Ok(Self::new(
+            ingame_client,
+            TimelineWriter::to_file(path, options)?,
+            polling_rate,
+            max_retries,
+        ))
+    }
+}
+
+impl<W: Write + Send + 'static> TimelineRecorder<W> {
+    /// Record with the given [TimelineWriter] \
+    /// Takes an [Option] that specifies the polling rate of the [IngameClient] that's being wrapped \
+    /// The default [Duration] is 1s \
+    /// Takes an [Option] that specifies how many API calls in a row can fail before the game is considered ended \
+    /// The default is 5
+    pub fn new(
+        ingame_client: IngameClient,
+        mut writer: TimelineWriter<W>,
+        polling_rate: Option<Duration>,
+        max_retries: Option<u32>,
+    ) -> Self {
+        let (stop_tx, mut stop_rx) = oneshot::channel::<()>();
+
+        let record_task_handle = tokio::spawn(async move {
+            let polling_rate =
+                polling_rate.unwrap_or(Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS));
+            let max_retries = max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
+            let mut timer = tokio::time::interval(polling_rate);
+            let mut started = false;
+            let mut failed_requests = 0;
+
+            loop {
+                tokio::select! {
+                    _ = &mut stop_rx => break,
+                    _ = timer.tick() => {}
+                }
+                match ingame_client.all_game_data_raw(None).await {
+                    Ok(data) => {
+                        started = true;
+                        failed_requests = 0;
+                        writer.write(&data)?;
+                    }
+                    // the game ended if the api calls fail more than max_retries times in a row
+                    Err(_) if started => {
+                        failed_requests += 1;
+                        if failed_requests > max_retries {
+                            break;
+                        }
+                    }
+                    // wait for a game to start
+                    Err(_) => {}
+                }
+            }
+
+            writer.finish()
+        });
+
+        Self {
+            stop_tx: Some(stop_tx),
+            record_task_handle: Some(record_task_handle),
+        }
+    }
+
+    /// Waits until the game ended and returns the writer of the completed timeline
+    pub async fn finished(mut self) -> io::Result<W> {
+        let handle = self
+            .record_task_handle
+            .take()
+            .expect("the handle is only taken when the recorder is consumed");
+        handle.await.map_err(io::Error::other)?
+    }
+
+    /// Stops the recording and returns the writer of the completed timeline
+    pub async fn stop(mut self) -> io::Result<W> {
+        if let Some(stop_tx) = self.stop_tx.take() {
+            let _ = stop_tx.send(());
+        }
+        self.finished().await
+    }
+}
+
+impl<W: Write> Drop for TimelineRecorder<W> {
+    fn drop(&mut self) {
+        if let Some(handle) = &self.record_task_handle {
+            handle.abort();
+        }
+    }
+}
+
+enum Frame {
+    Snapshot(Value),
+    Delta(Vec<PatchOperation>),
+}
+
+/// A timeline written by a [TimelineWriter], reconstructs the [AllGameData] at any recorded game time
+pub struct Timeline {
+    frames: Vec<(Time, Frame)>,
+    events: Vec<Value>,
+}
+
+impl Timeline {
+    /// Read a timeline from a file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Read a compressed timeline from any reader \
+    /// A timeline that has not been finished, e.g. because the recording is still running, is read up to the last complete entry
+    pub fn from_reader<R: Read>(reader: R) -> io::Result<Self> {
+        let mut frames = Vec::new();
+        let mut events = Vec::new();
+
+        for line in BufReader::new(GzDecoder::new(reader)).lines() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line)? {
+                TimelineEntry::Snapshot { game_time, data } => {
+                    frames.push((game_time, Frame::Snapshot(data)))
+                }
+                TimelineEntry::Delta { game_time, patch } => {
+                    if frames.is_empty() {
+                        return Err(invalid_data("the timeline starts with a delta"));
+                    }
+                    frames.push((game_time, Frame::Delta(patch)))
+                }
+                TimelineEntry::Event { event } => events.push(event),
+            }
+        }
+
+        Ok(Self { frames, events })
+    }
+
+    /// The game times of the recorded responses
+    pub fn game_times(&self) -> impl Iterator<Item = Time> + '_ {
+        self.frames.iter().map(|(game_time, _)| *game_time)
+    }
+
+    /// The number of recorded responses
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// All events of the game
+    pub fn events(&self) -> io::Result<Vec<GameEvent>> {
+        self.events
+            .iter()
+            .map(|event| serde_json::from_value(event.clone()).map_err(io::Error::from))
+            .collect()
+    }
+
+    /// The untyped response of the last recorded game time at or before `game_time`,
+    /// with the events that happened until then \
+    /// Returns None if `game_time` is before the start of the recording
+    pub fn raw_state_at(&self, game_time: Time) -> io::Result<Option<Value>> {
+        let end = self.frames.partition_point(|(t, _)| *t <= game_time);
+        let Some(start) = self.frames[..end]
+            .iter()
+            .rposition(|(_, frame)| matches!(frame, Frame::Snapshot(_)))
+        else {
+            return Ok(None);
+        };
+
+        let mut state = Value::Null;
+        for (_, frame) in &self.frames[start..end] {
+            apply_frame(&mut state, frame)?;
+        }
+        Ok(Some(self.with_events(state, self.frames[end - 1].0)))
+    }
+
+    /// The [AllGameData] of the last recorded game time at or before `game_time`,
+    /// with the events that happened until then \
+    /// Returns None if `game_time` is before the start of the recording
+    pub fn state_at(&self, game_time: Time) -> io::Result<Option<AllGameData>> {
+        self.raw_state_at(game_time)?
+            .map(|state| serde_json::from_value(state).map_err(io::Error::from))
+            .transpose()
+    }
+
+    /// Every recorded [AllGameData] in order, e.g. to plot the stats over the game
+    pub fn frames(&self) -> impl Iterator<Item = io::Result<AllGameData>> + '_ {
+        let mut state = Value::Null;
+        self.frames.iter().map(move |(game_time, frame)| {
+            apply_frame(&mut state, frame)?;
+            let data = self.with_events(state.clone(), *game_time);
+            serde_json::from_value(data).map_err(io::Error::from)
+        })
+    }
+
+    fn with_events(&self, mut state: Value, game_time: Time) -> Value {
+        let events: Vec<&Value> = self
+            .events
+            .iter()
+            .filter(|e| e["EventTime"].as_f64().unwrap_or_default() <= game_time)
+            .collect();
+        state["events"] = json!({ "Events": events });
+        state
+    }
+}
+
+fn apply_frame(state: &mut Value, frame: &Frame) -> io::Result<()> {
+    match frame {
+        Frame::Snapshot(data) => *state = data.clone(),
+        Frame::Delta(patch) => {
+            for operation in patch {
+                apply(state, operation)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Collects the operations that turn `old` into `new` \
+/// Arrays of the same length are compared element-wise, arrays that changed their length are replaced
+fn diff(old: &Value, new: &Value, path: &mut String, patch: &mut Vec<PatchOperation>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for key in old.keys().filter(|key| !new.contains_key(*key)) {
+                patch.push(PatchOperation::Remove {
+                    path: format!("{}/{}", path, escape(key)),
+                });
+            }
+            for (key, value) in new {
+                let len = path.len();
+                path.push('/');
+                path.push_str(&escape(key));
+                match old.get(key) {
+                    Some(old_value) => diff(old_value, value, path, patch),
+                    None => patch.push(PatchOperation::Add {
+                        path: path.clone(),
+                        value: value.clone(),
+                    }),
+                }
+                path.truncate(len);
+            }
+        }
+        (Value::Array(old), Value::Array(new)) if old.len() == new.len() => {
+            for (i, (old_value, value)) in old.iter().zip(new).enumerate() {
+                let len = path.len();
+                path.push_str(&format!("/{i}"));
+                diff(old_value, value, path, patch);
+                path.truncate(len);
+            }
+        }
+        _ if old != new => patch.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn apply(state: &mut Value, operation: &PatchOperation) -> io::Result<()> {
+    match operation {
+        PatchOperation::Replace { path, value } => {
+            *state
+                .pointer_mut(path)
+                .ok_or_else(|| invalid_data(format!("invalid patch path {path}")))? = value.clone();
+        }
+        PatchOperation::Add { path, value } => {
+            let (parent, key) = parent_object(state, path)?;
+            parent.insert(key, value.clone());
+        }
+        PatchOperation::Remove { path } => {
+            let (parent, key) = parent_object(state, path)?;
+            parent.remove(&key);
+        }
+    }
+    Ok(())
+}
+
+fn parent_object<'a>(
+    state: &'a mut Value,
+    path: &str,
+) -> io::Result<(&'a mut serde_json::Map<String, Value>, String)> {
+    let invalid_path = || invalid_data(format!("invalid patch path {path}"));
+    let (parent, key) = path.rsplit_once('/').ok_or_else(invalid_path)?;
+    let parent = state
+        .pointer_mut(parent)
+        .and_then(Value::as_object_mut)
+        .ok_or_else(invalid_path)?;
+    Ok((parent, key.replace("~1", "/").replace("~0", "~")))
+}
+
+/// Escapes a key for a JSON pointer
+fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+fn invalid_data<S: Into<String>>(message: S) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.into())
+}
@@ -0,0 +1,442 @@
+use std::{collections::HashSet, task::Poll, time::Duration};
+
+use futures_util::Stream;
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver},
+    sync::oneshot,
+    sync::oneshot::Sender,
+    sync::watch,
+    task::JoinHandle,
+};
+
+use crate::{
+    ingame::{IngameClient, DEFAULT_MAX_RETRIES, DEFAULT_POLLING_RATE_MILLIS},
+    model::{
+        ingame::{AllGameData, DragonType, EventId, GameEvent, MapName, Player, TeamId, Time},
+        timers::*,
+    },
+};
+
+/// The spawn rules used by the [ObjectiveTracker] \
+/// The defaults match the current Summoner's Rift, all times are in seconds of game time
+#[derive(Debug, Clone)]
+pub struct TimerOptions {
+    /// How long before a spawn or respawn a "soon" notification is emitted \
+    /// The default is 60s
+    pub lead_time: Time,
+    pub dragon_spawn: Time,
+    pub dragon_respawn: Time,
+    /// The delay of the first Elder Dragon after a soul was claimed and of every respawn
+    pub elder_respawn: Time,
+    pub herald_spawn: Time,
+    /// The herald leaves the map if it hasn't been slain until then
+    pub herald_despawn: Time,
+    pub baron_spawn: Time,
+    pub baron_respawn: Time,
+    pub baron_buff_duration: Time,
+    pub inhibitor_respawn: Time,
+    /// The number of elemental dragons a team needs to claim the soul
+    pub dragons_for_soul: usize,
+}
+
+impl Default for TimerOptions {
+    fn default() -> Self {
+        Self {
+            lead_time: 60.0,
+            dragon_spawn: 300.0,
+            dragon_respawn: 300.0,
+            elder_respawn: 360.0,
+            herald_spawn: 840.0,
+            herald_despawn: 1185.0,
+            baron_spawn: 1200.0,
+            baron_respawn: 360.0,
+            baron_buff_duration: 180.0,
+            inhibitor_respawn: 300.0,
+            dragons_for_soul: 4,
+        }
+    }
+}
+
+/// Tracks the spawn timers of epic monsters, inhibitor respawns, buffs and dragon souls \
+/// Feed it consecutive [AllGameData] snapshots with [ObjectiveTracker::update]
+#[derive(Debug, Clone, Default)]
+pub struct ObjectiveTracker {
+    options: TimerOptions,
+    timers: ObjectiveTimers,
+    started: bool,
+    next_event_id: EventId,
+    warned: HashSet<Objective>,
+    spawned: HashSet<Objective>,
+    warned_inhibitors: HashSet<String>,
+}
+
+impl ObjectiveTracker {
+    pub fn new(options: TimerOptions) -> Self {
+        Self {
+            options,
+            ..Default::default()
+        }
+    }
+
+    /// Forget the current game, e.g. when a new game starts
+    pub fn reset(&mut self) {
+        *self = Self::new(self.options.clone());
+    }
+
+    /// The current state of all timers
+    pub fn timers(&self) -> &ObjectiveTimers {
+        &self.timers
+    }
+
+    /// Processes the new events of the snapshot and advances the timers to its game time \
+    /// The notifications of the first snapshot of a game are suppressed,
+    /// so joining a running game doesn't repeat everything that happened before
+    pub fn update(&mut self, data: &AllGameData) -> Vec<TimerNotification> {
+        // the game time going backwards means a new game has started
+        if self.started && data.game_data.game_time < self.timers.game_time {
+            self.reset();
+        }
+
+        let first_update = !self.started;
+        if first_update {
+            self.start(&data.game_data.map_name);
+        }
+        self.timers.game_time = data.game_data.game_time;
+
+        let mut notifications = Vec::new();
+        let next_event_id = self.next_event_id;
+        for event in data
+            .events
+            .iter()
+            .filter(|e| e.get_event_id() >= next_event_id)
+        {
+            self.handle_event(event, &data.all_players, &mut notifications);
+            self.next_event_id = self.next_event_id.max(event.get_event_id() + 1);
+        }
+        self.check_timers(&mut notifications);
+
+        if first_update {
+            notifications.clear();
+        }
+        notifications
+    }
+
+    fn start(&mut self, map: &MapName) {
+        self.started = true;
+        // only Summoner's Rift has epic monsters
+        if !matches!(map, MapName::Map11) {
+            return;
+        }
+        self.timers.dragon = Some(SpawnTimer {
+            objective: Objective::Dragon,
+            spawns_at: self.options.dragon_spawn,
+        });
+        self.timers.herald = Some(SpawnTimer {
+            objective: Objective::Herald,
+            spawns_at: self.options.herald_spawn,
+        });
+        self.timers.baron = Some(SpawnTimer {
+            objective: Objective::Baron,
+            spawns_at: self.options.baron_spawn,
+        });
+    }
+
+    fn handle_event(
+        &mut self,
+        event: &GameEvent,
+        players: &[Player],
+        notifications: &mut Vec<TimerNotification>,
+    ) {
+        match event {
+            GameEvent::DragonKill(e) => {
+                let team = e.killer_name.team(players);
+                match (e.elder_buff_expiry(), team) {
+                    (Some(expires_at), Some(team)) => {
+                        self.add_buff(ObjectiveBuff::Elder, team, expires_at)
+                    }
+                    (None, Some(team)) => self.add_dragon(&e.dragon_type, team, notifications),
+                    _ => {}
+                }
+
+                let soul_claimed = self.timers.order_dragons.soul.is_some()
+                    || self.timers.chaos_dragons.soul.is_some();
+                self.timers.dragon = Some(if soul_claimed {
+                    SpawnTimer {
+                        objective: Objective::ElderDragon,
+                        spawns_at: e.event_time + self.options.elder_respawn,
+                    }
+                } else {
+                    SpawnTimer {
+                        objective: Objective::Dragon,
+                        spawns_at: e.event_time + self.options.dragon_respawn,
+                    }
+                });
+                self.forget_notifications(Objective::Dragon);
+                self.forget_notifications(Objective::ElderDragon);
+            }
+            GameEvent::BaronKill(e) => {
+                if let Some(team) = e.killer_name.team(players) {
+                    self.add_buff(
+                        ObjectiveBuff::Baron,
+                        team,
+                        e.event_time + self.options.baron_buff_duration,
+                    );
+                }
+                self.timers.baron = Some(SpawnTimer {
+                    objective: Objective::Baron,
+                    spawns_at: e.event_time + self.options.baron_respawn,
+                });
+                self.forget_notifications(Objective::Baron);
+            }
+            GameEvent::HeraldKill(_) => self.timers.herald = None,
+            GameEvent::InhibKilled(e) => {
+                let inhibitor = &e.inhib_killed;
+                self.remove_inhibitor(&inhibitor.raw);
+                self.timers.inhibitors.push(InhibitorTimer {
+                    inhibitor: inhibitor.clone(),
+                    respawns_at: e.event_time + self.options.inhibitor_respawn,
+                });
+            }
+            GameEvent::InhibRespawningSoon(e) => {
+                let inhibitor = &e.inhib_respawning_soon;
+                if let Some(timer) = self
+                    .timers
+                    .inhibitors
+                    .iter()
+                    .find(|timer| timer.inhibitor.raw == inhibitor.raw)
+                {
+                    if self.warned_inhibitors.insert(inhibitor.raw.clone()) {
+                        notifications.push(TimerNotification::InhibitorRespawningSoon {
+                            inhibitor: inhibitor.clone(),
+                            respawns_at: timer.respawns_at,
+                        });
+                    }
+                }
+            }
+            GameEvent::InhibRespawned(e) => {
+                if let Some(timer) = self.remove_inhibitor(&e.inhib_respawned.raw) {
+                    notifications.push(TimerNotification::InhibitorRespawned {
+                        inhibitor: timer.inhibitor,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn check_timers(&mut self, notifications: &mut Vec<TimerNotification>) {
+        let game_time = self.timers.game_time;
+        let lead_time = self.options.lead_time;
+
+        if game_time >= self.options.herald_despawn {
+            self.timers.herald = None;
+        }
+
+        for timer in [&self.timers.dragon, &self.timers.herald, &self.timers.baron]
+            .into_iter()
+            .flatten()
+        {
+            if game_time >= timer.spawns_at {
+                if self.spawned.insert(timer.objective) {
+                    self.warned.insert(timer.objective);
+                    notifications.push(TimerNotification::Spawned {
+                        objective: timer.objective,
+                    });
+                }
+            } else if game_time >= timer.spawns_at - lead_time
+                && self.warned.insert(timer.objective)
+            {
+                notifications.push(TimerNotification::SpawningSoon {
+                    objective: timer.objective,
+                    spawns_at: timer.spawns_at,
+                });
+            }
+        }
+
+        let mut respawned = Vec::new();
+        for timer in &self.timers.inhibitors {
+            if game_time >= timer.respawns_at {
+                respawned.push(timer.inhibitor.raw.clone());
+            } else if game_time >= timer.respawns_at - lead_time
+                && self.warned_inhibitors.insert(timer.inhibitor.raw.clone())
+            {
+                notifications.push(TimerNotification::InhibitorRespawningSoon {
+                    inhibitor: timer.inhibitor.clone(),
+                    respawns_at: timer.respawns_at,
+                });
+            }
+        }
+        for raw in respawned {
+            if let Some(timer) = self.remove_inhibitor(&raw) {
+                notifications.push(TimerNotification::InhibitorRespawned {
+                    inhibitor: timer.inhibitor,
+                });
+            }
+        }
+
+        self.timers.buffs.retain(|timer| {
+            if game_time < timer.expires_at {
+                return true;
+            }
+            notifications.push(TimerNotification::BuffExpired {
+                buff: timer.buff,
+                team: timer.team.clone(),
+            });
+            false
+        });
+    }
+
+    fn add_dragon(
+        &mut self,
+        dragon_type: &DragonType,
+        team: TeamId,
+        notifications: &mut Vec<TimerNotification>,
+    ) {
+        let dragons_for_soul = self.options.dragons_for_soul;
+        let progress = match team {
+            TeamId::Order => &mut self.timers.order_dragons,
+            TeamId::Chaos => &mut self.timers.chaos_dragons,
+            _ => return,
+        };
+        progress.dragons.push(dragon_type.clone());
+
+        // from the third dragon on only the dragon of the rift type spawns
+        if progress.soul.is_none() && progress.dragons.len() >= dragons_for_soul {
+            progress.soul = Some(dragon_type.clone());
+            notifications.push(TimerNotification::DragonSoulClaimed {
+                team,
+                soul: dragon_type.clone(),
+            });
+        }
+    }
+
+    fn add_buff(&mut self, buff: ObjectiveBuff, team: TeamId, expires_at: Time) {
+        self.timers
+            .buffs
+            .retain(|timer| timer.buff != buff || timer.team != team);
+        self.timers.buffs.push(BuffTimer {
+            buff,
+            team,
+            expires_at,
+        });
+    }
+
+    fn remove_inhibitor(&mut self, raw: &str) -> Option<InhibitorTimer> {
+        self.warned_inhibitors.remove(raw);
+        let index = self
+            .timers
+            .inhibitors
+            .iter()
+            .position(|timer| timer.inhibitor.raw == raw)?;
+        Some(self.timers.inhibitors.remove(index))
+    }
+
+    fn forget_notifications(&mut self, objective: Objective) {
+        self.warned.remove(&objective);
+        self.spawned.remove(&objective);
+    }
+}
+
+/// A wrapper around a [IngameClient] that regularly polls all game data \
+/// Emits the [TimerNotification]s of an [ObjectiveTracker] and exposes its current timers
+pub struct ObjectiveTimerStream {
+    start_tx: Option<Sender<()>>,
+    poll_task_handle: JoinHandle<()>,
+    notifications_rx: UnboundedReceiver<TimerNotification>,
+    timers_rx: watch::Receiver<ObjectiveTimers>,
+}
+
+impl ObjectiveTimerStream {
+    /// Create a [ObjectiveTimerStream] from an [IngameClient] \
+    /// Takes an [Option] that specifies the polling rate of the [IngameClient] that's being wrapped \
+    /// The default [Duration] is 500ms \
+    /// Takes an [Option] with the [TimerOptions] of the [ObjectiveTracker] \
+    /// Takes an [Option] that specifies how many API calls in a row can fail before the stream ends \
+    /// The default is 5
+    pub fn from_ingame_client(
+        ingame_client: IngameClient,
+        polling_rate: Option<Duration>,
+        options: Option<TimerOptions>,
+        max_retries: Option<u32>,
+    ) -> Self {
+        let (start_tx, start_rx) = oneshot::channel::<()>();
+        let (notifications_tx, notifications_rx) = unbounded_channel();
+        let (timers_tx, timers_rx) = watch::channel(ObjectiveTimers::default());
+
+        let poll_task_handle = tokio::spawn(async move {
+            let polling_rate =
+                polling_rate.unwrap_or(Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS));
+            let max_retries = max_retries.unwrap_or(DEFAULT_MAX_RETRIES);
+            let mut timer = tokio::time::interval(polling_rate);
+            let mut tracker = ObjectiveTracker::new(options.unwrap_or_default());
+            let mut failed_requests = 0;
+
+            // await start, but return on error (start_tx got dropped)
+            if start_rx.await.is_err() {
+                return;
+            }
+
+            // wait for a game to start
+            loop {
+                timer.tick().await;
+                if ingame_client.active_game().await {
+                    break;
+                };
+            }
+
+            // loop until the api calls fail more than max_retries times in a row
+            loop {
+                timer.tick().await;
+                match ingame_client.all_game_data(None).await {
+                    Ok(data) => {
+                        failed_requests = 0;
+                        tracker.update(&data).into_iter().for_each(|n| {
+                            let _ = notifications_tx.send(n);
+                        });
+                        timers_tx.send_replace(tracker.timers().clone());
+                    }
+                    Err(_) => {
+                        failed_requests += 1;
+                        if failed_requests > max_retries {
+                            return;
+                        }
+                    }
+                }
+            }
+        });
+
+        Self {
+            start_tx: Some(start_tx),
+            poll_task_handle,
+            notifications_rx,
+            timers_rx,
+        }
+    }
+
+    /// The timers as of the last successful poll
+    pub fn timers(&self) -> ObjectiveTimers {
+        self.timers_rx.borrow().clone()
+    }
+}
+
+impl Stream for ObjectiveTimerStream {
+    type Item = TimerNotification;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        if let Some(start_tx) = self.start_tx.take() {
+            if start_tx.send(()).is_err() {
+                return Poll::Ready(None);
+            }
+        }
+        self.notifications_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for ObjectiveTimerStream {
+    fn drop(&mut self) {
+        self.poll_task_handle.abort()
+    }
+}
@@ -0,0 +1,312 @@
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures_util::Stream;
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver},
+    task::JoinHandle,
+};
+
+use crate::{
+    error::{LockfileError, ProcessInfoError},
+    model::lockfile::{Lockfile, LockfileEvent},
+    rest::LCUClientInfo,
+    utils::process_info,
+};
+
+const LOCKFILE: &str = "lockfile";
+/// Relative to the local app data directory
+const RIOT_CLIENT_LOCKFILE: &str = "Riot Games/Riot Client/Config/lockfile";
+/// Relative to the program data directory, written by the Riot Client when League of Legends is installed
+const PRODUCT_SETTINGS: &str =
+    "Riot Games/Metadata/league_of_legends.live/league_of_legends.live.product_settings.yaml";
+
+const DEFAULT_POLLING_RATE_MILLIS: u64 = 1000;
+
+/// Where the credentials of the client are read from, see [DiscoveryOptions::order]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CredentialSource {
+    /// The lockfiles of the League client and the Riot Client, see [lockfile_client_info]
+    Lockfile,
+    /// The command line arguments of the League client process, see [get_lcu_client_info](process_info::get_lcu_client_info)
+    ProcessArgs,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiscoveryOptions {
+    /// The install directories of League of Legends that are searched for the `lockfile` in order \
+    /// The default is [default_install_paths]
+    pub install_paths: Vec<PathBuf>,
+    /// The paths of the lockfile of the Riot Client in order \
+    /// The default is [default_riot_client_lockfiles]
+    pub riot_client_lockfiles: Vec<PathBuf>,
+    /// The sources that are tried in order until one succeeds \
+    /// The default is [CredentialSource::Lockfile], then [CredentialSource::ProcessArgs]
+    pub order: Vec<CredentialSource>,
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            install_paths: default_install_paths(),
+            riot_client_lockfiles: default_riot_client_lockfiles(),
+            order: vec![CredentialSource::Lockfile, CredentialSource::ProcessArgs],
+        }
+    }
+}
+
+impl DiscoveryOptions {
+    /// The paths of the lockfile of the League client in the install directories
+    pub fn league_lockfiles(&self) -> Vec<PathBuf> {
+        self.install_paths
+            .iter()
+            .map(|path| path.join(LOCKFILE))
+            .collect()
+    }
+}
+
+/// Tries the sources in [DiscoveryOptions::order] and returns the credentials of the first one that succeeds \
+/// Returns the error of the last source if none succeeds
+pub fn discover_client_info(options: &DiscoveryOptions) -> Result<LCUClientInfo, ProcessInfoError> {
+    let mut error = ProcessInfoError::ProcessNotAvailable;
+    for source in &options.order {
+        let client_info = match source {
+            CredentialSource::Lockfile => {
+                lockfile_client_info(options).map_err(ProcessInfoError::Lockfile)
+            }
+            CredentialSource::ProcessArgs => process_info::get_lcu_client_info(),
+        };
+        match client_info {
+            Ok(client_info) => return Ok(client_info),
+            Err(e) => error = e,
+        }
+    }
+    Err(error)
+}
+
+/// Builds the credentials from the lockfile of the League client ([LCUClientInfo::remoting_port])
+/// and the lockfile of the Riot Client ([LCUClientInfo::port]) \
+/// Without a lockfile of the Riot Client both credentials are the ones of the League client,
+/// so the League client API is still usable while the Riot Client API is not \
+/// A client that crashed might have left a stale lockfile behind, connecting with its credentials fails
+pub fn lockfile_client_info(options: &DiscoveryOptions) -> Result<LCUClientInfo, LockfileError> {
+    let league = find_lockfile(&options.league_lockfiles())?;
+    let riot_client = match find_lockfile(&options.riot_client_lockfiles) {
+        Err(LockfileError::NotFound) => league.clone(),
+        riot_client => riot_client?,
+    };
+
+    Ok(LCUClientInfo {
+        port: riot_client.port,
+        token: riot_client.token(),
+        remoting_port: league.port,
+        remoting_token: league.token(),
+    })
+}
+
+pub fn read_lockfile<P: AsRef<Path>>(path: P) -> Result<Lockfile, LockfileError> {
+    let content = fs::read_to_string(path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => LockfileError::NotFound,
+        _ => LockfileError::IoError(e.to_string()),
+    })?;
+    content.parse().map_err(LockfileError::InvalidFormat)
+}
+
+/// Reads the first of the lockfiles that exists
+pub fn find_lockfile(paths: &[PathBuf]) -> Result<Lockfile, LockfileError> {
+    for path in paths {
+        match read_lockfile(path) {
+            Err(LockfileError::NotFound) => continue,
+            result => return result,
+        }
+    }
+    Err(LockfileError::NotFound)
+}
+
+/// The usual install directories of League of Legends on this OS \
+/// On Windows the directory recorded by the Riot Client comes first,
+/// on Linux the install directories in the Wine prefixes (`$WINEPREFIX`, `~/.wine` and the Lutris default `~/Games/league-of-legends`)
+pub fn default_install_paths() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    {
+        if let Some(path) = env_path("PROGRAMDATA")
+            .and_then(|program_data| recorded_install_path(&program_data.join(PRODUCT_SETTINGS)))
+        {
+            paths.push(PathBuf::from(path));
+        }
+        paths.push(PathBuf::from(r"C:\Riot Games\League of Legends"));
+    }
+
+    #[cfg(target_os = "macos")]
+    paths.push(PathBuf::from(
+        "/Applications/League of Legends.app/Contents/LoL",
+    ));
+
+    #[cfg(target_os = "linux")]
+    for prefix in wine_prefixes() {
+        let program_data = prefix.join("drive_c/ProgramData");
+        if let Some(path) = recorded_install_path(&program_data.join(PRODUCT_SETTINGS))
+            .and_then(|path| path_in_wine_prefix(&prefix, &path))
+        {
+            paths.push(path);
+        }
+        paths.push(prefix.join("drive_c/Riot Games/League of Legends"));
+    }
+
+    paths.dedup();
+    paths
+}
+
+/// The usual paths of the lockfile of the Riot Client on this OS \
+/// On Linux the app data directories of every user of the Wine prefixes, see [default_install_paths]
+pub fn default_riot_client_lockfiles() -> Vec<PathBuf> {
+    let mut paths = Vec::new();
+
+    #[cfg(target_os = "windows")]
+    if let Some(local_app_data) = env_path("LOCALAPPDATA") {
+        paths.push(local_app_data.join(RIOT_CLIENT_LOCKFILE));
+    }
+
+    #[cfg(target_os = "macos")]
+    if let Some(home) = env_path("HOME") {
+        paths.push(
+            home.join("Library/Application Support")
+                .join(RIOT_CLIENT_LOCKFILE),
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    for prefix in wine_prefixes() {
+        let Ok(users) = fs::read_dir(prefix.join("drive_c/users")) else {
+            continue;
+        };
+        for user in users.flatten() {
+            paths.push(user.path().join("AppData/Local").join(RIOT_CLIENT_LOCKFILE));
+        }
+    }
+
+    paths
+}
+
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+}
+
+#[cfg(target_os = "linux")]
+fn wine_prefixes() -> Vec<PathBuf> {
+    let mut prefixes = Vec::new();
+    if let Some(prefix) = env_path("WINEPREFIX") {
+        prefixes.push(prefix);
+    }
+    if let Some(home) = env_path("HOME") {
+        prefixes.push(home.join(".wine"));
+        prefixes.push(home.join("Games/league-of-legends"));
+    }
+    prefixes
+}
+
+/// Maps a Windows path like `C:/Riot Games/League of Legends` to the directory in the Wine prefix
+#[cfg(target_os = "linux")]
+fn path_in_wine_prefix(prefix: &Path, windows_path: &str) -> Option<PathBuf> {
+    let (drive, path) = windows_path.split_once(':')?;
+    let path = path.replace('\\', "/");
+    // every drive is linked in dosdevices, e.g. `c:` -> `../drive_c`
+    Some(
+        prefix
+            .join("dosdevices")
+            .join(format!("{}:", drive.to_lowercase()))
+            .join(path.trim_start_matches('/')),
+    )
+}
+
+/// Reads `product_install_full_path: "C:/Riot Games/League of Legends"` from the product settings
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn recorded_install_path(product_settings: &Path) -> Option<String> {
+    let settings = fs::read_to_string(product_settings).ok()?;
+    settings.lines().find_map(|line| {
+        let value = line.trim().strip_prefix("product_install_full_path:")?;
+        Some(value.trim().trim_matches('"').to_string())
+    })
+}
+
+/// Watches lockfiles and yields a [LockfileEvent] when one gets created, rewritten or removed \
+/// The paths are checked in order on every poll, the first one that exists is used \
+/// Polling is used instead of file system notifications on purpose: the install directories might not exist
+/// before the first start of the client, and notifications are unreliable for Wine prefixes and network drives
+pub struct LockfileWatcher {
+    watch_task_handle: JoinHandle<()>,
+    events_rx: UnboundedReceiver<LockfileEvent>,
+}
+
+impl LockfileWatcher {
+    /// Create a new [LockfileWatcher] for the lockfiles, e.g. [DiscoveryOptions::league_lockfiles] \
+    /// Takes an [Option] that specifies how often the lockfiles are checked \
+    /// The default [Duration] is 1s
+    pub fn new(paths: Vec<PathBuf>, polling_rate: Option<Duration>) -> Self {
+        let (events_tx, events_rx) = unbounded_channel();
+        let polling_rate =
+            polling_rate.unwrap_or(Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS));
+
+        let watch_task_handle = tokio::spawn(async move {
+            let mut timer = tokio::time::interval(polling_rate);
+            timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut current: Option<Lockfile> = None;
+
+            loop {
+                timer.tick().await;
+                let lockfile = tokio::task::spawn_blocking({
+                    let paths = paths.clone();
+                    move || find_lockfile(&paths)
+                })
+                .await;
+                let lockfile = match lockfile {
+                    Ok(Ok(lockfile)) => Some(lockfile),
+                    Ok(Err(LockfileError::NotFound)) => None,
+                    // e.g. a lockfile that is being written, checked again on the next poll
+                    _ => continue,
+                };
+
+                let event = match (&current, &lockfile) {
+                    (None, Some(lockfile)) => LockfileEvent::Created(lockfile.clone()),
+                    (Some(current), Some(lockfile)) if current != lockfile => {
+                        LockfileEvent::Changed(lockfile.clone())
+                    }
+                    (Some(_), None) => LockfileEvent::Removed,
+                    _ => continue,
+                };
+                current = lockfile;
+                if events_tx.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            watch_task_handle,
+            events_rx,
+        }
+    }
+}
+
+impl Stream for LockfileWatcher {
+    type Item = LockfileEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for LockfileWatcher {
+    fn drop(&mut self) {
+        self.watch_task_handle.abort()
+    }
+}
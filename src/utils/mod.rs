@@ -1,2 +1,5 @@
+#[cfg(any(feature = "mock-server", feature = "metrics", feature = "overlay"))]
+pub(crate) mod http;
+pub mod lockfile;
 pub mod process_info;
 pub(crate) mod request;
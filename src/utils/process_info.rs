@@ -1,9 +1,18 @@
-use std::ops::Deref;
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
 
 use base64::{engine::general_purpose, Engine};
-use sysinfo::{Process, ProcessExt, System, SystemExt};
+use futures_util::Stream;
+use sysinfo::{PidExt, ProcessExt, ProcessRefreshKind, RefreshKind, System, SystemExt};
+use tokio::{
+    sync::mpsc::{unbounded_channel, UnboundedReceiver},
+    task::JoinHandle,
+};
 
-use crate::{error::ProcessInfoError, rest::LCUClientInfo};
+use crate::{error::ProcessInfoError, model::process::LcuProcessEvent, rest::LCUClientInfo};
 
 #[cfg(target_os = "windows")]
 const TARGET_PROCESS: &str = "LeagueClientUx.exe";
@@ -20,16 +29,12 @@ const REMOTING_PORT_ARG: &str = "--app-port=";
 
 const REMOTING_TOKEN_ARG: &str = "--remoting-auth-token=";
 
-pub fn get_league_process_args() -> Option<String> {
-    let mut sys = System::new_all();
-    sys.refresh_processes();
+const DEFAULT_POLLING_RATE_MILLIS: u64 = 1000;
 
-    let process = sys
-        .processes()
-        .iter()
-        .find(|p| p.1.name().contains(TARGET_PROCESS));
-    
-    process.map(|p| p.1.cmd().join(" "))
+pub fn get_league_process_args() -> Option<String> {
+    let mut sys = process_system();
+    sys.refresh_processes_specifics(ProcessRefreshKind::new());
+    find_league_process_args(&sys)
 }
 
 pub fn get_auth_info(args: String) -> Result<LCUClientInfo, ProcessInfoError> {
@@ -39,9 +44,13 @@ pub fn get_auth_info(args: String) -> Result<LCUClientInfo, ProcessInfoError> {
     let remoting_token = get_arg(REMOTING_TOKEN_ARG, &args)?;
 
     Ok(LCUClientInfo {
-        port: port.parse::<u16>().unwrap(),
+        port: port
+            .parse::<u16>()
+            .map_err(|_| ProcessInfoError::PortNotFound)?,
         token: general_purpose::STANDARD.encode(format!("riot:{}", auth_token)),
-        remoting_port: remoting_port.parse::<u16>().unwrap(),
+        remoting_port: remoting_port
+            .parse::<u16>()
+            .map_err(|_| ProcessInfoError::PortNotFound)?,
         remoting_token: general_purpose::STANDARD.encode(format!("riot:{}", remoting_token)),
     })
 }
@@ -58,3 +67,107 @@ fn get_arg(arg: &str, args: &str) -> Result<String, ProcessInfoError> {
         .ok_or(ProcessInfoError::ProcessNotAvailable)
         .map(|s| s.replace(arg, ""))
 }
+
+/// A [System] that only tracks processes, without CPU, memory, disks and networks
+fn process_system() -> System {
+    System::new_with_specifics(RefreshKind::new().with_processes(ProcessRefreshKind::new()))
+}
+
+/// If multiple clients are running, e.g. for a moment during a restart, the newest one is used
+fn find_league_process_args(sys: &System) -> Option<String> {
+    league_processes_args(sys).into_iter().next()
+}
+
+/// The arguments of every running client, the newest first
+fn league_processes_args(sys: &System) -> Vec<String> {
+    let mut processes: Vec<_> = sys
+        .processes()
+        .values()
+        .filter(|p| p.name().contains(TARGET_PROCESS))
+        .collect();
+    processes.sort_by_key(|p| std::cmp::Reverse((p.start_time(), p.pid().as_u32())));
+    processes.iter().map(|p| p.cmd().join(" ")).collect()
+}
+
+/// Watches the processes for the League client and yields a [LcuProcessEvent] when it starts, restarts or exits \
+/// Only the process list gets refreshed, which is a lot cheaper than a full [get_lcu_client_info]
+pub struct ProcessWatcher {
+    watch_task_handle: JoinHandle<()>,
+    events_rx: UnboundedReceiver<LcuProcessEvent>,
+}
+
+impl ProcessWatcher {
+    /// Create a new [ProcessWatcher] \
+    /// Takes an [Option] that specifies how often the processes are refreshed \
+    /// The default [Duration] is 1s
+    pub fn new(polling_rate: Option<Duration>) -> Self {
+        let (events_tx, events_rx) = unbounded_channel();
+        let polling_rate =
+            polling_rate.unwrap_or(Duration::from_millis(DEFAULT_POLLING_RATE_MILLIS));
+
+        let watch_task_handle = tokio::spawn(async move {
+            let mut timer = tokio::time::interval(polling_rate);
+            timer.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            let mut sys = Some(process_system());
+            let mut current: Option<LCUClientInfo> = None;
+
+            loop {
+                timer.tick().await;
+                // refreshing reads the whole process list, which blocks for a while
+                let Ok((refreshed, running, client_info)) = tokio::task::spawn_blocking({
+                    let mut sys = sys.take().unwrap_or_else(process_system);
+                    move || {
+                        sys.refresh_processes_specifics(ProcessRefreshKind::new());
+                        let processes_args = league_processes_args(&sys);
+                        // a client that is still starting up might not have all arguments yet
+                        let client_info = processes_args
+                            .iter()
+                            .find_map(|args| get_auth_info(args.clone()).ok());
+                        (sys, !processes_args.is_empty(), client_info)
+                    }
+                })
+                .await
+                else {
+                    continue;
+                };
+                // the command line of a process is only read once, start over to see the arguments of a starting client
+                if client_info.is_some() || !running {
+                    sys = Some(refreshed);
+                }
+
+                let event = match (&current, &client_info) {
+                    (None, Some(info)) => LcuProcessEvent::Started(info.clone()),
+                    (Some(current), Some(info)) if current != info => {
+                        LcuProcessEvent::Restarted(info.clone())
+                    }
+                    // a new client that is still starting up is reported once it's ready
+                    (Some(_), None) if !running => LcuProcessEvent::Exited,
+                    _ => continue,
+                };
+                current = client_info;
+                if events_tx.send(event).is_err() {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            watch_task_handle,
+            events_rx,
+        }
+    }
+}
+
+impl Stream for ProcessWatcher {
+    type Item = LcuProcessEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events_rx.poll_recv(cx)
+    }
+}
+
+impl Drop for ProcessWatcher {
+    fn drop(&mut self) {
+        self.watch_task_handle.abort()
+    }
+}
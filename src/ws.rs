@@ -1,10 +1,19 @@
 use std::{
+    collections::HashMap,
     pin::Pin,
     task::{Context, Poll},
+    time::Duration,
 };
 
 use futures_util::{SinkExt, Stream, StreamExt};
-use tokio::net::TcpStream;
+use serde_json::Value;
+use tokio::{
+    net::TcpStream,
+    sync::{
+        mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+        oneshot,
+    },
+};
 use tokio_tungstenite::{
     tungstenite, tungstenite::client::IntoClientRequest, tungstenite::http::HeaderValue,
     tungstenite::Message, Connector, MaybeTlsStream, WebSocketStream,
@@ -23,35 +32,45 @@ impl LcuWebsocketClient {
     /// Tries to establish a connection to the LCU Websocket API \
     /// Returns an [LcuWebsocketError] if the API is not reachable
     pub async fn connect() -> Result<Self, LcuWebsocketError> {
-        let info = process_info::get_lcu_client_info()
-            .map_err(|e| LcuWebsocketError::LcuNotAvailable(e.to_string()))?;
+        Self::builder().connect().await
+    }
 
-        Self::connect_with_info(info).await
+    /// Tries to establish a connection to the LCU Websocket API with the given credentials \
+    /// Returns an [LcuWebsocketError] if the API is not reachable
+    pub async fn connect_with_info(info: LCUClientInfo) -> Result<Self, LcuWebsocketError> {
+        Self::builder().client_info(info).connect().await
     }
 
-    async fn connect_with_info(info: LCUClientInfo) -> Result<Self, LcuWebsocketError> {
-        let cert = native_tls::Certificate::from_pem(include_bytes!("./riotgames.pem")).unwrap();
-        let tls = native_tls::TlsConnector::builder()
-            .add_root_certificate(cert)
-            .build()
-            .unwrap();
-        let connector = Connector::NativeTls(tls);
+    /// Splits the client into a cloneable [LcuWebsocketHandle] to send commands and a [LcuEventStream] to read events \
+    /// The socket is owned by a background task that also sends a ping every `keepalive_interval` \
+    /// The default [Duration] is 30s \
+    /// The connection stays open as long as the [LcuEventStream] or any [LcuWebsocketHandle] exists,
+    /// events that arrive after the stream got dropped are discarded
+    pub fn split(
+        self,
+        keepalive_interval: Option<Duration>,
+    ) -> (LcuWebsocketHandle, LcuEventStream) {
+        let (commands_tx, commands_rx) = unbounded_channel();
+        let (events_tx, events_rx) = unbounded_channel();
+        let keepalive_interval =
+            keepalive_interval.unwrap_or(Duration::from_secs(DEFAULT_KEEPALIVE_INTERVAL_SECS));
 
-        let mut url = format!("wss://127.0.0.1:{}", info.remoting_port)
-            .into_client_request()
-            .map_err(|_| LcuWebsocketError::AuthError)?;
-        url.headers_mut().insert(
-            "Authorization",
-            HeaderValue::from_str(format!("Basic {}", info.remoting_token).as_str())
-                .map_err(|_| LcuWebsocketError::AuthError)?,
-        );
+        tokio::spawn(socket_task(
+            self.0,
+            commands_rx,
+            events_tx,
+            keepalive_interval,
+        ));
 
-        let (ws_stream, _response) =
-            tokio_tungstenite::connect_async_tls_with_config(url, None, Some(connector))
-                .await
-                .map_err(|e| LcuWebsocketError::Disconnected(e.to_string()))?;
+        (
+            LcuWebsocketHandle { commands_tx },
+            LcuEventStream { events_rx },
+        )
+    }
 
-        Ok(Self(ws_stream))
+    /// Create a [LcuWebsocketClientBuilder] to configure the connection
+    pub fn builder() -> LcuWebsocketClientBuilder {
+        LcuWebsocketClientBuilder::default()
     }
 
     /// The Websocket events to subscribe to.
@@ -65,12 +84,7 @@ impl LcuWebsocketClient {
         self.0
             .send(Message::text(format!("[5, \"{subscription}\"]")))
             .await
-            .map_err(|e| match e {
-                tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
-                    LcuWebsocketError::Disconnected(e.to_string())
-                }
-                _ => LcuWebsocketError::SendError,
-            })
+            .map_err(map_send_error)
     }
 
     /// The Websocket events to subscribe to.
@@ -84,12 +98,200 @@ impl LcuWebsocketClient {
         self.0
             .send(Message::text(format!("[6, \"{subscription}\"]")))
             .await
-            .map_err(|e| match e {
-                tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
-                    LcuWebsocketError::Disconnected(e.to_string())
-                }
-                _ => LcuWebsocketError::SendError,
-            })
+            .map_err(map_send_error)
+    }
+}
+
+/// A builder to configure the connection of a [LcuWebsocketClient] \
+/// By default the credentials of the running League client get used
+#[derive(Clone)]
+pub struct LcuWebsocketClientBuilder {
+    host: String,
+    client_info: Option<LCUClientInfo>,
+    port: Option<u16>,
+    token: Option<String>,
+    riot_client: bool,
+    secure: bool,
+    use_bundled_certificate: bool,
+    root_certificates: Vec<native_tls::Certificate>,
+    accept_invalid_certs: bool,
+    accept_invalid_hostnames: bool,
+    connect_timeout: Option<Duration>,
+}
+
+impl Default for LcuWebsocketClientBuilder {
+    fn default() -> Self {
+        Self {
+            host: "127.0.0.1".to_string(),
+            client_info: None,
+            port: None,
+            token: None,
+            riot_client: false,
+            secure: true,
+            use_bundled_certificate: true,
+            root_certificates: Vec::new(),
+            accept_invalid_certs: false,
+            accept_invalid_hostnames: false,
+            connect_timeout: None,
+        }
+    }
+}
+
+impl LcuWebsocketClientBuilder {
+    /// The host to connect to \
+    /// The default is `127.0.0.1`
+    pub fn host<S: Into<String>>(mut self, host: S) -> Self {
+        self.host = host.into();
+        self
+    }
+
+    /// Use the given credentials instead of reading them from the running League client
+    pub fn client_info(mut self, client_info: LCUClientInfo) -> Self {
+        self.client_info = Some(client_info);
+        self
+    }
+
+    /// Connect to the given port, takes precedence over the port of the [LCUClientInfo]
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = Some(port);
+        self
+    }
+
+    /// Authenticate with the given token, takes precedence over the token of the [LCUClientInfo] \
+    /// The token has to be encoded the same way as in [LCUClientInfo]: base64 of `riot:{password}`
+    pub fn token<S: Into<String>>(mut self, token: S) -> Self {
+        self.token = Some(token.into());
+        self
+    }
+
+    /// Connect to the Riot Client ([LCUClientInfo::port]) instead of the League client ([LCUClientInfo::remoting_port])
+    pub fn riot_client(mut self, riot_client: bool) -> Self {
+        self.riot_client = riot_client;
+        self
+    }
+
+    /// Use `wss://` (default) or plain `ws://` e.g. for a local mock server
+    pub fn secure(mut self, secure: bool) -> Self {
+        self.secure = secure;
+        self
+    }
+
+    /// Trust the Riot Games root certificate that is bundled with the library (default)
+    pub fn use_bundled_certificate(mut self, use_bundled_certificate: bool) -> Self {
+        self.use_bundled_certificate = use_bundled_certificate;
+        self
+    }
+
+    /// Trust an additional root certificate
+    pub fn add_root_certificate(mut self, certificate: native_tls::Certificate) -> Self {
+        self.root_certificates.push(certificate);
+        self
+    }
+
+    /// Disable the certificate verification \
+    /// **Only use this for local test servers**
+    pub fn danger_accept_invalid_certs(mut self, accept_invalid_certs: bool) -> Self {
+        self.accept_invalid_certs = accept_invalid_certs;
+        self
+    }
+
+    /// Disable the hostname verification, needed when connecting to a forwarded client under a different host
+    pub fn danger_accept_invalid_hostnames(mut self, accept_invalid_hostnames: bool) -> Self {
+        self.accept_invalid_hostnames = accept_invalid_hostnames;
+        self
+    }
+
+    /// Fail the connection attempt if it takes longer than the given [Duration]
+    pub fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    /// Tries to establish a connection to the configured Websocket API \
+    /// Returns an [LcuWebsocketError] if the API is not reachable
+    pub async fn connect(self) -> Result<LcuWebsocketClient, LcuWebsocketError> {
+        let (port, token) = self.credentials()?;
+
+        let scheme = if self.secure { "wss" } else { "ws" };
+        let mut url = format!("{}://{}:{}", scheme, self.host, port)
+            .into_client_request()
+            .map_err(|_| LcuWebsocketError::AuthError)?;
+        if let Some(token) = token {
+            url.headers_mut().insert(
+                "Authorization",
+                HeaderValue::from_str(format!("Basic {}", token).as_str())
+                    .map_err(|_| LcuWebsocketError::AuthError)?,
+            );
+        }
+
+        let connector = if self.secure {
+            Some(self.tls_connector()?)
+        } else {
+            None
+        };
+
+        let connect = tokio_tungstenite::connect_async_tls_with_config(url, None, connector);
+        let connected = match self.connect_timeout {
+            Some(connect_timeout) => tokio::time::timeout(connect_timeout, connect)
+                .await
+                .map_err(|_| {
+                    LcuWebsocketError::LcuNotAvailable("connection timed out".to_string())
+                })?,
+            None => connect.await,
+        };
+        let (ws_stream, _response) =
+            connected.map_err(|e| LcuWebsocketError::Disconnected(e.to_string()))?;
+
+        Ok(LcuWebsocketClient(ws_stream))
+    }
+
+    fn credentials(&self) -> Result<(u16, Option<String>), LcuWebsocketError> {
+        // only read the process information if it is needed
+        let client_info = match (&self.client_info, self.port, &self.token) {
+            (Some(client_info), _, _) => Some(client_info.clone()),
+            (None, Some(_), Some(_)) => None,
+            (None, _, _) => Some(
+                process_info::get_lcu_client_info()
+                    .map_err(|e| LcuWebsocketError::LcuNotAvailable(e.to_string()))?,
+            ),
+        };
+
+        let port = self.port.or(client_info.as_ref().map(|info| {
+            if self.riot_client {
+                info.port
+            } else {
+                info.remoting_port
+            }
+        }));
+        let token = self.token.clone().or(client_info.map(|info| {
+            if self.riot_client {
+                info.token
+            } else {
+                info.remoting_token
+            }
+        }));
+
+        port.map(|port| (port, token))
+            .ok_or_else(|| LcuWebsocketError::LcuNotAvailable("no port specified".to_string()))
+    }
+
+    fn tls_connector(&self) -> Result<Connector, LcuWebsocketError> {
+        let mut builder = native_tls::TlsConnector::builder();
+        if self.use_bundled_certificate {
+            let cert = native_tls::Certificate::from_pem(include_bytes!("./riotgames.pem"))
+                .map_err(|e| LcuWebsocketError::LcuNotAvailable(e.to_string()))?;
+            builder.add_root_certificate(cert);
+        }
+        for cert in &self.root_certificates {
+            builder.add_root_certificate(cert.clone());
+        }
+        let tls = builder
+            .danger_accept_invalid_certs(self.accept_invalid_certs)
+            .danger_accept_invalid_hostnames(self.accept_invalid_hostnames)
+            .build()
+            .map_err(|e| LcuWebsocketError::LcuNotAvailable(e.to_string()))?;
+
+        Ok(Connector::NativeTls(tls))
     }
 }
 
@@ -101,7 +303,7 @@ impl Stream for LcuWebsocketClient {
             return match self.0.poll_next_unpin(cx) {
                 Poll::Pending => Poll::Pending,
                 Poll::Ready(Some(Ok(Message::Text(text)))) => {
-                    let Ok(event) = serde_json::from_str::<LcuEvent>(&text) else {
+                    let Ok(event) = LcuEvent::from_message(&text) else {
                         continue;
                     };
                     Poll::Ready(Some(event))
@@ -112,3 +314,239 @@ impl Stream for LcuWebsocketClient {
         }
     }
 }
+
+fn map_send_error(e: tungstenite::Error) -> LcuWebsocketError {
+    match e {
+        tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed => {
+            LcuWebsocketError::Disconnected(e.to_string())
+        }
+        _ => LcuWebsocketError::SendError,
+    }
+}
+
+const DEFAULT_KEEPALIVE_INTERVAL_SECS: u64 = 30;
+
+enum Command {
+    Send(Message, oneshot::Sender<Result<(), LcuWebsocketError>>),
+    Call(
+        String,
+        Vec<Value>,
+        oneshot::Sender<Result<Value, LcuWebsocketError>>,
+    ),
+    Close(oneshot::Sender<Result<(), LcuWebsocketError>>),
+}
+
+/// The sending half of a split [LcuWebsocketClient] \
+/// Can be cloned and used from multiple tasks while the [LcuEventStream] gets read
+#[derive(Clone)]
+pub struct LcuWebsocketHandle {
+    commands_tx: UnboundedSender<Command>,
+}
+
+impl LcuWebsocketHandle {
+    /// The Websocket events to subscribe to.
+    /// Look at the in-official documentation for event strings to subscribe to.
+    ///
+    /// <https://www.mingweisamuel.com/lcu-schema/tool/#/>
+    pub async fn subscribe(
+        &self,
+        subscription: LcuSubscriptionType,
+    ) -> Result<(), LcuWebsocketError> {
+        self.send(Message::text(format!("[5, \"{subscription}\"]")))
+            .await
+    }
+
+    /// The Websocket events to unsubscribe from.
+    pub async fn unsubscribe(
+        &self,
+        subscription: LcuSubscriptionType,
+    ) -> Result<(), LcuWebsocketError> {
+        self.send(Message::text(format!("[6, \"{subscription}\"]")))
+            .await
+    }
+
+    /// Makes a remote procedure call and waits for its result \
+    /// Returns [LcuWebsocketError::CallError] if the LCU answers with an error
+    pub async fn call<S: Into<String>>(
+        &self,
+        procedure: S,
+        args: Vec<Value>,
+    ) -> Result<Value, LcuWebsocketError> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.commands_tx
+            .send(Command::Call(procedure.into(), args, response_tx))
+            .map_err(|_| disconnected())?;
+        response_rx.await.map_err(|_| disconnected())?
+    }
+
+    /// Closes the connection, the [LcuEventStream] ends afterwards
+    pub async fn close(&self) -> Result<(), LcuWebsocketError> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.commands_tx
+            .send(Command::Close(response_tx))
+            .map_err(|_| disconnected())?;
+        response_rx.await.map_err(|_| disconnected())?
+    }
+
+    /// Checks if the background task still owns an open connection
+    pub fn is_connected(&self) -> bool {
+        !self.commands_tx.is_closed()
+    }
+
+    async fn send(&self, message: Message) -> Result<(), LcuWebsocketError> {
+        let (response_tx, response_rx) = oneshot::channel();
+        self.commands_tx
+            .send(Command::Send(message, response_tx))
+            .map_err(|_| disconnected())?;
+        response_rx.await.map_err(|_| disconnected())?
+    }
+}
+
+/// The receiving half of a split [LcuWebsocketClient] \
+/// Ends when the connection gets closed
+pub struct LcuEventStream {
+    events_rx: UnboundedReceiver<LcuEvent>,
+}
+
+impl Stream for LcuEventStream {
+    type Item = LcuEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.events_rx.poll_recv(cx)
+    }
+}
+
+fn disconnected() -> LcuWebsocketError {
+    LcuWebsocketError::Disconnected("websocket task stopped".to_string())
+}
+
+/// Owns the socket of a split [LcuWebsocketClient] and runs until the connection gets closed
+/// or the [LcuEventStream] and all [LcuWebsocketHandle]s got dropped
+async fn socket_task(
+    mut socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    mut commands_rx: UnboundedReceiver<Command>,
+    events_tx: UnboundedSender<LcuEvent>,
+    keepalive_interval: Duration,
+) {
+    let mut keepalive = tokio::time::interval(keepalive_interval);
+    // the first tick completes immediately
+    keepalive.tick().await;
+    let mut awaiting_pong = false;
+    let mut commands_closed = false;
+    let mut events_closed = false;
+    let mut call_id: u64 = 0;
+    let mut pending_calls: HashMap<String, oneshot::Sender<Result<Value, LcuWebsocketError>>> =
+        HashMap::new();
+
+    loop {
+        tokio::select! {
+            command = commands_rx.recv(), if !commands_closed => {
+                let Some(command) = command else {
+                    // all handles got dropped, nothing can be sent anymore but events can still be read
+                    commands_closed = true;
+                    if events_tx.is_closed() {
+                        let _ = socket.close(None).await;
+                        break;
+                    }
+                    continue;
+                };
+                match command {
+                    Command::Send(message, response_tx) => {
+                        let _ = response_tx.send(socket.send(message).await.map_err(map_send_error));
+                    }
+                    Command::Call(procedure, args, response_tx) => {
+                        call_id += 1;
+                        let id = call_id.to_string();
+                        let mut message = vec![Value::from(2), Value::from(id.clone()), Value::from(procedure)];
+                        message.extend(args);
+
+                        match socket.send(Message::text(Value::Array(message).to_string())).await {
+                            Ok(_) => {
+                                pending_calls.insert(id, response_tx);
+                            }
+                            Err(e) => {
+                                let _ = response_tx.send(Err(map_send_error(e)));
+                            }
+                        }
+                    }
+                    Command::Close(response_tx) => {
+                        let _ = response_tx.send(socket.close(None).await.map_err(map_send_error));
+                        break;
+                    }
+                }
+            }
+            message = socket.next() => {
+                awaiting_pong = false;
+                match message {
+                    Some(Ok(Message::Text(text))) => {
+                        handle_text_message(&text, &events_tx, &mut pending_calls);
+                    }
+                    Some(Ok(Message::Close(_)) | Err(_)) | None => break,
+                    _ => {}
+                }
+            }
+            _ = events_tx.closed(), if !events_closed => {
+                // the event stream got dropped, the handles can still be used
+                events_closed = true;
+                if commands_closed {
+                    let _ = socket.close(None).await;
+                    break;
+                }
+            }
+            _ = keepalive.tick() => {
+                if awaiting_pong {
+                    // the last ping didn't get answered => the connection is dead
+                    break;
+                }
+                if socket.send(Message::Ping(Vec::new())).await.is_err() {
+                    break;
+                }
+                awaiting_pong = true;
+            }
+        }
+    }
+
+    pending_calls.drain().for_each(|(_, response_tx)| {
+        let _ = response_tx.send(Err(disconnected()));
+    });
+}
+
+/// Dispatches call results to the waiting callers and everything else to the event stream
+fn handle_text_message(
+    text: &str,
+    events_tx: &UnboundedSender<LcuEvent>,
+    pending_calls: &mut HashMap<String, oneshot::Sender<Result<Value, LcuWebsocketError>>>,
+) {
+    let Ok(Value::Array(message)) = serde_json::from_str::<Value>(text) else {
+        return;
+    };
+
+    match message.first().and_then(Value::as_i64) {
+        // [3, callId, result]
+        Some(3) => {
+            if let Some(response_tx) = call_id(&message).and_then(|id| pending_calls.remove(&id)) {
+                let _ = response_tx.send(Ok(message.get(2).cloned().unwrap_or(Value::Null)));
+            }
+        }
+        // [4, callId, errorUri, errorDescription, ...]
+        Some(4) => {
+            if let Some(response_tx) = call_id(&message).and_then(|id| pending_calls.remove(&id)) {
+                let error = message[2..]
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_string).unwrap_or(v.to_string()))
+                    .collect::<Vec<_>>()
+                    .join(": ");
+                let _ = response_tx.send(Err(LcuWebsocketError::CallError(error)));
+            }
+        }
+        _ => {
+            if let Ok(event) = LcuEvent::from_message(text) {
+                let _ = events_tx.send(event);
+            }
+        }
+    }
+}
+
+fn call_id(message: &[Value]) -> Option<String> {
+    message.get(1).and_then(Value::as_str).map(str::to_string)
+}
@@ -0,0 +1,225 @@
+use std::{
+    collections::VecDeque,
+    fs::File,
+    future::Future,
+    io,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use futures_util::{Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{
+    value::{to_raw_value, RawValue},
+    Value,
+};
+use tokio::time::Sleep;
+
+use crate::model::ws::LcuEvent;
+
+/// A single line of a recorded websocket session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedLcuEvent {
+    /// Milliseconds since the start of the recording
+    pub timestamp: u64,
+    /// The websocket message as it was received: `[opcode, topic, payload]`
+    pub message: Box<RawValue>,
+}
+
+impl RecordedLcuEvent {
+    /// Converts the recorded line back to the [LcuEvent] the websocket returned
+    pub fn to_lcu_event(&self) -> Result<LcuEvent, serde_json::Error> {
+        LcuEvent::from_message(self.message.get())
+    }
+}
+
+/// A wrapper around a [Stream] of [LcuEvent]s (e.g. a [LcuWebsocketClient](crate::ws::LcuWebsocketClient))
+/// that writes every event to a line-delimited JSON file while passing it through unchanged
+pub struct LcuEventRecorder<S, W: Write = BufWriter<File>> {
+    inner: S,
+    writer: W,
+    started: Instant,
+    error: Option<io::Error>,
+}
+
+impl<S> LcuEventRecorder<S> {
+    /// Create a recorder that writes to a new file at `path`, truncating an existing one
+    pub fn to_file<P: AsRef<Path>>(inner: S, path: P) -> io::Result<Self> {
+        Ok(Self::new(inner, BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<S, W: Write> LcuEventRecorder<S, W> {
+    /// Create a recorder that writes to the given writer
+    pub fn new(inner: S, writer: W) -> Self {
+        Self {
+            inner,
+            writer,
+            started: Instant::now(),
+            error: None,
+        }
+    }
+
+    /// The wrapped stream, e.g. to subscribe to more events
+    pub fn get_mut(&mut self) -> &mut S {
+        &mut self.inner
+    }
+
+    /// Returns the first error that occurred while writing \
+    /// Events are still passed through after an error but no longer recorded
+    pub fn error(&self) -> Option<&io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Flushes the writer and returns the wrapped stream and writer
+    pub fn into_inner(mut self) -> io::Result<(S, W)> {
+        self.writer.flush()?;
+        Ok((self.inner, self.writer))
+    }
+
+    fn record(&mut self, event: &LcuEvent) -> io::Result<()> {
+        let message = match event.raw_message() {
+            Some(message) if !message.contains('\n') => RawValue::from_string(message.to_string())?,
+            // the recording is line-delimited, so multi-line messages get minified
+            Some(message) => to_raw_value(&serde_json::from_str::<Value>(message)?)?,
+            // events that were not read from a websocket only have their modelled fields
+            None => to_raw_value(&serde_json::json!([
+                8,
+                event.subscription_type,
+                event.payload
+            ]))?,
+        };
+        let recorded_event = RecordedLcuEvent {
+            timestamp: self.started.elapsed().as_millis() as u64,
+            message,
+        };
+        serde_json::to_writer(&mut self.writer, &recorded_event)?;
+        self.writer.write_all(b"\n")?;
+        // flush after every event so the recording is complete even if the process gets killed
+        self.writer.flush()
+    }
+}
+
+impl<S: Stream<Item = LcuEvent> + Unpin, W: Write + Unpin> Stream for LcuEventRecorder<S, W> {
+    type Item = LcuEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let event = match self.inner.poll_next_unpin(cx) {
+            Poll::Ready(Some(event)) => event,
+            poll => return poll,
+        };
+
+        if self.error.is_none() {
+            if let Err(e) = self.record(&event) {
+                self.error = Some(e);
+            }
+        }
+        Poll::Ready(Some(event))
+    }
+}
+
+/// How fast a recording gets replayed
+#[derive(Debug, Clone, Copy)]
+pub enum ReplaySpeed {
+    /// Keep the original time between events
+    RealTime,
+    /// Divide the time between events by the given factor, which has to be finite and greater than 0
+    Accelerated(f64),
+    /// Return all events without waiting
+    Instant,
+}
+
+/// Replays a session recorded by a [LcuEventRecorder] as a [Stream] of [LcuEvent]s
+pub struct LcuEventReplayer {
+    events: VecDeque<LcuEvent>,
+    timestamps: VecDeque<u64>,
+    speed: ReplaySpeed,
+    started: Option<tokio::time::Instant>,
+    delay: Option<Pin<Box<Sleep>>>,
+}
+
+impl LcuEventReplayer {
+    /// Read a recording from a file \
+    /// See [LcuEventReplayer::from_reader]
+    pub fn from_file<P: AsRef<Path>>(path: P, speed: ReplaySpeed) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?), speed)
+    }
+
+    /// Read a recording from any reader \
+    /// The whole recording is parsed upfront, so an invalid line fails here and not while replaying \
+    /// Fails with [io::ErrorKind::InvalidInput] if the factor of [ReplaySpeed::Accelerated] is not finite or not greater than 0
+    pub fn from_reader<R: BufRead>(reader: R, speed: ReplaySpeed) -> io::Result<Self> {
+        if let ReplaySpeed::Accelerated(factor) = speed {
+            if !factor.is_finite() || factor <= 0.0 {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("invalid replay speed factor: {}", factor),
+                ));
+            }
+        }
+
+        let mut events = VecDeque::new();
+        let mut timestamps = VecDeque::new();
+
+        for line in reader.lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let recorded_event: RecordedLcuEvent = serde_json::from_str(&line)?;
+            events.push_back(recorded_event.to_lcu_event()?);
+            timestamps.push_back(recorded_event.timestamp);
+        }
+
+        Ok(Self {
+            events,
+            timestamps,
+            speed,
+            started: None,
+            delay: None,
+        })
+    }
+
+    /// The number of events that have not been replayed yet
+    pub fn remaining(&self) -> usize {
+        self.events.len()
+    }
+
+    fn offset(&self, timestamp: u64) -> Option<Duration> {
+        let millis = match self.speed {
+            ReplaySpeed::RealTime => timestamp as f64,
+            ReplaySpeed::Accelerated(factor) => timestamp as f64 / factor,
+            ReplaySpeed::Instant => return None,
+        };
+        Some(Duration::from_secs_f64(millis.max(0.0) / 1000.0))
+    }
+}
+
+impl Stream for LcuEventReplayer {
+    type Item = LcuEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let Some(&timestamp) = self.timestamps.front() else {
+            return Poll::Ready(None);
+        };
+
+        if let Some(offset) = self.offset(timestamp) {
+            let started = *self.started.get_or_insert_with(tokio::time::Instant::now);
+            let deadline = started + offset;
+
+            let delay = self
+                .delay
+                .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+            delay.as_mut().reset(deadline);
+            if delay.as_mut().poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+        }
+
+        self.timestamps.pop_front();
+        Poll::Ready(self.events.pop_front())
+    }
+}
@@ -0,0 +1,173 @@
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use shaco::{
+    cache::{LcuStateCache, GAMEFLOW_PHASE, LOBBY},
+    error::LcuWebsocketError,
+    ws::LcuWebsocketClient,
+};
+use tokio::{net::TcpListener, sync::oneshot};
+use tokio_tungstenite::tungstenite::Message;
+
+fn json_api_event(uri: &str, event_type: &str, data: Value) -> Message {
+    let topic = format!("OnJsonApiEvent{}", uri.replace('/', "_"));
+    Message::text(
+        json!([8, topic, {"data": data, "eventType": event_type, "uri": uri}]).to_string(),
+    )
+}
+
+async fn connect(port: u16) -> LcuWebsocketClient {
+    LcuWebsocketClient::builder()
+        .secure(false)
+        .port(port)
+        .token("cmlvdDp0ZXN0")
+        .connect_timeout(Duration::from_secs(5))
+        .connect()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn events_during_the_seed_are_applied_after_it() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (update_sent_tx, update_sent_rx) = oneshot::channel();
+    let (seeded_tx, seeded_rx) = oneshot::channel::<()>();
+    let (changed_tx, changed_rx) = oneshot::channel::<()>();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        ws.next().await.unwrap().unwrap();
+
+        // published while the seed is requested, after the response of the seed was built
+        ws.send(json_api_event(
+            GAMEFLOW_PHASE,
+            "Update",
+            json!("ReadyCheck"),
+        ))
+        .await
+        .unwrap();
+        update_sent_tx.send(()).unwrap();
+
+        seeded_rx.await.unwrap();
+        ws.send(json_api_event(
+            GAMEFLOW_PHASE,
+            "Update",
+            json!("ChampSelect"),
+        ))
+        .await
+        .unwrap();
+        changed_rx.await.unwrap();
+        ws.send(json_api_event(GAMEFLOW_PHASE, "Delete", Value::Null))
+            .await
+            .unwrap();
+        ws
+    });
+
+    let mut update_sent_rx = Some(update_sent_rx);
+    let cache = LcuStateCache::with_seed(connect(port).await, [GAMEFLOW_PHASE], |_| {
+        let update_sent_rx = update_sent_rx.take().unwrap();
+        async move {
+            update_sent_rx.await.unwrap();
+            // give the cache time to receive the newer event
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            Ok(json!("Matchmaking"))
+        }
+    })
+    .await
+    .unwrap();
+
+    // the update that arrived mid-seed wins over the older seed
+    assert_eq!(cache.get(GAMEFLOW_PHASE), Some(json!("ReadyCheck")));
+    assert_eq!(
+        cache.get_as::<String>(GAMEFLOW_PHASE).as_deref(),
+        Some("ReadyCheck")
+    );
+
+    seeded_tx.send(()).unwrap();
+    let changed = tokio::time::timeout(Duration::from_secs(5), cache.changed(GAMEFLOW_PHASE))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(changed, Some(json!("ChampSelect")));
+    changed_tx.send(()).unwrap();
+
+    let changed = tokio::time::timeout(Duration::from_secs(5), cache.changed(GAMEFLOW_PHASE))
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(changed, None);
+    assert!(cache.is_connected());
+
+    let ws = server.await.unwrap();
+    drop(ws);
+    let result = tokio::time::timeout(Duration::from_secs(5), cache.changed(GAMEFLOW_PHASE))
+        .await
+        .unwrap();
+    assert!(matches!(result, Err(LcuWebsocketError::Disconnected(_))));
+    assert!(!cache.is_connected());
+}
+
+#[tokio::test]
+async fn error_responses_seed_missing_resources() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let subscriptions = vec![
+            ws.next().await.unwrap().unwrap().into_text().unwrap(),
+            ws.next().await.unwrap().unwrap().into_text().unwrap(),
+        ];
+        (ws, subscriptions)
+    });
+
+    let cache = LcuStateCache::with_seed(connect(port).await, [GAMEFLOW_PHASE, LOBBY], |uri| async move {
+        Ok(match uri.as_str() {
+            LOBBY => json!({"errorCode": "RPC_ERROR", "httpStatus": 404, "message": "LOBBY_NOT_FOUND"}),
+            _ => json!("None"),
+        })
+    })
+    .await
+    .unwrap();
+
+    let (_ws, subscriptions) = server.await.unwrap();
+    assert_eq!(
+        subscriptions,
+        [
+            "[5, \"OnJsonApiEvent_lol-gameflow_v1_gameflow-phase\"]",
+            "[5, \"OnJsonApiEvent_lol-lobby_v2_lobby\"]"
+        ]
+    );
+
+    assert_eq!(cache.get(GAMEFLOW_PHASE), Some(json!("None")));
+    assert_eq!(cache.get(LOBBY), None);
+    assert_eq!(cache.uris().count(), 2);
+    assert!(matches!(
+        cache.changed("/lol-summoner/v1/current-summoner").await,
+        Err(LcuWebsocketError::NotSubscribed(_))
+    ));
+}
+
+#[tokio::test]
+async fn failed_seed_is_returned() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        ws.next().await.unwrap().unwrap();
+        ws
+    });
+
+    let result = LcuStateCache::with_seed(connect(port).await, [GAMEFLOW_PHASE], |_| async {
+        Err(LcuWebsocketError::LcuNotAvailable("timed out".to_string()))
+    })
+    .await;
+    assert!(matches!(result, Err(LcuWebsocketError::LcuNotAvailable(_))));
+    server.await.unwrap();
+}
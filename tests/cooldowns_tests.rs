@@ -0,0 +1,90 @@
+use serde_json::{json, Value};
+use shaco::{
+    cooldowns::{apply_haste, CooldownTracker},
+    model::{cooldowns::CooldownSlot, ingame::AllGameData},
+};
+
+fn snapshot() -> Value {
+    serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap()
+}
+
+fn game_data(value: &Value) -> AllGameData {
+    serde_json::from_value(value.clone()).unwrap()
+}
+
+#[test]
+fn summoner_spell_cooldown() {
+    let mut tracker = CooldownTracker::default();
+    let mut data = snapshot();
+    data["gameData"]["gameTime"] = json!(600.0);
+    tracker.update(&game_data(&data));
+
+    // summonerName1 has Flash as first summoner spell
+    let cooldown = tracker
+        .report_summoner_spell("summonerName1", CooldownSlot::SummonerSpellOne, None)
+        .unwrap();
+    assert_eq!(cooldown.spell.as_deref(), Some("SummonerFlash"));
+    assert_eq!(cooldown.ready_at, 600.0 + apply_haste(300.0, 10.0));
+    let ready_at = cooldown.ready_at;
+
+    data["gameData"]["gameTime"] = json!(700.0);
+    tracker.update(&game_data(&data));
+    assert_eq!(
+        tracker.remaining("summonerName1", CooldownSlot::SummonerSpellOne),
+        Some(ready_at - 700.0)
+    );
+    assert_eq!(tracker.active_cooldowns().count(), 1);
+
+    data["gameData"]["gameTime"] = json!(901.0);
+    tracker.update(&game_data(&data));
+    assert_eq!(
+        tracker.remaining("summonerName1", CooldownSlot::SummonerSpellOne),
+        Some(0.0)
+    );
+    assert_eq!(tracker.active_cooldowns().count(), 0);
+}
+
+#[test]
+fn summoner_spell_haste_from_items() {
+    let mut tracker = CooldownTracker::default();
+    // summonerName1 owns Ionian Boots of Lucidity
+    tracker.update(&game_data(&snapshot()));
+    assert_eq!(tracker.summoner_spell_haste("summonerName1"), Some(10.0));
+
+    let cooldown = tracker
+        .report_summoner_spell("summonerName1", CooldownSlot::SummonerSpellOne, Some(0.0))
+        .unwrap();
+    assert_eq!(cooldown.ready_at, apply_haste(300.0, 10.0));
+}
+
+#[test]
+fn summoner_spell_haste_from_runes() {
+    let mut tracker = CooldownTracker::default();
+    let mut data = snapshot();
+    // give the active player Cosmic Insight
+    data["activePlayer"]["fullRunes"]["generalRunes"][4]["id"] = json!(8347);
+    tracker.update(&game_data(&data));
+    assert_eq!(tracker.summoner_spell_haste("summonerName8"), Some(18.0));
+}
+
+#[test]
+fn ultimate_cooldown() {
+    let mut tracker = CooldownTracker::default();
+    tracker.update(&game_data(&snapshot()));
+
+    // the ability haste of the active player is known
+    let cooldown = tracker
+        .report_ultimate("summonerName8", 100.0, None, Some(0.0))
+        .unwrap();
+    assert_eq!(cooldown.haste, 10.0);
+    assert_eq!(cooldown.ready_at, apply_haste(100.0, 10.0));
+
+    let cooldown = tracker
+        .report_ultimate("summonerName2", 100.0, None, Some(0.0))
+        .unwrap();
+    assert_eq!(cooldown.ready_at, 100.0);
+
+    assert!(tracker
+        .report_ultimate("unknown player", 100.0, None, None)
+        .is_none());
+}
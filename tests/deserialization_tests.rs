@@ -2,12 +2,17 @@ use serde::{Deserialize, Serialize};
 
 use shaco::{
     ingame::IngameClient,
-    model::ingame::{AllGameData, GameEvent, GameMode},
+    model::ingame::{
+        AllGameData, DragonType, GameEvent, GameMode, Inhibitor, Killer, Lane, LaneCode, MapName,
+        Subteam, TeamId, Turret, TurretKind, TurretLocation, TurretTier,
+    },
 };
 
 /// check if all api calls deserialize without errors \
-/// DOES NOT CHECK IF THE EVENTS GET DESERIALIZED CORRECTLY
+/// DOES NOT CHECK IF THE EVENTS GET DESERIALIZED CORRECTLY \
+/// run with `cargo test -- --ignored` while in a game, see `mock_server_tests.rs` for the offline version
 #[tokio::test]
+#[ignore = "requires a running game"]
 async fn ingame_livegame_api_deserialization() {
     let client = IngameClient::new().unwrap();
 
@@ -33,8 +38,10 @@ async fn ingame_livegame_api_deserialization() {
 }
 
 /// check if all api calls deserialize without errors \
-/// DOES NOT CHECK IF THE EVENTS GET DESERIALIZED CORRECTLY
+/// DOES NOT CHECK IF THE EVENTS GET DESERIALIZED CORRECTLY \
+/// run with `cargo test -- --ignored` while spectating a game, see `mock_server_tests.rs` for the offline version
 #[tokio::test]
+#[ignore = "requires a spectated game"]
 async fn ingame_spectate_api_deserialization() {
     let client = IngameClient::new().unwrap();
 
@@ -99,9 +106,305 @@ fn deserialize_game_mode() {
         "ODYSSEY",
         "NEXUSBLITZ",
         "ULTBOOK",
+        "CHERRY",
+        "STRAWBERRY",
     ];
 
     vec.iter().for_each(|game_mode| {
         serde_json::from_str::<GameMode>(&format!("\"{game_mode}\"")).unwrap();
     })
 }
+
+#[test]
+fn deserialize_unknown_event() {
+    let raw = r#"{"EventID": 42, "EventName": "SomeFutureEvent", "EventTime": 1234.5, "KillerName": "summonerName1"}"#;
+
+    let event: GameEvent = serde_json::from_str(raw).unwrap();
+    let GameEvent::Unknown {
+        name,
+        event_id,
+        event_time,
+        raw: raw_event,
+        error,
+    } = &event
+    else {
+        panic!("expected an unknown event");
+    };
+    assert_eq!(name, "SomeFutureEvent");
+    assert!(error.is_none());
+    assert_eq!(*event_id, 42);
+    assert_eq!(*event_time, 1234.5);
+    assert_eq!(raw_event["KillerName"], "summonerName1");
+    assert_eq!(event.get_event_id(), 42);
+
+    // unknown events serialize to the raw event
+    let serialized = serde_json::to_value(&event).unwrap();
+    assert_eq!(
+        serialized,
+        serde_json::from_str::<serde_json::Value>(raw).unwrap()
+    );
+}
+
+#[test]
+fn serialize_known_event() {
+    let raw = r#"{"EventID": 0, "EventName": "GameStart", "EventTime": 0.0}"#;
+
+    let event: GameEvent = serde_json::from_str(raw).unwrap();
+    assert!(matches!(event, GameEvent::GameStart(_)));
+
+    let serialized = serde_json::to_value(&event).unwrap();
+    assert_eq!(
+        serialized,
+        serde_json::from_str::<serde_json::Value>(raw).unwrap()
+    );
+}
+
+/// the test suite is built with the `strict` feature to detect changes of the API
+#[test]
+fn strict_known_event_with_unknown_field() {
+    let raw = r#"{"EventID": 0, "EventName": "GameStart", "EventTime": 0.0, "SomeNewField": 1}"#;
+
+    assert!(serde_json::from_str::<GameEvent>(raw).is_err());
+}
+
+/// hand-written events, the captured responses don't contain voidgrubs, Atakhan or feats of strength yet
+#[test]
+fn deserialize_objective_events() {
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[serde(rename_all = "PascalCase")]
+    struct IngameEvents {
+        events: Vec<GameEvent>,
+    }
+
+    // hand-written in the shape of the other captures, there is no captured game with voidgrubs,
+    // Atakhan and feats of strength yet, replace it with a capture once there is one
+    let test_data = include_str!("GetLiveclientdataEventdata3_synthetic.json");
+    let events: IngameEvents = serde_json::from_str(test_data).unwrap();
+    let events = events.events;
+    assert_eq!(events.len(), 17);
+    assert!(!events
+        .iter()
+        .any(|e| matches!(e, GameEvent::Unknown { .. })));
+
+    let GameEvent::FeatUpdate(feat_update) = &events[4] else {
+        panic!("expected a FeatUpdate");
+    };
+    assert!(matches!(feat_update.feat_team, TeamId::Order));
+
+    let horde_kills: Vec<_> = events
+        .iter()
+        .filter_map(|e| match e {
+            GameEvent::HordeKill(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(horde_kills.len(), 3);
+    assert!(!horde_kills[1].stolen);
+    assert_eq!(horde_kills[1].assisters, vec!["Garen Bot"]);
+    assert!(horde_kills[2].stolen);
+
+    let GameEvent::AtakhanKill(atakhan_kill) = &events[11] else {
+        panic!("expected an AtakhanKill");
+    };
+    assert_eq!(atakhan_kill.assisters.len(), 4);
+    assert!(matches!(&atakhan_kill.killer_name, Killer::Summoner(name) if name == "Lee Sin Bot"));
+
+    let GameEvent::DragonKill(elder_kill) = &events[13] else {
+        panic!("expected a DragonKill");
+    };
+    assert!(elder_kill.grants_elder_buff());
+    assert_eq!(
+        elder_kill.elder_buff_expiry(),
+        Some(elder_kill.event_time + 150.0)
+    );
+
+    assert!(
+        matches!(&events[14], GameEvent::ChampionKill(e) if matches!(e.killer_name, Killer::Atakhan))
+    );
+    assert!(
+        matches!(&events[15], GameEvent::ChampionKill(e) if matches!(e.killer_name, Killer::Horde))
+    );
+}
+
+#[test]
+fn game_event_round_trip() {
+    let raw = r#"[
+        {"Assisters": [], "DragonType": "Fire", "EventID": 1, "EventName": "DragonKill", "EventTime": 788.0, "KillerName": "summonerName", "Stolen": "False"},
+        {"Assisters": [], "DragonType": "Chaos", "EventID": 2, "EventName": "DragonKill", "EventTime": 790.0, "KillerName": "summonerName", "Stolen": "True"},
+        {"Assisters": [], "EventID": 3, "EventName": "ChampionKill", "EventTime": 800.0, "KillerName": "SRU_Dragon_Water", "VictimName": "summonerName"},
+        {"Assisters": [], "EventID": 4, "EventName": "ChampionKill", "EventTime": 801.0, "KillerName": "SRU_Baron12.1.1", "VictimName": "summonerName"},
+        {"Assisters": [], "EventID": 5, "EventName": "TurretKilled", "EventTime": 955.5, "KillerName": "Minion_T100L2S29N0183", "TurretKilled": "Turret_T2_L_03_A"},
+        {"Assisters": [], "EventID": 6, "EventName": "TurretKilled", "EventTime": 960.0, "KillerName": "Turret_T1_C_05_A", "TurretKilled": "Turret_T2_C_05_A"}
+    ]"#;
+    let events: Vec<GameEvent> = serde_json::from_str(raw).unwrap();
+
+    let serialized = serde_json::to_string(&events).unwrap();
+    let deserialized: Vec<GameEvent> = serde_json::from_str(&serialized).unwrap();
+    assert!(!deserialized
+        .iter()
+        .any(|e| matches!(e, GameEvent::Unknown { .. })));
+    // a second round trip doesn't change anything anymore
+    assert_eq!(serde_json::to_string(&deserialized).unwrap(), serialized);
+    // killers keep the names of the API
+    assert!(serialized.contains(r#""KillerName":"SRU_Dragon_Water""#));
+
+    let dragon_types: Vec<_> = deserialized
+        .iter()
+        .filter_map(|e| match e {
+            GameEvent::DragonKill(e) => Some(e.dragon_type.clone()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        dragon_types,
+        [
+            DragonType::Infernal,
+            DragonType::Unknown("Chaos".to_string())
+        ]
+    );
+    assert!(matches!(
+        &deserialized[2],
+        GameEvent::ChampionKill(e) if matches!(e.killer_name, Killer::Dragon(DragonType::Ocean))
+    ));
+    assert!(matches!(
+        &deserialized[3],
+        GameEvent::ChampionKill(e) if matches!(e.killer_name, Killer::Baron)
+    ));
+    assert!(matches!(
+        &deserialized[5],
+        GameEvent::TurretKilled(e) if matches!(&e.killer_name, Killer::Turret(turret) if turret.team == Some(TeamId::Order))
+    ));
+}
+
+#[test]
+fn deserialize_killer() {
+    let vec = [
+        ("\"SRU_Dragon_Elder\"", "SRU_Dragon_Elder"),
+        ("\"SRU_Dragon_Fire\"", "SRU_Dragon_Fire"),
+        ("\"SRU_Dragon_Water\"", "SRU_Dragon_Water"),
+        ("\"SRU_Dragon_Ruined\"", "SRU_Dragon_Ruined"),
+        ("\"SRU_Baron12.1.1\"", "SRU_Baron"),
+        ("\"SRU_RiftHerald17.1.1\"", "SRU_RiftHerald"),
+        ("\"Minion_T100L1S19N0116\"", "Minion"),
+        ("\"Red Buff Enjoyer\"", "Red Buff Enjoyer"),
+    ];
+
+    vec.iter().for_each(|(killer, display)| {
+        assert_eq!(
+            serde_json::from_str::<Killer>(killer).unwrap().to_string(),
+            *display
+        );
+    })
+}
+
+#[test]
+fn deserialize_arena_data() {
+    // hand-written, there is no captured Arena game yet, replace it with a capture once there is one
+    let test_data = include_str!("arena_allgamedata1_synthetic.json");
+    let data: AllGameData = serde_json::from_str(test_data).unwrap();
+
+    assert!(matches!(data.game_data.game_mode, GameMode::Arena));
+    assert!(matches!(data.game_data.map_name, MapName::Map30));
+    assert_eq!(data.all_players.len(), 16);
+
+    let player = &data.all_players[15];
+    assert_eq!(player.subteam(), Some(Subteam::Gromp));
+    assert_eq!(player.augments.len(), 1);
+    assert!(data.all_players.iter().all(|p| p.pve_stats.is_none()));
+
+    let rounds = data
+        .events
+        .iter()
+        .filter(|e| matches!(e, GameEvent::RoundEnd(_)))
+        .count();
+    assert_eq!(rounds, 2);
+}
+
+#[test]
+fn deserialize_swarm_data() {
+    // hand-written, there is no captured Swarm game yet, replace it with a capture once there is one
+    let test_data = include_str!("swarm_allgamedata1_synthetic.json");
+    let data: AllGameData = serde_json::from_str(test_data).unwrap();
+
+    assert!(matches!(data.game_data.game_mode, GameMode::Swarm));
+    assert!(matches!(data.game_data.map_name, MapName::Map33));
+    assert!(data
+        .all_players
+        .iter()
+        .all(|p| p.team == TeamId::Order && p.subteam().is_none() && p.pve_stats.is_some()));
+}
+
+#[test]
+fn deserialize_team_id() {
+    assert_eq!(
+        serde_json::from_str::<TeamId>("\"CHAOS\"").unwrap(),
+        TeamId::Chaos
+    );
+
+    // teams beyond ORDER and CHAOS keep their name
+    let team: TeamId = serde_json::from_str("\"TEAM_THREE\"").unwrap();
+    assert_eq!(team, TeamId::Other("TEAM_THREE".to_string()));
+    assert_eq!(serde_json::to_string(&team).unwrap(), "\"TEAM_THREE\"");
+
+    // an unexpected team of a player is kept as well
+    let mut data: serde_json::Value =
+        serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    data["allPlayers"][0]["team"] = "TEAM_THREE".into();
+    let data: AllGameData = serde_json::from_value(data).unwrap();
+    assert_eq!(
+        data.all_players[0].team,
+        TeamId::Other("TEAM_THREE".to_string())
+    );
+    assert_eq!(data.all_players[1].team, TeamId::Order);
+}
+
+#[test]
+fn deserialize_structures() {
+    let turret: Turret = serde_json::from_str("\"Turret_T1_C_06_A\"").unwrap();
+    assert_eq!(turret.team, Some(TeamId::Order));
+    assert_eq!(
+        turret.location(&MapName::Map11),
+        Some(TurretLocation {
+            lane: Some(Lane::Top),
+            tier: TurretTier::Inhibitor
+        })
+    );
+    // the raw name is preserved
+    assert_eq!(turret.to_string(), "Turret_T1_C_06_A");
+    assert_eq!(
+        serde_json::to_string(&turret).unwrap(),
+        "\"Turret_T1_C_06_A\""
+    );
+
+    let aram_turret: Turret = serde_json::from_str("\"Turret_T2_L_03_A\"").unwrap();
+    assert_eq!(
+        aram_turret.location(&MapName::Map12).unwrap().tier,
+        TurretTier::Nexus
+    );
+    assert_eq!(
+        aram_turret.location(&MapName::Map11).unwrap().tier,
+        TurretTier::Outer
+    );
+
+    let ten: Turret = serde_json::from_str("\"Turret_T1_C_010_A\"").unwrap();
+    assert!(matches!(
+        ten.kind,
+        TurretKind::Turret {
+            lane_code: LaneCode::C,
+            number: 10
+        }
+    ));
+
+    let fountain: Turret = serde_json::from_str("\"Turret_ChaosTurretShrine_A\"").unwrap();
+    assert_eq!(fountain.team, Some(TeamId::Chaos));
+    assert_eq!(fountain.kind, TurretKind::Fountain);
+
+    let unknown: Turret = serde_json::from_str("\"Turret_SomethingNew\"").unwrap();
+    assert_eq!(unknown.kind, TurretKind::Unknown);
+    assert_eq!(unknown.location(&MapName::Map11), None);
+
+    let inhibitor: Inhibitor = serde_json::from_str("\"Barracks_T2_R1\"").unwrap();
+    assert_eq!(inhibitor.team, Some(TeamId::Chaos));
+    assert_eq!(inhibitor.lane, Some(Lane::Bot));
+    assert_eq!(inhibitor.to_string(), "Barracks_T2_R1");
+}
@@ -0,0 +1,241 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use shaco::{
+    diff::{DiffEventStream, DiffOptions, GameDataDiffer},
+    mock::{MockGame, MockGamePhase, MockIngameServer},
+    model::{
+        diff::{AbilitySlot, ActivePlayerChange, DerivedEvent, IngameDiffEvent, PlayerChange},
+        ingame::AllGameData,
+    },
+};
+
+fn snapshot() -> Value {
+    serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap()
+}
+
+fn game_data(value: &Value) -> AllGameData {
+    serde_json::from_value(value.clone()).unwrap()
+}
+
+fn player_changes(events: &[DerivedEvent], summoner_name: &str) -> Vec<PlayerChange> {
+    events
+        .iter()
+        .filter_map(|e| match e {
+            DerivedEvent::Player {
+                summoner_name: name,
+                change,
+                ..
+            } if name == summoner_name => Some(change.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn item(id: i32, price: i32, consumable: bool, count: i32, slot: i32) -> Value {
+    json!({
+        "canUse": consumable,
+        "consumable": consumable,
+        "count": count,
+        "displayName": format!("Item {id}"),
+        "itemID": id,
+        "price": price,
+        "rawDescription": "",
+        "rawDisplayName": "",
+        "slot": slot
+    })
+}
+
+#[test]
+fn first_snapshot_has_no_events() {
+    let mut differ = GameDataDiffer::default();
+    assert!(differ.diff(&game_data(&snapshot())).is_empty());
+    assert!(differ.diff(&game_data(&snapshot())).is_empty());
+}
+
+#[test]
+fn player_state_changes() {
+    let mut differ = GameDataDiffer::default();
+    let mut data = snapshot();
+    data["allPlayers"][0]["level"] = json!(17);
+    data["allPlayers"][0]["scores"]["creepScore"] = json!(99);
+    data["allPlayers"][1]["isDead"] = json!(false);
+    differ.diff(&game_data(&data));
+
+    data["gameData"]["gameTime"] = json!(1745.0);
+    data["allPlayers"][0]["level"] = json!(18);
+    data["allPlayers"][0]["scores"]["creepScore"] = json!(101);
+    data["allPlayers"][0]["scores"]["wardScore"] = json!(1.5);
+    data["allPlayers"][1]["isDead"] = json!(true);
+    data["allPlayers"][1]["respawnTimer"] = json!(40.0);
+    let events = differ.diff(&game_data(&data));
+
+    let changes = player_changes(&events, "summonerName1");
+    assert!(changes
+        .iter()
+        .any(|c| matches!(c, PlayerChange::LevelUp { level: 18 })));
+    assert!(changes
+        .iter()
+        .any(|c| matches!(c, PlayerChange::CreepScoreMilestone { creep_score: 100 })));
+    assert!(changes
+        .iter()
+        .any(|c| matches!(c, PlayerChange::WardScoreChanged { .. })));
+
+    let changes = player_changes(&events, "summonerName2");
+    assert!(matches!(
+        changes.as_slice(),
+        [PlayerChange::Died { respawn_timer }] if *respawn_timer == 40.0
+    ));
+
+    data["gameData"]["gameTime"] = json!(1785.0);
+    data["allPlayers"][1]["isDead"] = json!(false);
+    let events = differ.diff(&game_data(&data));
+    assert!(matches!(
+        player_changes(&events, "summonerName2").as_slice(),
+        [PlayerChange::Respawned]
+    ));
+    assert!(player_changes(&events, "summonerName1").is_empty());
+}
+
+#[test]
+fn item_changes() {
+    let mut differ = GameDataDiffer::new(DiffOptions {
+        undo_window: 10.0,
+        ..Default::default()
+    });
+    let mut data = snapshot();
+    data["allPlayers"][0]["items"] = json!([
+        item(1036, 350, false, 1, 0),
+        item(1037, 875, false, 1, 1),
+        item(2003, 50, true, 2, 2)
+    ]);
+    differ.diff(&game_data(&data));
+
+    // a potion gets used, a long sword gets sold
+    data["gameData"]["gameTime"] = json!(1750.0);
+    data["allPlayers"][0]["items"] =
+        json!([item(1037, 875, false, 1, 1), item(2003, 50, true, 1, 2)]);
+    let changes = player_changes(&differ.diff(&game_data(&data)), "summonerName1");
+    assert_eq!(changes.len(), 2);
+    assert!(changes.iter().any(|c| matches!(
+        c,
+        PlayerChange::ItemSold { item } if item.item_id == 1036
+    )));
+    assert!(changes.iter().any(|c| matches!(
+        c,
+        PlayerChange::ItemConsumed { item, previous_count: 2 } if item.item_id == 2003
+    )));
+
+    // the pickaxe gets combined into a new item
+    data["gameData"]["gameTime"] = json!(1800.0);
+    data["allPlayers"][0]["items"] =
+        json!([item(3035, 1450, false, 1, 1), item(2003, 50, true, 1, 2)]);
+    let changes = player_changes(&differ.diff(&game_data(&data)), "summonerName1");
+    assert_eq!(changes.len(), 2);
+    assert!(changes.iter().any(|c| matches!(
+        c,
+        PlayerChange::ItemCombined { item } if item.item_id == 1037
+    )));
+    assert!(changes.iter().any(|c| matches!(
+        c,
+        PlayerChange::ItemPurchased { item } if item.item_id == 3035
+    )));
+
+    // the purchase gets undone which restores the pickaxe
+    data["gameData"]["gameTime"] = json!(1805.0);
+    data["allPlayers"][0]["items"] =
+        json!([item(1037, 875, false, 1, 1), item(2003, 50, true, 1, 2)]);
+    let changes = player_changes(&differ.diff(&game_data(&data)), "summonerName1");
+    assert!(matches!(
+        changes.as_slice(),
+        [PlayerChange::ItemUndone { item }] if item.item_id == 3035
+    ));
+
+    // items moving between slots don't change anything
+    data["gameData"]["gameTime"] = json!(1810.0);
+    data["allPlayers"][0]["items"] =
+        json!([item(2003, 50, true, 1, 0), item(1037, 875, false, 1, 5)]);
+    assert!(player_changes(&differ.diff(&game_data(&data)), "summonerName1").is_empty());
+}
+
+#[test]
+fn active_player_changes() {
+    let mut differ = GameDataDiffer::default();
+    let mut data = snapshot();
+    let current_gold = data["activePlayer"]["currentGold"].as_f64().unwrap();
+    let q_level = data["activePlayer"]["abilities"]["Q"]["abilityLevel"]
+        .as_i64()
+        .unwrap();
+    data["activePlayer"]["abilities"]["Q"]["abilityLevel"] = json!(q_level - 1);
+    differ.diff(&game_data(&data));
+
+    data["gameData"]["gameTime"] = json!(1745.0);
+    data["activePlayer"]["abilities"]["Q"]["abilityLevel"] = json!(q_level);
+    data["activePlayer"]["currentGold"] = json!(current_gold + 300.0);
+    let events = differ.diff(&game_data(&data));
+
+    let changes: Vec<&ActivePlayerChange> = events
+        .iter()
+        .filter_map(|e| match e {
+            DerivedEvent::ActivePlayer { change, .. } => Some(change),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(changes.len(), 2);
+    assert!(changes.iter().any(|c| matches!(
+        c,
+        ActivePlayerChange::AbilityLeveled { ability: AbilitySlot::Q, level } if *level as i64 == q_level
+    )));
+    assert!(changes
+        .iter()
+        .any(|c| matches!(c, ActivePlayerChange::GoldSpike { amount, .. } if *amount == 300.0)));
+}
+
+#[test]
+fn new_game_resets_the_differ() {
+    let mut differ = GameDataDiffer::default();
+    let mut data = snapshot();
+    differ.diff(&game_data(&data));
+
+    data["gameData"]["gameTime"] = json!(10.0);
+    data["allPlayers"][1]["isDead"] = json!(false);
+    assert!(differ.diff(&game_data(&data)).is_empty());
+}
+
+async fn next_native_event_id(stream: &mut DiffEventStream) -> u32 {
+    loop {
+        let event = tokio::time::timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .unwrap();
+        if let IngameDiffEvent::Native(event) = event {
+            return event.get_event_id();
+        }
+    }
+}
+
+#[tokio::test]
+async fn diff_event_stream_retries_failed_requests() {
+    let server = MockIngameServer::start(MockGame::from_all_game_data(snapshot()).unwrap())
+        .await
+        .unwrap();
+    server.set_game_time(50.0);
+
+    let mut stream = DiffEventStream::from_ingame_client(
+        server.client(),
+        Some(Duration::from_millis(10)),
+        None,
+        Some(20),
+    );
+    assert_eq!(next_native_event_id(&mut stream).await, 0);
+    assert_eq!(next_native_event_id(&mut stream).await, 1);
+
+    // a few failed requests in a row don't end the stream
+    server.set_phase(MockGamePhase::NoGame);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    server.set_game_time(105.0);
+    server.set_phase(MockGamePhase::InGame);
+    assert_eq!(next_native_event_id(&mut stream).await, 2);
+    assert_eq!(next_native_event_id(&mut stream).await, 3);
+}
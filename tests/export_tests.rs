@@ -0,0 +1,162 @@
+use serde_json::{json, Value};
+use shaco::{
+    export::{
+        active_player_stats_table, events_table, player_ticks_table, to_record_batch, write_csv,
+    },
+    model::{
+        export::ColumnValues,
+        ingame::{AllGameData, GameEvent},
+    },
+};
+
+fn frames() -> Vec<AllGameData> {
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    [60.0, 120.0]
+        .into_iter()
+        .map(|game_time| {
+            let mut data = data.clone();
+            data["gameData"]["gameTime"] = json!(game_time);
+            serde_json::from_value(data).unwrap()
+        })
+        .collect()
+}
+
+fn events() -> Vec<GameEvent> {
+    serde_json::from_value(json!([
+        { "EventID": 0, "EventName": "GameStart", "EventTime": 0.02 },
+        {
+            "Assisters": ["summonerName2", "summonerName3"],
+            "EventID": 1,
+            "EventName": "ChampionKill",
+            "EventTime": 100.0,
+            "KillerName": "summonerName1",
+            "VictimName": "summonerName6",
+        },
+        {
+            "Assisters": [],
+            "EventID": 2,
+            "EventName": "ChampionKill",
+            "EventTime": 200.0,
+            "KillerName": "SRU_Baron12.1.1",
+            "VictimName": "summonerName2",
+        },
+        {
+            "Assisters": [],
+            "DragonType": "Fire",
+            "EventID": 3,
+            "EventName": "DragonKill",
+            "EventTime": 400.0,
+            "KillerName": "summonerName1",
+            "Stolen": "True",
+        },
+    ]))
+    .unwrap()
+}
+
+#[test]
+fn player_ticks() {
+    let table = player_ticks_table(&frames());
+    assert_eq!(table.num_rows(), 20);
+    assert_eq!(table.columns.len(), 14);
+
+    let ColumnValues::Float(game_times) = &table.column("game_time").unwrap().values else {
+        panic!("game_time should be a float column");
+    };
+    assert_eq!(game_times[0], Some(60.0));
+    assert_eq!(game_times[10], Some(120.0));
+    assert!(matches!(
+        table.column("is_dead").unwrap().values,
+        ColumnValues::Bool(_)
+    ));
+    let ColumnValues::Text(items) = &table.column("items").unwrap().values else {
+        panic!("items should be a text column");
+    };
+    assert!(items.iter().all(Option::is_some));
+}
+
+#[test]
+fn events_and_active_player_stats() {
+    let table = events_table(&events());
+    assert_eq!(table.num_rows(), 4);
+    let text = |name: &str| {
+        let ColumnValues::Text(values) = &table.column(name).unwrap().values else {
+            panic!("{name} should be a text column");
+        };
+        values
+            .iter()
+            .map(|value| value.as_deref())
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        text("event_name"),
+        [
+            Some("GameStart"),
+            Some("ChampionKill"),
+            Some("ChampionKill"),
+            Some("DragonKill")
+        ]
+    );
+    assert_eq!(
+        text("killer_name"),
+        [
+            None,
+            Some("summonerName1"),
+            Some("SRU_Baron"),
+            Some("summonerName1")
+        ]
+    );
+    assert_eq!(
+        text("victim_name"),
+        [None, Some("summonerName6"), Some("summonerName2"), None]
+    );
+    assert_eq!(
+        text("assisters"),
+        [
+            None,
+            Some("summonerName2;summonerName3"),
+            Some(""),
+            Some("")
+        ]
+    );
+    let ColumnValues::Bool(stolen) = &table.column("stolen").unwrap().values else {
+        panic!("stolen should be a bool column");
+    };
+    assert_eq!(stolen, &[None, None, None, Some(true)]);
+    let details = text("details");
+    assert_eq!(details[0], None);
+    assert_eq!(details[1], None);
+    assert_eq!(details[3], Some(r#"{"DragonType":"Infernal"}"#));
+
+    let table = active_player_stats_table(&frames());
+    assert_eq!(table.num_rows(), 2);
+    assert!(table.column("ability_haste").is_some());
+    assert!(matches!(
+        table.column("resource_type").unwrap().values,
+        ColumnValues::Text(_)
+    ));
+}
+
+#[test]
+fn csv_and_arrow() {
+    let table = events_table(&events());
+
+    let mut csv = Vec::new();
+    write_csv(&table, &mut csv).unwrap();
+    let csv = String::from_utf8(csv).unwrap();
+    let mut lines = csv.lines();
+    assert_eq!(
+        lines.next().unwrap(),
+        "event_id,event_time,event_name,killer_name,victim_name,assisters,stolen,details"
+    );
+    assert_eq!(lines.next().unwrap(), "0,0.02,GameStart,,,,,");
+    assert_eq!(
+        lines.next().unwrap(),
+        "1,100,ChampionKill,summonerName1,summonerName6,summonerName2;summonerName3,,"
+    );
+    assert_eq!(csv.lines().count(), 5);
+
+    let batch = to_record_batch(&player_ticks_table(&frames())).unwrap();
+    assert_eq!(batch.num_rows(), 20);
+    assert_eq!(batch.num_columns(), 14);
+    assert_eq!(batch.schema().field(0).name(), "game_time");
+}
@@ -0,0 +1,170 @@
+use serde_json::{json, Value};
+use shaco::{
+    highlights::{
+        clip_render, clip_sequence, plan_highlights, play_clip, score_moments, HighlightOptions,
+    },
+    mock::{MockGame, MockIngameServer},
+    model::{
+        highlights::HighlightReason,
+        ingame::{AllGameData, DragonType, GameEvent},
+        replay::{CameraMode, EasingType},
+    },
+    replay::ReplayClient,
+};
+
+fn events(events: Value) -> Vec<GameEvent> {
+    serde_json::from_value(events).unwrap()
+}
+
+fn kill(id: u32, time: f64, killer: &str, victim: &str) -> Value {
+    json!({
+        "Assisters": [],
+        "EventID": id,
+        "EventName": "ChampionKill",
+        "EventTime": time,
+        "KillerName": killer,
+        "VictimName": victim,
+    })
+}
+
+fn game_events() -> Vec<GameEvent> {
+    events(json!([
+        { "EventID": 0, "EventName": "GameStart", "EventTime": 0.02 },
+        kill(1, 100.0, "summonerName1", "summonerName6"),
+        { "EventID": 2, "EventName": "FirstBlood", "EventTime": 100.0, "Recipient": "summonerName1" },
+        // a lone kill is dropped with the default min score
+        kill(3, 300.0, "summonerName7", "summonerName2"),
+        // a triple kill with an ace
+        kill(4, 600.0, "summonerName4", "summonerName6"),
+        kill(5, 603.0, "summonerName4", "summonerName7"),
+        { "EventID": 6, "EventName": "Multikill", "EventTime": 603.0, "KillStreak": 2, "KillerName": "summonerName4" },
+        kill(7, 606.0, "summonerName4", "summonerName8"),
+        { "EventID": 8, "EventName": "Multikill", "EventTime": 606.0, "KillStreak": 3, "KillerName": "summonerName4" },
+        kill(9, 615.0, "summonerName2", "summonerName9"),
+        kill(10, 620.0, "summonerName3", "summonerName10"),
+        { "EventID": 11, "EventName": "Ace", "EventTime": 620.0, "Acer": "summonerName3", "AcingTeam": "ORDER" },
+        // a stolen baron
+        {
+            "Assisters": [],
+            "EventID": 12,
+            "EventName": "BaronKill",
+            "EventTime": 1400.0,
+            "KillerName": "summonerName9",
+            "Stolen": "True",
+        },
+        {
+            "Assisters": [],
+            "DragonType": "Elder",
+            "EventID": 13,
+            "EventName": "DragonKill",
+            "EventTime": 2000.0,
+            "KillerName": "summonerName5",
+            "Stolen": "False",
+        },
+    ]))
+}
+
+fn players() -> AllGameData {
+    serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap()
+}
+
+#[test]
+fn score_moments_of_events() {
+    let options = HighlightOptions::default();
+    let moments = score_moments(&game_events(), &options);
+
+    assert_eq!(moments.len(), 13);
+    let baron = moments.iter().find(|m| m.event_id == 12).unwrap();
+    assert_eq!(baron.reason, HighlightReason::BaronKill { stolen: true });
+    assert_eq!(baron.score, options.baron + options.steal);
+    assert_eq!(baron.player.as_deref(), Some("summonerName9"));
+
+    let elder = moments.iter().find(|m| m.event_id == 13).unwrap();
+    assert_eq!(
+        elder.reason,
+        HighlightReason::DragonKill {
+            dragon_type: DragonType::Elder,
+            stolen: false
+        }
+    );
+    assert_eq!(elder.score, options.elder_dragon);
+}
+
+#[test]
+fn plan_ranked_clips() {
+    let options = HighlightOptions::default();
+    let clips = plan_highlights(&game_events(), &options);
+
+    // first blood, teamfight, baron and elder, the lone kill is dropped
+    assert_eq!(clips.len(), 4);
+    let teamfight = &clips[0];
+    assert_eq!(teamfight.start, 590.0);
+    assert_eq!(teamfight.end, 625.0);
+    assert_eq!(teamfight.moments.len(), 8);
+    assert_eq!(teamfight.focus_player.as_deref(), Some("summonerName4"));
+    assert_eq!(
+        teamfight.reason,
+        HighlightReason::Multikill { kill_streak: 3 }
+    );
+    assert_eq!(teamfight.score, 5.0 + 2.0 + 4.0 + 3.0);
+
+    assert_eq!(clips[1].reason, HighlightReason::BaronKill { stolen: true });
+    assert!(clips[1].score > clips[2].score);
+    assert!(clips.windows(2).all(|w| w[0].score >= w[1].score));
+
+    let first_blood = clips.iter().find(|c| c.start == 90.0).unwrap();
+    assert_eq!(first_blood.reason, HighlightReason::FirstBlood);
+    assert_eq!(first_blood.focus_player.as_deref(), Some("summonerName1"));
+}
+
+#[test]
+fn clip_replay_sequence() {
+    let options = HighlightOptions::default();
+    let clips = plan_highlights(&game_events(), &options);
+    let players = players().all_players;
+
+    let render = clip_render(&clips[0], &players);
+    assert_eq!(render.camera_mode, Some(CameraMode::Focus));
+    assert_eq!(render.selection_name.as_deref(), Some("Ezreal"));
+
+    let sequence = clip_sequence(&clips[0], &players, &options);
+    let selection = sequence.selection_name.unwrap();
+    assert_eq!(selection[0].time, 590.0);
+    assert_eq!(selection[0].value, "Ezreal");
+    assert_eq!(selection[0].blend, EasingType::Snap);
+
+    // slow motion centered on the triple kill
+    let speed = sequence.playback_speed.unwrap();
+    let times: Vec<f64> = speed.iter().map(|k| k.time).collect();
+    assert_eq!(times, [590.0, 604.0, 606.0, 608.0]);
+    assert_eq!(speed[2].value, 0.5);
+
+    let no_slow_motion = HighlightOptions {
+        slow_motion_speed: 1.0,
+        ..Default::default()
+    };
+    let sequence = clip_sequence(&clips[0], &players, &no_slow_motion);
+    assert_eq!(sequence.playback_speed.unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn play_clip_on_replay() {
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    let game = MockGame::from_all_game_data(data).unwrap().replay(true);
+    let server = MockIngameServer::start(game).await.unwrap();
+    let client = ReplayClient::from_ingame_client(&server.client());
+
+    let options = HighlightOptions::default();
+    let clips = plan_highlights(&game_events(), &options);
+    let playback = play_clip(&client, &clips[0], &players().all_players, &options)
+        .await
+        .unwrap();
+
+    assert!(!playback.paused);
+    assert!(playback.time >= 590.0);
+    assert_eq!(server.replay_render()["selectionName"], json!("Ezreal"));
+    assert_eq!(
+        server.replay_sequence()["selectionName"][0]["value"],
+        json!("Ezreal")
+    );
+}
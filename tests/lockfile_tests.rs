@@ -0,0 +1,199 @@
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use futures_util::StreamExt;
+use shaco::{
+    error::{LockfileError, ProcessInfoError},
+    model::lockfile::{Lockfile, LockfileEvent},
+    rest::LCUClientInfo,
+    utils::lockfile::{
+        discover_client_info, lockfile_client_info, read_lockfile, CredentialSource,
+        DiscoveryOptions, LockfileWatcher,
+    },
+};
+
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("shaco-lockfile-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn write_lockfile(path: &Path, content: &str) {
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, content).unwrap();
+}
+
+fn options(dir: &Path) -> DiscoveryOptions {
+    DiscoveryOptions {
+        install_paths: vec![dir.join("missing"), dir.join("League of Legends")],
+        riot_client_lockfiles: vec![dir.join("Riot Client/Config/lockfile")],
+        order: vec![CredentialSource::Lockfile],
+    }
+}
+
+#[test]
+fn parse_lockfile() {
+    let lockfile: Lockfile = "LeagueClient:1234:52437:Xyz-pass:https\n".parse().unwrap();
+    assert_eq!(
+        lockfile,
+        Lockfile {
+            name: "LeagueClient".to_string(),
+            pid: 1234,
+            port: 52437,
+            password: "Xyz-pass".to_string(),
+            protocol: "https".to_string(),
+        }
+    );
+    assert_eq!(lockfile.token(), "cmlvdDpYeXotcGFzcw==");
+
+    assert!("LeagueClient:1234:52437:https".parse::<Lockfile>().is_err());
+    assert!("LeagueClient:1234:port:pass:https"
+        .parse::<Lockfile>()
+        .is_err());
+}
+
+#[test]
+fn read_lockfile_errors() {
+    let dir = temp_dir("read");
+    assert!(matches!(
+        read_lockfile(dir.join("lockfile")),
+        Err(LockfileError::NotFound)
+    ));
+
+    write_lockfile(&dir.join("lockfile"), "LeagueClient:1234");
+    assert!(matches!(
+        read_lockfile(dir.join("lockfile")),
+        Err(LockfileError::InvalidFormat(_))
+    ));
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn discover_from_lockfiles() {
+    let dir = temp_dir("discover");
+    let options = options(&dir);
+
+    // the League lockfile is needed
+    assert!(matches!(
+        discover_client_info(&options),
+        Err(ProcessInfoError::Lockfile(LockfileError::NotFound))
+    ));
+    write_lockfile(
+        &dir.join("Riot Client/Config/lockfile"),
+        "Riot Client:1200:52400:riot:https",
+    );
+    assert!(matches!(
+        discover_client_info(&options),
+        Err(ProcessInfoError::Lockfile(LockfileError::NotFound))
+    ));
+    std::fs::remove_file(dir.join("Riot Client/Config/lockfile")).unwrap();
+
+    // without the Riot Client lockfile the League client credentials are used for both
+    write_lockfile(
+        &dir.join("League of Legends/lockfile"),
+        "LeagueClient:1234:52437:league:https",
+    );
+    assert_eq!(
+        discover_client_info(&options).unwrap(),
+        LCUClientInfo {
+            port: 52437,
+            token: "cmlvdDpsZWFndWU=".to_string(),
+            remoting_port: 52437,
+            remoting_token: "cmlvdDpsZWFndWU=".to_string(),
+        }
+    );
+
+    write_lockfile(
+        &dir.join("Riot Client/Config/lockfile"),
+        "Riot Client:1200:52400:riot:https",
+    );
+    let client_info = discover_client_info(&options).unwrap();
+    assert_eq!(
+        client_info,
+        LCUClientInfo {
+            port: 52400,
+            token: "cmlvdDpyaW90".to_string(),
+            remoting_port: 52437,
+            remoting_token: "cmlvdDpsZWFndWU=".to_string(),
+        }
+    );
+    assert_eq!(lockfile_client_info(&options).unwrap(), client_info);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn discover_without_sources() {
+    let options = DiscoveryOptions {
+        order: Vec::new(),
+        ..options(Path::new("/nonexistent"))
+    };
+    assert!(matches!(
+        discover_client_info(&options),
+        Err(ProcessInfoError::ProcessNotAvailable)
+    ));
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn default_paths_in_wine_prefix() {
+    let prefix = temp_dir("wine");
+    std::fs::create_dir_all(prefix.join("drive_c/users/player")).unwrap();
+    std::env::set_var("WINEPREFIX", &prefix);
+
+    let options = DiscoveryOptions::default();
+    assert_eq!(
+        options.install_paths.first(),
+        Some(&prefix.join("drive_c/Riot Games/League of Legends"))
+    );
+    assert_eq!(
+        options.riot_client_lockfiles.first(),
+        Some(
+            &prefix
+                .join("drive_c/users/player/AppData/Local/Riot Games/Riot Client/Config/lockfile")
+        )
+    );
+
+    std::fs::remove_dir_all(prefix).unwrap();
+}
+
+#[tokio::test]
+async fn watch_lockfile() {
+    async fn next_event(watcher: &mut LockfileWatcher) -> LockfileEvent {
+        tokio::time::timeout(Duration::from_secs(2), watcher.next())
+            .await
+            .expect("no lockfile event in time")
+            .unwrap()
+    }
+
+    let dir = temp_dir("watch");
+    let path = dir.join("League of Legends/lockfile");
+    let mut watcher = LockfileWatcher::new(
+        options(&dir).league_lockfiles(),
+        Some(Duration::from_millis(20)),
+    );
+
+    write_lockfile(&path, "LeagueClient:1234:52437:first:https");
+    assert_eq!(
+        next_event(&mut watcher).await,
+        LockfileEvent::Created(read_lockfile(&path).unwrap())
+    );
+
+    // a partially written lockfile is skipped
+    write_lockfile(&path, "LeagueClient:12");
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    write_lockfile(&path, "LeagueClient:1300:52500:second:https");
+    assert_eq!(
+        next_event(&mut watcher).await,
+        LockfileEvent::Changed(read_lockfile(&path).unwrap())
+    );
+
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(next_event(&mut watcher).await, LockfileEvent::Removed);
+
+    std::fs::remove_dir_all(dir).unwrap();
+}
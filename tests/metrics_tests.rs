@@ -0,0 +1,153 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use shaco::{
+    metrics::{ingame_metrics, lcu_metrics, MetricsExporter, MetricsOptions},
+    mock::{MockGame, MockGamePhase, MockIngameServer},
+    model::ingame::AllGameData,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+fn game_data() -> Value {
+    serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap()
+}
+
+async fn scrape(exporter: &MetricsExporter, path: &str) -> String {
+    let mut stream = TcpStream::connect(exporter.addr()).await.unwrap();
+    stream
+        .write_all(format!("GET {path} HTTP/1.1\r\nHost: localhost\r\n\r\n").as_bytes())
+        .await
+        .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+#[test]
+fn encode_ingame_metrics() {
+    let data: AllGameData = serde_json::from_value(game_data()).unwrap();
+    let text = ingame_metrics(&data);
+
+    assert!(text.starts_with("# HELP shaco_ingame_up "));
+    assert!(text.contains("\nshaco_ingame_up 1\n"));
+    assert!(text.contains(&format!(
+        "\nshaco_game_time_seconds {}\n",
+        data.game_data.game_time
+    )));
+    // one family with a sample per player
+    assert_eq!(text.matches("# TYPE shaco_player_kills gauge").count(), 1);
+    assert_eq!(text.matches("\nshaco_player_kills{").count(), 10);
+    // every player gauge has the same labels
+    let player = &data.all_players[0];
+    let labels = format!(
+        "{{summoner_name=\"{}\",champion_name=\"{}\",team=\"{}\"}}",
+        player.summoner_name, player.champion_name, player.team
+    );
+    for name in [
+        "shaco_player_kills",
+        "shaco_player_level",
+        "shaco_player_kda",
+    ] {
+        assert!(text.contains(&format!("\n{name}{labels} ")), "{name}");
+    }
+    assert_eq!(text.matches("\nshaco_team_recipe_gold{").count(), 2);
+    assert!(text.contains("stat=\"abilityHaste\""));
+    assert!(text.lines().all(
+        |line| line.starts_with('#') || line.rsplit_once(' ').unwrap().1.parse::<f64>().is_ok()
+    ));
+}
+
+#[test]
+fn encode_lcu_metrics() {
+    let text = lcu_metrics(
+        &json!("Matchmaking"),
+        &json!({ "timeInQueue": 42.5, "estimatedQueueTime": 90.0 }),
+        &json!({
+            "queues": [
+                { "queueType": "RANKED_SOLO_5x5", "tier": "GOLD", "division": "II", "leaguePoints": 45 },
+                { "queueType": "RANKED_FLEX_SR", "tier": "", "division": "NA", "leaguePoints": 0 },
+            ]
+        }),
+    );
+    assert!(text.contains("\nshaco_lcu_up 1\n"));
+    assert!(text.contains("\nshaco_gameflow_phase{phase=\"Matchmaking\"} 1\n"));
+    assert!(text.contains("\nshaco_queue_time_seconds 42.5\n"));
+    assert!(text.contains("\nshaco_queue_estimated_time_seconds 90\n"));
+    assert!(text.contains(
+        "\nshaco_ranked_league_points{queue_type=\"RANKED_SOLO_5x5\",tier=\"GOLD\",division=\"II\"} 45\n"
+    ));
+
+    // error bodies while not queueing are skipped
+    let text = lcu_metrics(
+        &json!("Lobby\"\n"),
+        &json!({ "errorCode": "RPC_ERROR", "httpStatus": 404 }),
+        &Value::Null,
+    );
+    assert!(text.contains("{phase=\"Lobby\\\"\\n\"}"));
+    assert!(!text.contains("shaco_queue_time_seconds"));
+    assert!(!text.contains("shaco_ranked_league_points"));
+}
+
+#[tokio::test]
+async fn scrape_mock_game() {
+    let server = MockIngameServer::start(MockGame::from_all_game_data(game_data()).unwrap())
+        .await
+        .unwrap();
+    let exporter = MetricsExporter::start(
+        "127.0.0.1:0",
+        Some(server.client()),
+        None,
+        MetricsOptions {
+            polling_rate: Duration::from_millis(20),
+        },
+    )
+    .await
+    .unwrap();
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let response = scrape(&exporter, "/metrics").await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    assert!(response.contains("Content-Type: text/plain; version=0.0.4"));
+    assert!(response.contains("\nshaco_ingame_up 1\n"));
+    assert!(!response.contains("shaco_lcu_up"));
+
+    server.set_phase(MockGamePhase::NoGame);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let response = scrape(&exporter, "/metrics").await;
+    assert!(response.contains("\nshaco_ingame_up 0\n"));
+    assert!(!response.contains("shaco_player_kills"));
+
+    let response = scrape(&exporter, "/").await;
+    assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+}
+
+#[tokio::test]
+async fn reject_oversized_requests() {
+    let exporter = MetricsExporter::start("127.0.0.1:0", None, None, MetricsOptions::default())
+        .await
+        .unwrap();
+    let addr = exporter.addr();
+    let send = |request: Vec<u8>| async move {
+        let mut stream = TcpStream::connect(addr).await.unwrap();
+        let _ = stream.write_all(&request).await;
+        let mut response = Vec::new();
+        // the connection is either closed or reset without a response
+        let _ = tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response))
+            .await
+            .unwrap();
+        response
+    };
+
+    let mut headers = b"GET /metrics HTTP/1.1\r\n".to_vec();
+    headers.extend(b"X-Padding: ".iter().chain(&[b'a'; 32 * 1024]));
+    assert!(send(headers).await.is_empty());
+
+    let content_length = b"POST /metrics HTTP/1.1\r\nContent-Length: 1073741824\r\n\r\n".to_vec();
+    assert!(send(content_length).await.is_empty());
+
+    let response = send(b"GET /metrics HTTP/1.1\r\n\r\n".to_vec()).await;
+    assert!(response.starts_with(b"HTTP/1.1 200 OK\r\n"));
+}
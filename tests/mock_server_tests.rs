@@ -0,0 +1,237 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde_json::Value;
+use shaco::{
+    error::IngameClientError,
+    ingame::{ContinuousEventStream, EventStream, IngameClient},
+    mock::{MockGame, MockGamePhase, MockIngameServer},
+    model::ingame::{GameEvent, IngameStreamEvent},
+};
+
+fn recorded_game() -> MockGame {
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    MockGame::from_all_game_data(data).unwrap()
+}
+
+/// the offline version of `ingame_livegame_api_deserialization`
+#[tokio::test]
+async fn mock_livegame_api_deserialization() {
+    let server = MockIngameServer::start(recorded_game()).await.unwrap();
+    server.set_game_time(1741.5);
+    let client = server.client();
+
+    assert!(client.active_game().await);
+    assert!(client.active_game_loadingscreen().await);
+    assert!(!client.is_spectator_mode().await.unwrap());
+    client.all_game_data(None).await.unwrap();
+    client.event_data(None).await.unwrap();
+    client.game_stats().await.unwrap();
+
+    let players = client.player_list(None).await.unwrap();
+    assert_eq!(players.len(), 10);
+    let player_name = players.first().unwrap().summoner_name.to_string();
+
+    client.player_items(&player_name).await.unwrap();
+    client.player_main_runes(&player_name).await.unwrap();
+    client.player_scores(&player_name).await.unwrap();
+    client.player_summoner_spells(&player_name).await.unwrap();
+
+    client.active_player().await.unwrap();
+    client.active_player_abilities().await.unwrap();
+    assert_eq!(client.active_player_name().await.unwrap(), "summonerName8");
+    client.active_player_runes().await.unwrap();
+}
+
+/// the offline version of `ingame_spectate_api_deserialization`
+#[tokio::test]
+async fn mock_spectate_api_deserialization() {
+    let server = MockIngameServer::start(recorded_game().spectator(true))
+        .await
+        .unwrap();
+    let client = server.client();
+
+    assert!(client.active_game().await);
+    assert!(client.is_spectator_mode().await.unwrap());
+    let data = client.all_game_data(None).await.unwrap();
+    assert!(data.active_player.is_none());
+    assert!(matches!(
+        client.active_player().await,
+        Err(IngameClientError::ApiNotAvailableInSpectatorMode)
+    ));
+
+    let players = client.player_list(None).await.unwrap();
+    let player_name = players.first().unwrap().summoner_name.to_string();
+    client.player_scores(&player_name).await.unwrap();
+}
+
+#[tokio::test]
+async fn mock_game_phases() {
+    let server = MockIngameServer::start(recorded_game()).await.unwrap();
+    let client = server.client();
+
+    server.set_phase(MockGamePhase::NoGame);
+    assert!(!client.active_game_loadingscreen().await);
+    assert!(!client.active_game().await);
+
+    server.set_phase(MockGamePhase::LoadingScreen);
+    assert!(client.active_game_loadingscreen().await);
+    assert!(!client.active_game().await);
+    assert!(matches!(
+        client.game_stats().await,
+        Err(IngameClientError::ApiNotAvailableDuringLoadingScreen)
+    ));
+
+    server.set_phase(MockGamePhase::InGame);
+    assert!(client.active_game().await);
+}
+
+#[tokio::test]
+async fn mock_advances_game_time() {
+    let server = MockIngameServer::start(recorded_game()).await.unwrap();
+    let client = server.client();
+
+    // only the events until the current game time are served
+    assert!(client.event_data(None).await.unwrap().is_empty());
+    server.advance(Duration::from_secs(1));
+    let events = client.event_data(None).await.unwrap();
+    assert_eq!(events.len(), 1);
+    assert!(matches!(events[0], GameEvent::GameStart(_)));
+
+    server.advance(Duration::from_secs(99));
+    assert_eq!(client.game_stats().await.unwrap().game_time, 100.0);
+    let events = client.event_data(Some(1)).await.unwrap();
+    assert!(events.iter().all(|e| e.get_event_id() >= 1));
+    assert!(events.iter().all(|e| e.get_event_time() <= 100.0));
+    assert!(!events.is_empty());
+
+    server.set_time_scale(1000.0);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(client.game_stats().await.unwrap().game_time > 100.0);
+}
+
+#[tokio::test]
+async fn mock_event_stream() {
+    let server = MockIngameServer::start(recorded_game()).await.unwrap();
+    server.set_game_time(50.0);
+
+    let mut stream =
+        EventStream::from_ingame_client(server.client(), Some(Duration::from_millis(10)));
+    assert!(matches!(
+        stream.next().await.unwrap(),
+        GameEvent::GameStart(_)
+    ));
+    assert!(matches!(
+        stream.next().await.unwrap(),
+        GameEvent::MinionsSpawning(_)
+    ));
+}
+
+async fn next_stream_event(stream: &mut ContinuousEventStream) -> IngameStreamEvent {
+    tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+async fn next_event_ids(stream: &mut ContinuousEventStream, count: usize) -> Vec<u32> {
+    let mut ids = Vec::new();
+    while ids.len() < count {
+        match next_stream_event(stream).await {
+            IngameStreamEvent::GameEvent(event) => ids.push(event.get_event_id()),
+            event => panic!("expected a game event, got {:?}", event),
+        }
+    }
+    ids
+}
+
+#[tokio::test]
+async fn mock_continuous_event_stream_survives_outage() {
+    let server = MockIngameServer::start(recorded_game()).await.unwrap();
+    // GameStart, MinionsSpawning, ChampionKill and FirstBlood
+    server.set_game_time(105.0);
+
+    let mut stream = ContinuousEventStream::from_ingame_client(
+        server.client(),
+        Some(Duration::from_millis(10)),
+        Some(2),
+    );
+    assert!(matches!(
+        next_stream_event(&mut stream).await,
+        IngameStreamEvent::GameStarted
+    ));
+    assert_eq!(next_event_ids(&mut stream, 4).await, [0, 1, 2, 3]);
+
+    // the API is unreachable for a while in the middle of the game
+    server.set_phase(MockGamePhase::NoGame);
+    assert!(matches!(
+        next_stream_event(&mut stream).await,
+        IngameStreamEvent::GameEnded
+    ));
+    server.set_game_time(108.0);
+    server.set_phase(MockGamePhase::InGame);
+    assert!(matches!(
+        next_stream_event(&mut stream).await,
+        IngameStreamEvent::GameStarted
+    ));
+    // only the events that happened during the outage follow
+    assert_eq!(next_event_ids(&mut stream, 2).await, [4, 5]);
+
+    // a new game starts from the first event again
+    server.set_phase(MockGamePhase::NoGame);
+    assert!(matches!(
+        next_stream_event(&mut stream).await,
+        IngameStreamEvent::GameEnded
+    ));
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata2.json")).unwrap();
+    server.set_game(MockGame::from_all_game_data(data).unwrap());
+    server.set_game_time(90.0);
+    server.set_phase(MockGamePhase::InGame);
+    assert!(matches!(
+        next_stream_event(&mut stream).await,
+        IngameStreamEvent::GameStarted
+    ));
+    assert_eq!(next_event_ids(&mut stream, 5).await, [0, 1, 2, 3, 4]);
+}
+
+#[tokio::test]
+async fn builder_connects_to_configured_host() {
+    let server = MockIngameServer::start(recorded_game()).await.unwrap();
+    let client = IngameClient::builder()
+        .host("localhost")
+        .port(server.addr().port())
+        .secure(false)
+        .timeout(Duration::from_secs(2))
+        .build()
+        .unwrap();
+
+    assert!(client.active_game().await);
+    assert_eq!(client.player_list(None).await.unwrap().len(), 10);
+}
+
+#[tokio::test]
+async fn builder_timeout() {
+    // accepts connections but never answers
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let _server = tokio::spawn(async move {
+        let mut connections = Vec::new();
+        while let Ok((stream, _)) = listener.accept().await {
+            connections.push(stream);
+        }
+    });
+
+    let client = IngameClient::builder()
+        .port(port)
+        .secure(false)
+        .timeout(Duration::from_millis(50))
+        .build()
+        .unwrap();
+
+    let started = std::time::Instant::now();
+    assert!(matches!(
+        client.game_stats().await,
+        Err(IngameClientError::ConnectionError(_))
+    ));
+    assert!(started.elapsed() < Duration::from_millis(400));
+}
@@ -0,0 +1,301 @@
+use std::time::Duration;
+
+use futures_util::{stream, Stream, StreamExt};
+use serde_json::{json, Value};
+use shaco::{
+    cache::{CHAMP_SELECT_SESSION, GAMEFLOW_PHASE},
+    mock::{MockGame, MockGamePhase, MockIngameServer},
+    model::{overlay::OverlayState, ws::LcuEvent},
+    overlay::{OverlayOptions, OverlayServer},
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+use tokio_tungstenite::tungstenite::{self, Message};
+
+fn game_data() -> Value {
+    serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap()
+}
+
+fn lcu_event(uri: &str, data: Value) -> LcuEvent {
+    serde_json::from_value(json!([
+        8,
+        "OnJsonApiEvent",
+        { "data": data, "eventType": "Update", "uri": uri }
+    ]))
+    .unwrap()
+}
+
+async fn request(server: &OverlayServer, head: &str) -> TcpStream {
+    let mut stream = TcpStream::connect(server.addr()).await.unwrap();
+    stream
+        .write_all(format!("{head}\r\nHost: localhost\r\n\r\n").as_bytes())
+        .await
+        .unwrap();
+    stream
+}
+
+async fn read_to_string(mut stream: TcpStream) -> String {
+    let mut response = String::new();
+    stream.read_to_string(&mut response).await.unwrap();
+    response
+}
+
+/// Reads Server-Sent Events until `done` returns true for the received messages
+async fn read_events<F: Fn(&[Value]) -> bool>(stream: &mut TcpStream, done: F) -> Vec<Value> {
+    let mut text = String::new();
+    let mut buf = [0; 4096];
+    loop {
+        let messages: Vec<Value> = text
+            .lines()
+            .filter_map(|line| line.strip_prefix("data: "))
+            .map(|data| serde_json::from_str(data).unwrap())
+            .collect();
+        if done(&messages) {
+            return messages;
+        }
+        let n = tokio::time::timeout(Duration::from_secs(2), stream.read(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(n > 0, "the event stream ended");
+        text.push_str(&String::from_utf8_lossy(&buf[..n]));
+    }
+}
+
+async fn next_message<S>(socket: &mut S) -> Value
+where
+    S: Stream<Item = Result<Message, tungstenite::Error>> + Unpin,
+{
+    let message = tokio::time::timeout(Duration::from_secs(2), socket.next())
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+    serde_json::from_str(message.to_text().unwrap()).unwrap()
+}
+
+/// Collects the ids of the next `count` game events
+async fn next_event_ids<S>(socket: &mut S, count: usize) -> Vec<u64>
+where
+    S: Stream<Item = Result<Message, tungstenite::Error>> + Unpin,
+{
+    let mut ids = Vec::new();
+    // state messages keep arriving while the game runs
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while ids.len() < count {
+            let message = next_message(socket).await;
+            if message["type"] == "gameEvent" {
+                ids.push(message["event"]["EventID"].as_u64().unwrap());
+            }
+        }
+    })
+    .await
+    .unwrap_or_else(|_| panic!("only received the events {ids:?}"));
+    ids
+}
+
+#[tokio::test]
+async fn websocket_and_snapshot() {
+    let data = game_data();
+    let event_count = data["events"]["Events"].as_array().unwrap().len();
+    let mock = MockIngameServer::start(MockGame::from_all_game_data(data).unwrap())
+        .await
+        .unwrap();
+    // serve every event of the recording
+    mock.set_game_time(f64::MAX);
+    let mut server = OverlayServer::start(
+        "127.0.0.1:0",
+        OverlayOptions {
+            throttle: Duration::from_millis(20),
+            polling_rate: Duration::from_millis(20),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", server.addr()))
+        .await
+        .unwrap();
+
+    // the current state is sent on connect
+    let message = next_message(&mut socket).await;
+    assert_eq!(message["type"], "state");
+    let state: OverlayState = serde_json::from_value(message["state"].clone()).unwrap();
+    assert!(state.game.is_none());
+
+    server.forward_ingame(mock.client());
+    let mut events = 0;
+    let game = loop {
+        let message = next_message(&mut socket).await;
+        match message["type"].as_str().unwrap() {
+            "gameEvent" => events += 1,
+            "state" if !message["state"]["game"].is_null() => {
+                let state: OverlayState = serde_json::from_value(message["state"].clone()).unwrap();
+                break state.game.unwrap();
+            }
+            _ => {}
+        }
+    };
+    assert_eq!(events, event_count);
+    assert_eq!(game.players.len(), 10);
+    assert_eq!(game.teams.len(), 2);
+
+    let response = read_to_string(request(&server, "GET /state HTTP/1.1").await).await;
+    assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+    let (_, body) = response.split_once("\r\n\r\n").unwrap();
+    let snapshot: Value = serde_json::from_str(body).unwrap();
+    assert_eq!(snapshot["game"]["players"].as_array().unwrap().len(), 10);
+
+    let response = read_to_string(request(&server, "POST /state HTTP/1.1").await).await;
+    assert!(response.starts_with("HTTP/1.1 405 "));
+}
+
+#[tokio::test]
+async fn server_sent_lcu_events_and_cors() {
+    let mut server = OverlayServer::start(
+        "127.0.0.1:0",
+        OverlayOptions {
+            allowed_origins: vec!["http://localhost:3000".to_string()],
+            throttle: Duration::from_millis(200),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let response =
+        read_to_string(request(&server, "GET /state HTTP/1.1\r\nOrigin: http://example.com").await)
+            .await;
+    assert!(response.starts_with("HTTP/1.1 403 "));
+
+    let response = read_to_string(
+        request(
+            &server,
+            "OPTIONS /events HTTP/1.1\r\nOrigin: http://localhost:3000",
+        )
+        .await,
+    )
+    .await;
+    assert!(response.starts_with("HTTP/1.1 204 "));
+    assert!(response.contains("Access-Control-Allow-Origin: http://localhost:3000\r\n"));
+
+    let mut events = request(
+        &server,
+        "GET /events HTTP/1.1\r\nOrigin: http://localhost:3000",
+    )
+    .await;
+    read_events(&mut events, |messages| !messages.is_empty()).await;
+
+    server.forward_lcu_events(
+        stream::iter([
+            lcu_event(GAMEFLOW_PHASE, json!("ChampSelect")),
+            lcu_event(CHAMP_SELECT_SESSION, json!({ "timer": 1 })),
+            lcu_event(CHAMP_SELECT_SESSION, json!({ "timer": 2 })),
+            lcu_event("/lol-chat/v1/me", json!({})),
+            lcu_event(CHAMP_SELECT_SESSION, json!({ "timer": 3 })),
+        ])
+        .chain(stream::pending()),
+    );
+    let messages = read_events(&mut events, |messages| {
+        messages.iter().any(|message| {
+            message["type"] == "state" && !message["state"]["gameflowPhase"].is_null()
+        })
+    })
+    .await;
+
+    let lcu_events: Vec<&Value> = messages
+        .iter()
+        .filter(|message| message["type"] == "lcuEvent")
+        .map(|message| &message["event"])
+        .collect();
+    // the updates of the session are coalesced and other URIs are not forwarded
+    assert_eq!(lcu_events.len(), 2);
+    assert_eq!(lcu_events[0]["uri"], GAMEFLOW_PHASE);
+    assert_eq!(lcu_events[1]["data"], json!({ "timer": 3 }));
+    assert_eq!(
+        server.state().gameflow_phase.as_deref(),
+        Some("ChampSelect")
+    );
+}
+
+#[tokio::test]
+async fn default_origins_are_local() {
+    let server = OverlayServer::start("127.0.0.1:0", OverlayOptions::default())
+        .await
+        .unwrap();
+
+    for origin in ["http://localhost:5173", "http://127.0.0.1"] {
+        let response = read_to_string(
+            request(&server, &format!("GET /state HTTP/1.1\r\nOrigin: {origin}")).await,
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 200 "), "{origin}");
+        assert!(response.contains(&format!("Access-Control-Allow-Origin: {origin}\r\n")));
+    }
+    for origin in [
+        "http://example.com",
+        "http://localhost.example.com",
+        "http://localhost:3000.example.com",
+    ] {
+        let response = read_to_string(
+            request(&server, &format!("GET /state HTTP/1.1\r\nOrigin: {origin}")).await,
+        )
+        .await;
+        assert!(response.starts_with("HTTP/1.1 403 "), "{origin}");
+    }
+}
+
+#[tokio::test]
+async fn forward_ingame_survives_outage() {
+    let mock = MockIngameServer::start(MockGame::from_all_game_data(game_data()).unwrap())
+        .await
+        .unwrap();
+    // GameStart, MinionsSpawning, ChampionKill and FirstBlood
+    mock.set_game_time(105.0);
+    let mut server = OverlayServer::start(
+        "127.0.0.1:0",
+        OverlayOptions {
+            throttle: Duration::from_millis(20),
+            polling_rate: Duration::from_millis(20),
+            max_retries: 2,
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+    let (mut socket, _) = tokio_tungstenite::connect_async(format!("ws://{}/ws", server.addr()))
+        .await
+        .unwrap();
+    next_message(&mut socket).await;
+
+    server.forward_ingame(mock.client());
+    assert_eq!(next_event_ids(&mut socket, 4).await, [0, 1, 2, 3]);
+
+    // a single failed poll keeps the game
+    mock.set_phase(MockGamePhase::NoGame);
+    tokio::time::sleep(Duration::from_millis(30)).await;
+    mock.set_game_time(108.0);
+    mock.set_phase(MockGamePhase::InGame);
+    assert_eq!(next_event_ids(&mut socket, 2).await, [4, 5]);
+    assert!(server.state().game.is_some());
+
+    // the same game answering after it was considered ended doesn't publish its events again
+    mock.set_phase(MockGamePhase::NoGame);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(server.state().game.is_none());
+    mock.set_game_time(113.0);
+    mock.set_phase(MockGamePhase::InGame);
+    assert_eq!(next_event_ids(&mut socket, 1).await, [6]);
+
+    // a new game starts from the first event again
+    mock.set_phase(MockGamePhase::NoGame);
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata2.json")).unwrap();
+    mock.set_game(MockGame::from_all_game_data(data).unwrap());
+    mock.set_game_time(90.0);
+    mock.set_phase(MockGamePhase::InGame);
+    assert_eq!(next_event_ids(&mut socket, 5).await, [0, 1, 2, 3, 4]);
+}
@@ -0,0 +1,87 @@
+#![cfg(target_os = "linux")]
+
+use std::{
+    path::PathBuf,
+    process::{Child, Command},
+    time::Duration,
+};
+
+use futures_util::StreamExt;
+use shaco::{
+    model::process::LcuProcessEvent,
+    utils::process_info::{get_auth_info, ProcessWatcher},
+};
+
+/// A shell copied to a file named like the client, so the process name matches
+fn fake_client_binary() -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("shaco-process-watcher-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let binary = dir.join("LeagueClientUx.exe");
+    std::fs::copy("/bin/sh", &binary).unwrap();
+    binary
+}
+
+fn args(port: u16) -> Vec<String> {
+    vec![
+        format!("--riotclient-app-port={port}"),
+        format!("--riotclient-auth-token=token{port}"),
+        format!("--app-port={}", port + 1),
+        format!("--remoting-auth-token=remoting{port}"),
+    ]
+}
+
+fn start_client(binary: &PathBuf, port: u16) -> Child {
+    // the arguments after the script are ignored by the shell
+    Command::new(binary)
+        .arg("-c")
+        .arg("sleep 30; :")
+        .args(args(port))
+        .spawn()
+        .unwrap()
+}
+
+/// A client that only gets its arguments after a while, like a client that is still starting up \
+/// The arguments are passed in the environment, so they're not part of the command line before the `exec`
+fn start_slow_client(binary: &PathBuf, port: u16) -> Child {
+    Command::new(binary)
+        .arg("-c")
+        .arg("sleep 1; exec \"$CLIENT\" -c 'sleep 30; :' $CLIENT_ARGS")
+        .env("CLIENT", binary)
+        .env("CLIENT_ARGS", args(port).join(" "))
+        .spawn()
+        .unwrap()
+}
+
+async fn next_event(watcher: &mut ProcessWatcher) -> LcuProcessEvent {
+    tokio::time::timeout(Duration::from_secs(5), watcher.next())
+        .await
+        .expect("no process event in time")
+        .unwrap()
+}
+
+#[tokio::test]
+async fn watch_start_restart_and_exit() {
+    let binary = fake_client_binary();
+    let mut watcher = ProcessWatcher::new(Some(Duration::from_millis(50)));
+
+    let mut first = start_client(&binary, 50001);
+    assert_eq!(
+        next_event(&mut watcher).await,
+        LcuProcessEvent::Started(get_auth_info(args(50001).join(" ")).unwrap())
+    );
+
+    // the old client exits while the new one is still starting up
+    let mut second = start_slow_client(&binary, 50011);
+    first.kill().unwrap();
+    first.wait().unwrap();
+    assert_eq!(
+        next_event(&mut watcher).await,
+        LcuProcessEvent::Restarted(get_auth_info(args(50011).join(" ")).unwrap())
+    );
+
+    second.kill().unwrap();
+    second.wait().unwrap();
+    assert_eq!(next_event(&mut watcher).await, LcuProcessEvent::Exited);
+
+    std::fs::remove_dir_all(binary.parent().unwrap()).unwrap();
+}
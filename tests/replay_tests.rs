@@ -0,0 +1,212 @@
+use std::time::Duration;
+
+use serde_json::{json, Value};
+use shaco::{
+    error::ReplayClientError,
+    ingame::IngameClient,
+    mock::{MockGame, MockIngameServer},
+    model::replay::*,
+    replay::ReplayClient,
+};
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+fn recorded_game() -> MockGame {
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    MockGame::from_all_game_data(data).unwrap()
+}
+
+#[test]
+fn serialize_partial_updates() {
+    let render = Render {
+        fog_of_war: Some(false),
+        interface_all: Some(false),
+        camera_mode: Some(CameraMode::Focus),
+        selection_name: Some("Ahri".to_string()),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&render).unwrap(),
+        json!({
+            "cameraMode": "focus",
+            "fogOfWar": false,
+            "interfaceAll": false,
+            "selectionName": "Ahri",
+        })
+    );
+
+    let sequence = Sequence {
+        field_of_view: Some(vec![
+            KeyFrame::new(10.0, 45.0, EasingType::Linear),
+            KeyFrame::new(12.5, 60.0, EasingType::CubicEaseInOut),
+        ]),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&sequence).unwrap(),
+        json!({
+            "fieldOfView": [
+                { "blend": "linear", "time": 10.0, "value": 45.0 },
+                { "blend": "cubicEaseInOut", "time": 12.5, "value": 60.0 },
+            ]
+        })
+    );
+    assert_eq!(
+        serde_json::to_value(PlaybackUpdate::default()).unwrap(),
+        json!({})
+    );
+}
+
+#[test]
+fn deserialize_render() {
+    let render: Render = serde_json::from_value(json!({
+        "cameraAttached": false,
+        "cameraMode": "tps",
+        "cameraPosition": { "x": 1500.0, "y": 1900.0, "z": 12000.0 },
+        "depthFogColor": { "r": 0.0, "g": 0.0, "b": 0.0, "a": 1.0 },
+        "fogOfWar": true,
+        "healthBarMinions": true,
+        "interfaceScoreboard": false,
+        "unknownField": 1,
+    }))
+    .unwrap();
+
+    assert_eq!(render.camera_mode, Some(CameraMode::Tps));
+    assert_eq!(render.camera_position.unwrap().z, 12000.0);
+    assert_eq!(render.depth_fog_color.unwrap().a, 1.0);
+    assert_eq!(render.interface_scoreboard, Some(false));
+    assert_eq!(render.interface_all, None);
+}
+
+#[tokio::test]
+async fn replay_api_disabled() {
+    let server = MockIngameServer::start(recorded_game()).await.unwrap();
+    let client = ReplayClient::from_ingame_client(&server.client());
+
+    assert!(matches!(
+        client.playback().await,
+        Err(ReplayClientError::ApiNotEnabled)
+    ));
+}
+
+#[tokio::test]
+async fn mock_replay_api() {
+    let server = MockIngameServer::start(recorded_game().replay(true))
+        .await
+        .unwrap();
+    let client = ReplayClient::from_ingame_client(&server.client());
+
+    assert_eq!(client.game().await.unwrap().process_id, std::process::id());
+
+    let playback = client.seek(600.0).await.unwrap();
+    assert!(playback.paused);
+    assert_eq!(playback.time, 600.0);
+    assert_eq!(server.game_time(), 600.0);
+    client.set_speed(2.0).await.unwrap();
+    assert!(!client.play().await.unwrap().paused);
+    assert!(client.pause().await.unwrap().paused);
+    assert_eq!(client.playback().await.unwrap().speed, 2.0);
+
+    let render = client
+        .set_render(&Render {
+            fog_of_war: Some(false),
+            health_bar_minions: Some(false),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(render.fog_of_war, Some(false));
+    assert_eq!(render.interface_all, Some(true));
+    assert_eq!(server.replay_render()["healthBarMinions"], json!(false));
+
+    let recording = client
+        .start_recording(Recording {
+            start_time: Some(600.0),
+            end_time: Some(630.0),
+            output_path: Some("C:/highlights/clip.webm".to_string()),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    assert_eq!(recording.recording, Some(true));
+    assert_eq!(recording.codec.as_deref(), Some("webm"));
+    assert_eq!(
+        client.stop_recording().await.unwrap().recording,
+        Some(false)
+    );
+
+    let sequence = Sequence {
+        camera_position: Some(vec![
+            KeyFrame::new(
+                600.0,
+                Vector3 {
+                    x: 1000.0,
+                    y: 1500.0,
+                    z: 1000.0,
+                },
+                EasingType::Linear,
+            ),
+            KeyFrame::new(
+                610.0,
+                Vector3 {
+                    x: 5000.0,
+                    y: 1500.0,
+                    z: 5000.0,
+                },
+                EasingType::SmoothStep,
+            ),
+        ]),
+        ..Default::default()
+    };
+    assert_eq!(client.set_sequence(&sequence).await.unwrap(), sequence);
+    assert_eq!(client.sequence().await.unwrap(), sequence);
+}
+
+/// Answers a single request to `/replay/playback` after the given delay
+async fn slow_server(delay: Duration) -> u16 {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    tokio::spawn(async move {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut request = [0; 1024];
+        let _ = stream.read(&mut request).await.unwrap();
+        tokio::time::sleep(delay).await;
+        let body =
+            json!({"length": 1800.0, "paused": true, "seeking": false, "speed": 1.0, "time": 0.0})
+                .to_string();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            body.len(),
+            body
+        );
+        let _ = stream.write_all(response.as_bytes()).await;
+    });
+    port
+}
+
+#[tokio::test]
+async fn replay_client_timeout() {
+    let ingame_client = |port| {
+        IngameClient::builder()
+            .secure(false)
+            .port(port)
+            .timeout(Duration::from_millis(50))
+            .build()
+            .unwrap()
+    };
+
+    // the short timeout of the ingame client is not used for the slower replay requests
+    let port = slow_server(Duration::from_millis(300)).await;
+    let client = ReplayClient::from_ingame_client(&ingame_client(port));
+    assert!(client.playback().await.unwrap().paused);
+
+    let port = slow_server(Duration::from_millis(300)).await;
+    let client =
+        ReplayClient::from_ingame_client(&ingame_client(port)).timeout(Duration::from_millis(50));
+    assert!(matches!(
+        client.playback().await,
+        Err(ReplayClientError::ConnectionError(_))
+    ));
+}
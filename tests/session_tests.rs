@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use serde_json::Value;
+use shaco::{
+    mock::{MockGame, MockGamePhase, MockIngameServer},
+    model::{ingame::IngameStreamEvent, session::SessionEvent},
+    rest::LCUClientInfo,
+    session::LeagueSession,
+    ws::LcuWebsocketClient,
+};
+use tokio::{net::TcpListener, sync::mpsc};
+use tokio_tungstenite::tungstenite::Message;
+
+fn phase_event(phase: &str) -> Message {
+    Message::text(format!(
+        r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {{"data": "{phase}", "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}}]"#
+    ))
+}
+
+async fn next_event(session: &mut LeagueSession) -> SessionEvent {
+    tokio::time::timeout(Duration::from_secs(2), session.next())
+        .await
+        .expect("no session event in time")
+        .unwrap()
+}
+
+/// Skips the events until one matches
+async fn wait_for<F: Fn(&SessionEvent) -> bool>(
+    session: &mut LeagueSession,
+    matches: F,
+) -> SessionEvent {
+    loop {
+        let event = next_event(session).await;
+        if matches(&event) {
+            return event;
+        }
+    }
+}
+
+#[tokio::test]
+async fn session_lifecycle() {
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    let ingame = MockIngameServer::start(MockGame::from_all_game_data(data).unwrap())
+        .await
+        .unwrap();
+    ingame.set_phase(MockGamePhase::NoGame);
+    ingame.set_game_time(f64::MAX);
+
+    // a fake League client that sends the phases it receives and closes the connection on None
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    let (phases_tx, mut phases_rx) = mpsc::unbounded_channel::<Option<&str>>();
+    let client = tokio::spawn(async move {
+        let mut subscriptions = Vec::new();
+        for _ in 0..2 {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+            subscriptions.push(ws.next().await.unwrap().unwrap().into_text().unwrap());
+            while let Some(Some(phase)) = phases_rx.recv().await {
+                ws.send(phase_event(phase)).await.unwrap();
+            }
+            ws.close(None).await.unwrap();
+        }
+        subscriptions
+    });
+
+    let mut session = LeagueSession::builder()
+        // the REST API of this port is not reachable, the phase is only known from the websocket
+        .client_info(LCUClientInfo {
+            port: 1,
+            token: String::new(),
+            remoting_port: 1,
+            remoting_token: String::new(),
+        })
+        .websocket(
+            LcuWebsocketClient::builder()
+                .secure(false)
+                .port(port)
+                .token("cmlvdDp0ZXN0"),
+        )
+        .ingame_client(ingame.client())
+        .discovery_interval(Duration::from_millis(20))
+        .polling_rate(Duration::from_millis(20))
+        .build()
+        .unwrap();
+
+    assert!(matches!(
+        next_event(&mut session).await,
+        SessionEvent::ClientConnected
+    ));
+    assert!(session.rest_client().is_some());
+    assert!(session.ws_handle().is_some());
+
+    phases_tx.send(Some("ChampSelect")).unwrap();
+    let SessionEvent::PhaseChanged(phase) = next_event(&mut session).await else {
+        panic!("expected a phase change");
+    };
+    assert_eq!(phase, "ChampSelect");
+    assert_eq!(session.phase().as_deref(), Some("ChampSelect"));
+
+    // the ingame API is only polled once the game is in progress
+    ingame.set_phase(MockGamePhase::InGame);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    phases_tx.send(Some("InProgress")).unwrap();
+    assert!(matches!(
+        next_event(&mut session).await,
+        SessionEvent::PhaseChanged(phase) if phase == "InProgress"
+    ));
+    assert!(matches!(
+        next_event(&mut session).await,
+        SessionEvent::Ingame(IngameStreamEvent::GameStarted)
+    ));
+    assert!(matches!(
+        next_event(&mut session).await,
+        SessionEvent::Ingame(IngameStreamEvent::GameEvent(_))
+    ));
+
+    // the game keeps being polled after the phase changed until it ended
+    phases_tx.send(Some("WaitingForStats")).unwrap();
+    wait_for(
+        &mut session,
+        |event| matches!(event, SessionEvent::PhaseChanged(phase) if phase == "WaitingForStats"),
+    )
+    .await;
+    ingame.set_phase(MockGamePhase::NoGame);
+    wait_for(&mut session, |event| {
+        matches!(event, SessionEvent::Ingame(IngameStreamEvent::GameEnded))
+    })
+    .await;
+
+    // a restarted client gets reconnected
+    phases_tx.send(None).unwrap();
+    assert!(matches!(
+        next_event(&mut session).await,
+        SessionEvent::ClientDisconnected
+    ));
+    assert!(session.phase().is_none());
+    assert!(matches!(
+        next_event(&mut session).await,
+        SessionEvent::ClientConnected
+    ));
+    phases_tx.send(None).unwrap();
+
+    let subscriptions = client.await.unwrap();
+    assert_eq!(
+        subscriptions,
+        ["[5, \"OnJsonApiEvent_lol-gameflow_v1_gameflow-phase\"]"; 2]
+    );
+}
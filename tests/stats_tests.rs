@@ -0,0 +1,96 @@
+use serde_json::{json, Value};
+use shaco::{
+    model::ingame::{AllGameData, DragonType, PlayerItem, TeamId},
+    stats::{game_summary, recipe_gold},
+};
+
+fn snapshot() -> Value {
+    serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap()
+}
+
+fn game_data(value: &Value) -> AllGameData {
+    serde_json::from_value(value.clone()).unwrap()
+}
+
+#[test]
+fn team_aggregates() {
+    let summary = game_summary(&game_data(&snapshot()));
+    assert_eq!(summary.teams.len(), 2);
+
+    let order = summary.team(&TeamId::Order).unwrap();
+    assert_eq!(order.kills, 80);
+    assert_eq!(order.creep_score, 380);
+    assert_eq!(order.recipe_gold, 24785);
+    // the obelisk destroyed by a summoner counts as well
+    assert_eq!(order.turrets, 5);
+    assert_eq!(order.inhibitors, 3);
+
+    let chaos = summary.team(&TeamId::Chaos).unwrap();
+    assert_eq!(chaos.kills, 56);
+    assert_eq!(chaos.recipe_gold, 22965);
+    assert_eq!(chaos.turrets, 2);
+    assert_eq!(chaos.inhibitors, 1);
+}
+
+#[test]
+fn objectives_from_events() {
+    let mut data = snapshot();
+    let events = data["events"]["Events"].as_array_mut().unwrap();
+    events.push(json!({
+        "Assisters": [],
+        "DragonType": "Water",
+        "EventID": 1000,
+        "EventName": "DragonKill",
+        "EventTime": 1700.0,
+        "KillerName": "summonerName7",
+        "Stolen": "False"
+    }));
+    events.push(json!({
+        "Assisters": [],
+        "EventID": 1001,
+        "EventName": "BaronKill",
+        "EventTime": 1710.0,
+        "KillerName": "summonerName3",
+        "Stolen": "True"
+    }));
+    let summary = game_summary(&game_data(&data));
+
+    let chaos = summary.team(&TeamId::Chaos).unwrap();
+    assert_eq!(chaos.dragons, vec![DragonType::Ocean]);
+    assert_eq!(chaos.barons, 0);
+    assert_eq!(summary.team(&TeamId::Order).unwrap().barons, 1);
+}
+
+#[test]
+fn player_ratios() {
+    let summary = game_summary(&game_data(&snapshot()));
+    assert_eq!(summary.players.len(), 10);
+
+    // 5 kills, 8 deaths, 67 assists and 90 cs after 1741.5s
+    let player = summary.player("summonerName1").unwrap();
+    assert_eq!(player.kda, 72.0 / 8.0);
+    assert_eq!(player.kill_participation, Some(72.0 / 80.0));
+    assert!((player.cs_per_minute - 3.1).abs() < 0.01);
+    assert_eq!(player.recipe_gold, 6580);
+}
+
+#[test]
+fn recipe_gold_sums_the_recipe_costs() {
+    let item = |item_id: i32, price: i32, count: i32| -> PlayerItem {
+        serde_json::from_value(json!({
+            "canUse": false,
+            "consumable": false,
+            "count": count,
+            "displayName": "",
+            "itemID": item_id,
+            "price": price,
+            "rawDescription": "",
+            "rawDisplayName": "",
+            "slot": 0
+        }))
+        .unwrap()
+    };
+    // the API reports the combine cost of completed items, e.g. 625 for Infinity Edge
+    let items = [item(3031, 625, 1), item(2003, 50, 3)];
+    assert_eq!(recipe_gold(&items), 775);
+}
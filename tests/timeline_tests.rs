@@ -0,0 +1,194 @@
+use std::{io::Read, time::Duration};
+
+use flate2::read::GzDecoder;
+use serde_json::{json, Value};
+use shaco::{
+    mock::{MockGame, MockGamePhase, MockIngameServer},
+    model::{ingame::GameEvent, timeline::TimelineEntry},
+    timeline::{Timeline, TimelineOptions, TimelineRecorder, TimelineWriter},
+};
+
+fn response(game_time: f64, kills: i32, events: &[Value]) -> Value {
+    let mut data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    data["gameData"]["gameTime"] = json!(game_time);
+    data["allPlayers"][0]["scores"]["kills"] = json!(kills);
+    data["events"]["Events"] = json!(events);
+    data
+}
+
+fn event(id: u32, time: f64) -> Value {
+    json!({ "EventID": id, "EventName": "MinionsSpawning", "EventTime": time })
+}
+
+fn entries(bytes: &[u8]) -> Vec<TimelineEntry> {
+    let mut text = String::new();
+    GzDecoder::new(bytes).read_to_string(&mut text).unwrap();
+    text.lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+fn write_game(options: TimelineOptions) -> Vec<u8> {
+    let mut writer = TimelineWriter::new(Vec::new(), options);
+    writer.write(&response(0.5, 0, &[event(0, 0.1)])).unwrap();
+    writer
+        .write(&response(30.0, 1, &[event(0, 0.1), event(1, 20.0)]))
+        .unwrap();
+    writer
+        .write(&response(65.0, 3, &[event(0, 0.1), event(1, 20.0)]))
+        .unwrap();
+    writer
+        .write(&response(
+            70.0,
+            4,
+            &[event(0, 0.1), event(1, 20.0), event(2, 68.0)],
+        ))
+        .unwrap();
+    writer.finish().unwrap()
+}
+
+#[test]
+fn snapshots_deltas_and_events() {
+    let entries = entries(&write_game(TimelineOptions::default()));
+    let kinds: Vec<&str> = entries
+        .iter()
+        .map(|entry| match entry {
+            TimelineEntry::Snapshot { .. } => "snapshot",
+            TimelineEntry::Delta { .. } => "delta",
+            TimelineEntry::Event { .. } => "event",
+        })
+        .collect();
+    assert_eq!(
+        kinds,
+        ["snapshot", "event", "delta", "event", "snapshot", "delta", "event"]
+    );
+
+    // only the game time and the kills changed
+    let TimelineEntry::Delta { patch, .. } = &entries[2] else {
+        panic!("expected a delta");
+    };
+    assert_eq!(patch.len(), 2);
+}
+
+#[test]
+fn reconstruct_game_data() {
+    let timeline = Timeline::from_reader(&write_game(TimelineOptions::default())[..]).unwrap();
+    assert_eq!(timeline.len(), 4);
+    assert_eq!(
+        timeline.game_times().collect::<Vec<_>>(),
+        [0.5, 30.0, 65.0, 70.0]
+    );
+
+    assert!(timeline.state_at(0.0).unwrap().is_none());
+    for (game_time, kills, events) in [(0.5, 0, 1), (45.0, 1, 2), (65.0, 3, 2), (100.0, 4, 3)] {
+        let data = timeline.state_at(game_time).unwrap().unwrap();
+        assert_eq!(data.all_players[0].scores.kills, kills);
+        assert_eq!(data.events.len(), events);
+    }
+
+    let raw = timeline.raw_state_at(30.0).unwrap().unwrap();
+    assert_eq!(raw, response(30.0, 1, &[event(0, 0.1), event(1, 20.0)]));
+
+    let kills: Vec<i32> = timeline
+        .frames()
+        .map(|data| data.unwrap().all_players[0].scores.kills)
+        .collect();
+    assert_eq!(kills, [0, 1, 3, 4]);
+    assert!(matches!(
+        timeline.events().unwrap()[2],
+        GameEvent::MinionsSpawning(_)
+    ));
+}
+
+#[test]
+fn read_unfinished_timeline() {
+    let mut writer = TimelineWriter::new(Vec::new(), TimelineOptions::default());
+    writer.write(&response(0.5, 0, &[])).unwrap();
+    writer.write(&response(1.5, 1, &[])).unwrap();
+    // strip the gzip trailer like in a recording that got killed before it was finished
+    let bytes = writer.finish().unwrap();
+    let unfinished = &bytes[..bytes.len() - 8];
+
+    let timeline = Timeline::from_reader(unfinished).unwrap();
+    assert_eq!(timeline.len(), 2);
+}
+
+#[tokio::test]
+async fn record_mock_game() {
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    let server = MockIngameServer::start(MockGame::from_all_game_data(data).unwrap())
+        .await
+        .unwrap();
+    server.set_phase(MockGamePhase::NoGame);
+
+    let writer = TimelineWriter::new(
+        Vec::new(),
+        TimelineOptions {
+            snapshot_interval: 5.0,
+            ..Default::default()
+        },
+    );
+    let recorder = TimelineRecorder::new(
+        server.client(),
+        writer,
+        Some(Duration::from_millis(20)),
+        None,
+    );
+
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    server.set_phase(MockGamePhase::InGame);
+    server.set_time_scale(50.0);
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    server.set_phase(MockGamePhase::NoGame);
+
+    let bytes = tokio::time::timeout(Duration::from_secs(2), recorder.finished())
+        .await
+        .unwrap()
+        .unwrap();
+    let timeline = Timeline::from_reader(&bytes[..]).unwrap();
+    assert!(timeline.len() > 3);
+
+    let game_times: Vec<f64> = timeline.game_times().collect();
+    assert!(game_times.windows(2).all(|w| w[0] <= w[1]));
+    let last = timeline
+        .state_at(*game_times.last().unwrap())
+        .unwrap()
+        .unwrap();
+    assert_eq!(last.all_players.len(), 10);
+    assert!(!last.events.is_empty());
+}
+
+#[tokio::test]
+async fn record_mock_game_survives_outage() {
+    let data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    let server = MockIngameServer::start(MockGame::from_all_game_data(data).unwrap())
+        .await
+        .unwrap();
+    server.set_game_time(100.0);
+
+    let writer = TimelineWriter::new(Vec::new(), TimelineOptions::default());
+    let recorder = TimelineRecorder::new(
+        server.client(),
+        writer,
+        Some(Duration::from_millis(20)),
+        Some(10),
+    );
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    // the API is unreachable for a few polls in the middle of the game
+    server.set_phase(MockGamePhase::NoGame);
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    server.set_game_time(200.0);
+    server.set_phase(MockGamePhase::InGame);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    server.set_phase(MockGamePhase::NoGame);
+
+    let bytes = tokio::time::timeout(Duration::from_secs(2), recorder.finished())
+        .await
+        .unwrap()
+        .unwrap();
+    let timeline = Timeline::from_reader(&bytes[..]).unwrap();
+    let game_times: Vec<f64> = timeline.game_times().collect();
+    assert!(game_times.first().unwrap() < &200.0);
+    assert!(game_times.last().unwrap() >= &200.0);
+}
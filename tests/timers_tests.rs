@@ -0,0 +1,240 @@
+use std::time::Duration;
+
+use futures_util::StreamExt;
+use serde_json::{json, Value};
+use shaco::{
+    mock::{MockGame, MockGamePhase, MockIngameServer},
+    model::{
+        ingame::{AllGameData, DragonType, TeamId},
+        timers::{Objective, ObjectiveBuff, TimerNotification},
+    },
+    timers::{ObjectiveTimerStream, ObjectiveTracker},
+};
+
+/// A Summoner's Rift snapshot at the given game time with the given events
+fn snapshot(game_time: f64, events: &[Value]) -> AllGameData {
+    serde_json::from_value(raw_snapshot(game_time, events)).unwrap()
+}
+
+fn raw_snapshot(game_time: f64, events: &[Value]) -> Value {
+    let mut data: Value = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    data["gameData"]["gameMode"] = json!("CLASSIC");
+    data["gameData"]["mapName"] = json!("Map11");
+    data["gameData"]["mapNumber"] = json!(11);
+    data["gameData"]["gameTime"] = json!(game_time);
+    data["events"] = json!({ "Events": events });
+    data
+}
+
+fn dragon_kill(id: u32, time: f64, dragon_type: &str, killer: &str) -> Value {
+    json!({
+        "Assisters": [],
+        "DragonType": dragon_type,
+        "EventID": id,
+        "EventName": "DragonKill",
+        "EventTime": time,
+        "KillerName": killer,
+        "Stolen": "False"
+    })
+}
+
+#[test]
+fn first_snapshot_has_no_notifications() {
+    let mut tracker = ObjectiveTracker::default();
+    assert!(tracker.update(&snapshot(1500.0, &[])).is_empty());
+
+    let timers = tracker.timers();
+    assert!(timers.is_alive(Objective::Dragon));
+    assert!(timers.is_alive(Objective::Baron));
+    // the herald leaves the map before baron spawns
+    assert!(timers.herald.is_none());
+}
+
+#[test]
+fn spawn_notifications() {
+    let mut tracker = ObjectiveTracker::default();
+    tracker.update(&snapshot(200.0, &[]));
+
+    let notifications = tracker.update(&snapshot(245.0, &[]));
+    assert!(matches!(
+        notifications.as_slice(),
+        [TimerNotification::SpawningSoon {
+            objective: Objective::Dragon,
+            spawns_at
+        }] if *spawns_at == 300.0
+    ));
+    // every notification is only emitted once
+    assert!(tracker.update(&snapshot(250.0, &[])).is_empty());
+
+    let notifications = tracker.update(&snapshot(301.0, &[]));
+    assert!(matches!(
+        notifications.as_slice(),
+        [TimerNotification::Spawned {
+            objective: Objective::Dragon
+        }]
+    ));
+
+    let kill = dragon_kill(0, 320.0, "Fire", "summonerName1");
+    assert!(tracker.update(&snapshot(321.0, &[kill])).is_empty());
+    let timers = tracker.timers();
+    assert!(!timers.is_alive(Objective::Dragon));
+    assert_eq!(timers.dragon.as_ref().unwrap().spawns_at, 620.0);
+    assert_eq!(timers.order_dragons.dragons, vec![DragonType::Infernal]);
+}
+
+#[test]
+fn dragon_soul_and_elder() {
+    let mut tracker = ObjectiveTracker::default();
+    tracker.update(&snapshot(100.0, &[]));
+
+    let events: Vec<Value> = ["Fire", "Water", "Earth", "Earth"]
+        .iter()
+        .enumerate()
+        .map(|(i, dragon_type)| {
+            dragon_kill(
+                i as u32,
+                300.0 * (i + 1) as f64,
+                dragon_type,
+                "summonerName7",
+            )
+        })
+        .collect();
+    let notifications = tracker.update(&snapshot(1201.0, &events));
+    assert!(notifications.iter().any(|n| matches!(
+        n,
+        TimerNotification::DragonSoulClaimed {
+            team: TeamId::Chaos,
+            soul: DragonType::Mountain
+        }
+    )));
+
+    let timers = tracker.timers();
+    assert_eq!(
+        timers.dragon_soul_progress(&TeamId::Chaos).unwrap().soul,
+        Some(DragonType::Mountain)
+    );
+    let dragon = timers.dragon.as_ref().unwrap();
+    assert_eq!(dragon.objective, Objective::ElderDragon);
+    assert_eq!(dragon.spawns_at, 1560.0);
+
+    let mut events = events;
+    events.push(dragon_kill(4, 1600.0, "Elder", "summonerName2"));
+    tracker.update(&snapshot(1601.0, &events));
+    let buff = &tracker.timers().buffs[0];
+    assert_eq!(buff.buff, ObjectiveBuff::Elder);
+    assert_eq!(buff.team, TeamId::Order);
+    assert_eq!(buff.expires_at, 1750.0);
+
+    let notifications = tracker.update(&snapshot(1751.0, &events));
+    assert!(notifications.iter().any(|n| matches!(
+        n,
+        TimerNotification::BuffExpired {
+            buff: ObjectiveBuff::Elder,
+            team: TeamId::Order
+        }
+    )));
+    assert!(tracker.timers().buffs.is_empty());
+}
+
+#[test]
+fn inhibitor_respawn() {
+    let mut tracker = ObjectiveTracker::default();
+    tracker.update(&snapshot(1000.0, &[]));
+
+    let mut events = vec![json!({
+        "Assisters": [],
+        "EventID": 0,
+        "EventName": "InhibKilled",
+        "EventTime": 1100.0,
+        "InhibKilled": "Barracks_T2_L1",
+        "KillerName": "summonerName1"
+    })];
+    tracker.update(&snapshot(1101.0, &events));
+    assert_eq!(tracker.timers().inhibitors[0].respawns_at, 1400.0);
+
+    events.push(json!({
+        "EventID": 1,
+        "EventName": "InhibRespawningSoon",
+        "EventTime": 1340.0,
+        "InhibRespawningSoon": "Barracks_T2_L1"
+    }));
+    // baron spawned in the meantime
+    let notifications = tracker.update(&snapshot(1341.0, &events));
+    assert!(matches!(
+        notifications.as_slice(),
+        [
+            TimerNotification::InhibitorRespawningSoon { inhibitor, respawns_at },
+            TimerNotification::Spawned { objective: Objective::Baron },
+        ] if inhibitor.raw == "Barracks_T2_L1" && *respawns_at == 1400.0
+    ));
+
+    events.push(json!({
+        "EventID": 2,
+        "EventName": "InhibRespawned",
+        "EventTime": 1400.0,
+        "InhibRespawned": "Barracks_T2_L1"
+    }));
+    let notifications = tracker.update(&snapshot(1401.0, &events));
+    assert!(matches!(
+        notifications.as_slice(),
+        [TimerNotification::InhibitorRespawned { .. }]
+    ));
+    assert!(tracker.timers().inhibitors.is_empty());
+}
+
+#[test]
+fn no_objectives_on_aram() {
+    let mut tracker = ObjectiveTracker::default();
+    let data: AllGameData = serde_json::from_str(include_str!("aram_allgamedata1.json")).unwrap();
+    tracker.update(&data);
+    assert_eq!(tracker.timers().upcoming().count(), 0);
+    assert!(tracker.timers().dragon.is_none());
+}
+
+async fn next_notification(stream: &mut ObjectiveTimerStream) -> TimerNotification {
+    tokio::time::timeout(Duration::from_secs(5), stream.next())
+        .await
+        .unwrap()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn timer_stream_retries_failed_requests() {
+    let game = MockGame::from_all_game_data(raw_snapshot(0.0, &[])).unwrap();
+    let server = MockIngameServer::start(game).await.unwrap();
+    server.set_game_time(200.0);
+
+    let mut stream = ObjectiveTimerStream::from_ingame_client(
+        server.client(),
+        Some(Duration::from_millis(10)),
+        None,
+        Some(20),
+    );
+    // start the stream, the first snapshot has no notifications
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), stream.next())
+            .await
+            .is_err()
+    );
+
+    server.set_game_time(245.0);
+    assert!(matches!(
+        next_notification(&mut stream).await,
+        TimerNotification::SpawningSoon {
+            objective: Objective::Dragon,
+            ..
+        }
+    ));
+
+    // a few failed requests in a row don't end the stream
+    server.set_phase(MockGamePhase::NoGame);
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    server.set_game_time(301.0);
+    server.set_phase(MockGamePhase::InGame);
+    assert!(matches!(
+        next_notification(&mut stream).await,
+        TimerNotification::Spawned {
+            objective: Objective::Dragon
+        }
+    ));
+}
@@ -0,0 +1,209 @@
+use std::time::Duration;
+
+use futures_util::{SinkExt, StreamExt};
+use shaco::{model::ws::LcuSubscriptionType, ws::LcuWebsocketClient};
+use tokio::net::TcpListener;
+use tokio_tungstenite::tungstenite::{
+    handshake::server::{Request, Response},
+    Message,
+};
+
+#[tokio::test]
+#[allow(clippy::result_large_err)]
+async fn builder_connects_with_explicit_credentials() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut authorization = None;
+        let mut ws = tokio_tungstenite::accept_hdr_async(stream, |req: &Request, res: Response| {
+            authorization = req
+                .headers()
+                .get("Authorization")
+                .map(|h| h.to_str().unwrap().to_string());
+            Ok(res)
+        })
+        .await
+        .unwrap();
+
+        let subscription = ws.next().await.unwrap().unwrap();
+        ws.send(Message::text(
+            r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {"data": "Lobby", "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}]"#,
+        ))
+        .await
+        .unwrap();
+
+        (authorization, subscription)
+    });
+
+    let mut client = LcuWebsocketClient::builder()
+        .secure(false)
+        .port(port)
+        .token("cmlvdDp0ZXN0")
+        .connect_timeout(Duration::from_secs(5))
+        .connect()
+        .await
+        .unwrap();
+    client
+        .subscribe(LcuSubscriptionType::JsonApiEvent(
+            "/lol-gameflow/v1/gameflow-phase".to_string(),
+        ))
+        .await
+        .unwrap();
+
+    let event = client.next().await.unwrap();
+    assert_eq!(
+        event.subscription_type,
+        LcuSubscriptionType::JsonApiEvent("/lol-gameflow/v1/gameflow-phase".to_string())
+    );
+
+    let (authorization, subscription) = server.await.unwrap();
+    assert_eq!(authorization.as_deref(), Some("Basic cmlvdDp0ZXN0"));
+    assert_eq!(
+        subscription.into_text().unwrap(),
+        "[5, \"OnJsonApiEvent_lol-gameflow_v1_gameflow-phase\"]"
+    );
+}
+
+#[tokio::test]
+async fn builder_fails_without_server() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+    drop(listener);
+
+    let result = LcuWebsocketClient::builder()
+        .secure(false)
+        .port(port)
+        .token("cmlvdDp0ZXN0")
+        .connect_timeout(Duration::from_secs(5))
+        .connect()
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn split_client() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let mut received = Vec::new();
+
+        while let Some(Ok(message)) = ws.next().await {
+            let Message::Text(text) = message else {
+                continue;
+            };
+            let response = match text.as_str() {
+                r#"[5, "OnJsonApiEvent"]"# => {
+                    r#"[8, "OnJsonApiEvent", {"data": "Lobby", "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}]"#
+                }
+                r#"[2,"1","GET /lol-gameflow/v1/gameflow-phase"]"# => r#"[3, "1", "Lobby"]"#,
+                r#"[2,"2","GET /unknown"]"# => r#"[4, "2", "RPC_ERROR", "Invalid URI"]"#,
+                _ => "",
+            };
+            received.push(text);
+            if !response.is_empty() {
+                ws.send(Message::text(response)).await.unwrap();
+            }
+        }
+        received
+    });
+
+    let client = LcuWebsocketClient::builder()
+        .secure(false)
+        .port(port)
+        .token("cmlvdDp0ZXN0")
+        .connect()
+        .await
+        .unwrap();
+    let (handle, mut events) = client.split(Some(Duration::from_secs(60)));
+
+    // subscribe from a different task while the stream is read here
+    let subscribe_handle = handle.clone();
+    tokio::spawn(async move {
+        subscribe_handle
+            .subscribe(LcuSubscriptionType::AllJsonApiEvents)
+            .await
+            .unwrap();
+    })
+    .await
+    .unwrap();
+
+    let event = events.next().await.unwrap();
+    assert_eq!(
+        event.subscription_type,
+        LcuSubscriptionType::AllJsonApiEvents
+    );
+
+    let result = handle
+        .call("GET /lol-gameflow/v1/gameflow-phase", Vec::new())
+        .await
+        .unwrap();
+    assert_eq!(result, "Lobby");
+    assert!(handle.call("GET /unknown", Vec::new()).await.is_err());
+
+    handle.close().await.unwrap();
+    assert!(events.next().await.is_none());
+    assert!(handle.subscribe(LcuSubscriptionType::Log).await.is_err());
+
+    let received = server.await.unwrap();
+    assert_eq!(received.len(), 3);
+}
+
+#[tokio::test]
+async fn split_handle_outlives_event_stream() {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let port = listener.local_addr().unwrap().port();
+
+    let server = tokio::spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let mut closed = false;
+
+        while let Some(Ok(message)) = ws.next().await {
+            match message {
+                Message::Text(text)
+                    if text == r#"[2,"1","GET /lol-gameflow/v1/gameflow-phase"]"# =>
+                {
+                    ws.send(Message::text(r#"[8, "OnLog", "discarded"]"#))
+                        .await
+                        .unwrap();
+                    ws.send(Message::text(r#"[3, "1", "Lobby"]"#))
+                        .await
+                        .unwrap();
+                }
+                Message::Close(_) => closed = true,
+                _ => {}
+            }
+        }
+        closed
+    });
+
+    let client = LcuWebsocketClient::builder()
+        .secure(false)
+        .port(port)
+        .token("cmlvdDp0ZXN0")
+        .connect()
+        .await
+        .unwrap();
+    let (handle, events) = client.split(None);
+    drop(events);
+
+    let result = handle
+        .call("GET /lol-gameflow/v1/gameflow-phase", Vec::new())
+        .await
+        .unwrap();
+    assert_eq!(result, "Lobby");
+    assert!(handle.is_connected());
+
+    // the connection gets closed once nothing can use it anymore
+    drop(handle);
+    let closed = tokio::time::timeout(Duration::from_secs(5), server)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(closed);
+}
@@ -0,0 +1,91 @@
+use shaco::model::ws::{LcuEvent, LcuEventPayload, LcuSubscriptionType};
+
+#[test]
+fn subscription_type_round_trip() {
+    let vec = vec![
+        (LcuSubscriptionType::AllJsonApiEvents, "OnJsonApiEvent"),
+        (LcuSubscriptionType::AllLcdsEvents, "OnLcdsEvent"),
+        (
+            LcuSubscriptionType::JsonApiEvent("/lol-gameflow/v1/gameflow-phase".to_string()),
+            "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase",
+        ),
+        (
+            LcuSubscriptionType::LcdsEvent("/cn-1/summoner".to_string()),
+            "OnLcdsEvent_cn-1_summoner",
+        ),
+        (LcuSubscriptionType::Log, "OnLog"),
+        (
+            LcuSubscriptionType::RegionLocaleChanged,
+            "OnRegionLocaleChanged",
+        ),
+        (
+            LcuSubscriptionType::ServiceProxyAsyncEvent,
+            "OnServiceProxyAsyncEvent",
+        ),
+        (
+            LcuSubscriptionType::ServiceProxyMethodEvent,
+            "OnServiceProxyMethodEvent",
+        ),
+        (
+            LcuSubscriptionType::ServiceProxyUuidEvent,
+            "OnServiceProxyUuidEvent",
+        ),
+        (
+            LcuSubscriptionType::Other("OnSomeFutureEvent".to_string()),
+            "OnSomeFutureEvent",
+        ),
+    ];
+
+    vec.into_iter().for_each(|(subscription_type, topic)| {
+        assert_eq!(subscription_type.to_string(), topic);
+
+        let serialized = serde_json::to_string(&subscription_type).unwrap();
+        assert_eq!(serialized, format!("\"{topic}\""));
+
+        let deserialized: LcuSubscriptionType = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(deserialized, subscription_type);
+        assert_eq!(deserialized.to_string(), topic);
+    })
+}
+
+#[test]
+fn deserialize_json_api_event() {
+    let event: LcuEvent = serde_json::from_str(
+        r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {"data": "ChampSelect", "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}]"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        event.subscription_type,
+        LcuSubscriptionType::JsonApiEvent("/lol-gameflow/v1/gameflow-phase".to_string())
+    );
+    let LcuEventPayload::JsonApi(payload) = event.payload else {
+        panic!("expected a JsonApi payload");
+    };
+    assert_eq!(payload.data, "ChampSelect");
+    assert_eq!(payload.event_type, "Update");
+    assert_eq!(payload.uri, "/lol-gameflow/v1/gameflow-phase");
+}
+
+#[test]
+fn deserialize_lcds_event() {
+    let event: LcuEvent = serde_json::from_str(
+        r#"[8, "OnLcdsEvent", {"body": {"summonerId": 1}, "subtype": "update", "typeName": "com.riotgames.platform.summoner.SummonerNotification"}]"#,
+    )
+    .unwrap();
+
+    assert_eq!(event.subscription_type, LcuSubscriptionType::AllLcdsEvents);
+    let LcuEventPayload::Lcds(payload) = event.payload else {
+        panic!("expected a Lcds payload");
+    };
+    assert_eq!(payload.body["summonerId"], 1);
+    assert_eq!(payload.subtype.as_deref(), Some("update"));
+}
+
+#[test]
+fn deserialize_other_event() {
+    let event: LcuEvent = serde_json::from_str(r#"[8, "OnLog", "some log line"]"#).unwrap();
+
+    assert_eq!(event.subscription_type, LcuSubscriptionType::Log);
+    assert!(matches!(event.payload, LcuEventPayload::Other(_)));
+}
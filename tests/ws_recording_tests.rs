@@ -0,0 +1,91 @@
+use futures_util::{stream, StreamExt};
+use shaco::{
+    model::ws::{LcuEvent, LcuEventPayload, LcuSubscriptionType},
+    ws_recording::{LcuEventRecorder, LcuEventReplayer, ReplaySpeed},
+};
+
+fn test_events() -> Vec<LcuEvent> {
+    [
+        r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {"data": "Lobby", "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}]"#,
+        r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {"data": "ChampSelect", "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}]"#,
+        r#"[8, "OnLcdsEvent", {"body": {"summonerId": 1}, "subtype": "update", "typeName": "SummonerNotification"}]"#,
+        r#"[8, "OnLog", "some log line"]"#,
+    ]
+    .iter()
+    .map(|s| LcuEvent::from_message(s).unwrap())
+    .collect()
+}
+
+#[tokio::test]
+async fn record_and_replay() {
+    let mut recorder = LcuEventRecorder::new(stream::iter(test_events()), Vec::new());
+    let passed_through: Vec<LcuEvent> = (&mut recorder).collect().await;
+    assert_eq!(passed_through.len(), 4);
+    assert!(recorder.error().is_none());
+
+    let (_, recording) = recorder.into_inner().unwrap();
+    assert_eq!(recording.iter().filter(|b| **b == b'\n').count(), 4);
+
+    for speed in [
+        ReplaySpeed::Instant,
+        ReplaySpeed::Accelerated(100.0),
+        ReplaySpeed::RealTime,
+    ] {
+        let replayer = LcuEventReplayer::from_reader(recording.as_slice(), speed).unwrap();
+        assert_eq!(replayer.remaining(), 4);
+
+        let replayed: Vec<LcuEvent> = replayer.collect().await;
+        assert_eq!(replayed.len(), 4);
+
+        replayed
+            .iter()
+            .zip(passed_through.iter())
+            .for_each(|(replayed, original)| {
+                assert_eq!(replayed.subscription_type, original.subscription_type);
+                assert_eq!(
+                    serde_json::to_value(&replayed.payload).unwrap(),
+                    serde_json::to_value(&original.payload).unwrap()
+                );
+            });
+
+        assert_eq!(
+            replayed[1].subscription_type,
+            LcuSubscriptionType::JsonApiEvent("/lol-gameflow/v1/gameflow-phase".to_string())
+        );
+        assert!(matches!(replayed[2].payload, LcuEventPayload::Lcds(_)));
+        assert!(matches!(replayed[3].payload, LcuEventPayload::Other(_)));
+    }
+}
+
+#[tokio::test]
+async fn recording_keeps_unmodelled_fields() {
+    let message = r#"[8, "OnLcdsEvent", {"body": {"summonerId": 1}, "subtype": "update", "typeName": "SummonerNotification", "sequence": 7}]"#;
+    let events = vec![LcuEvent::from_message(message).unwrap()];
+
+    let mut recorder = LcuEventRecorder::new(stream::iter(events), Vec::new());
+    (&mut recorder).collect::<Vec<_>>().await;
+    let (_, recording) = recorder.into_inner().unwrap();
+
+    let replayed: Vec<LcuEvent> =
+        LcuEventReplayer::from_reader(recording.as_slice(), ReplaySpeed::Instant)
+            .unwrap()
+            .collect()
+            .await;
+    assert_eq!(replayed[0].raw_message(), Some(message));
+}
+
+#[test]
+fn replay_invalid_speed() {
+    for factor in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        let error = LcuEventReplayer::from_reader(&b""[..], ReplaySpeed::Accelerated(factor))
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidInput);
+    }
+}
+
+#[test]
+fn replay_invalid_recording() {
+    let recording = b"{\"timestamp\": 0, \"topic\": \"OnLog\"}\n";
+    assert!(LcuEventReplayer::from_reader(&recording[..], ReplaySpeed::Instant).is_err());
+}